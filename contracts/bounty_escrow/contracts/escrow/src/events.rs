@@ -1,6 +1,28 @@
-use soroban_sdk::{contracttype, symbol_short, Address, Env};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
 
 pub const EVENT_VERSION_V2: u32 = 2;
+/// Version of the events published under `versioned_topics`.
+pub const EVENT_VERSION_V3: u32 = 3;
+
+/// True once the admin has turned the lock, release and refund events off
+/// with `set_emit_events`.
+fn transfer_events_muted(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .has(&crate::DataKeyExt::TransferEventsMuted)
+}
+
+/// Topics of every event about one escrow: `("escrow", "v3", action, bounty_id)`. The version symbol matches the
+/// `version` field of the payload and changes whenever the payload does.
+/// See EVENTS.md for the schema.
+fn versioned_topics(action: Symbol, bounty_id: u64) -> (Symbol, Symbol, Symbol, u64) {
+    (
+        symbol_short!("escrow"),
+        symbol_short!("v3"),
+        action,
+        bounty_id,
+    )
+}
 
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -16,6 +38,45 @@ pub fn emit_bounty_initialized(env: &Env, event: BountyEscrowInitialized) {
     env.events().publish(topics, event.clone());
 }
 
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AdminTransferProposed {
+    pub current_admin: Address,
+    pub proposed_admin: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_admin_transfer_proposed(env: &Env, event: AdminTransferProposed) {
+    let topics = (symbol_short!("adm_prop"),);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AdminTransferred {
+    pub previous_admin: Address,
+    pub new_admin: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_admin_transferred(env: &Env, event: AdminTransferred) {
+    let topics = (symbol_short!("adm_xfer"),);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AdminTransferCancelled {
+    pub admin: Address,
+    pub cancelled_admin: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_admin_transfer_cancelled(env: &Env, event: AdminTransferCancelled) {
+    let topics = (symbol_short!("adm_cncl"),);
+    env.events().publish(topics, event.clone());
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct FundsLocked {
@@ -24,10 +85,131 @@ pub struct FundsLocked {
     pub amount: i128,
     pub depositor: Address,
     pub deadline: u64,
+    pub token: Address,
+    pub timestamp: u64,
 }
 
 pub fn emit_funds_locked(env: &Env, event: FundsLocked) {
-    let topics = (symbol_short!("f_lock"), event.bounty_id);
+    if transfer_events_muted(env) {
+        return;
+    }
+    let topics = versioned_topics(symbol_short!("lock"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FundsToppedUp {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub depositor: Address,
+    pub old_amount: i128,
+    pub new_amount: i128,
+    pub token: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_funds_topped_up(env: &Env, event: FundsToppedUp) {
+    let topics = versioned_topics(symbol_short!("top_up"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DeadlineExtended {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub old_deadline: u64,
+    pub new_deadline: u64,
+    pub extended_by: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_deadline_extended(env: &Env, event: DeadlineExtended) {
+    let topics = versioned_topics(symbol_short!("extend"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BountyAssigned {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub contributor: Address,
+    pub assigned_by: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_bounty_assigned(env: &Env, event: BountyAssigned) {
+    let topics = versioned_topics(symbol_short!("assign"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BountyUnassigned {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub contributor: Address,
+    pub unassigned_by: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_bounty_unassigned(env: &Env, event: BountyUnassigned) {
+    let topics = versioned_topics(symbol_short!("unassign"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+/// Emitted by `block_contributor` and `unblock_contributor`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ContributorBlocked {
+    pub version: u32,
+    pub contributor: Address,
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_contributor_blocked(env: &Env, event: ContributorBlocked) {
+    let topics = (symbol_short!("c_block"), event.contributor.clone());
+    env.events().publish(topics, event.clone());
+}
+
+pub fn emit_contributor_unblocked(env: &Env, event: ContributorBlocked) {
+    let topics = (symbol_short!("c_unblock"), event.contributor.clone());
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DisputeOpened {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub contributor: Address,
+    pub depositor: Address,
+    /// Amount held by the escrow while the dispute is open.
+    pub amount: i128,
+    pub token: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_dispute_opened(env: &Env, event: DisputeOpened) {
+    let topics = versioned_topics(symbol_short!("dispute"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DisputeResolved {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub ruling: crate::DisputeRuling,
+    pub arbiter: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_dispute_resolved(env: &Env, event: DisputeResolved) {
+    let topics = versioned_topics(symbol_short!("resolve"), event.bounty_id);
     env.events().publish(topics, event.clone());
 }
 
@@ -36,13 +218,48 @@ pub fn emit_funds_locked(env: &Env, event: FundsLocked) {
 pub struct FundsReleased {
     pub version: u32,
     pub bounty_id: u64,
+    pub depositor: Address,
+    /// Amount taken out of escrow, before the platform fee.
+    pub gross: i128,
+    /// Net amount received by the recipient, after the platform fee.
     pub amount: i128,
+    /// Platform fee deducted from the release; 0 when fees are disabled.
+    pub fee: i128,
     pub recipient: Address,
+    /// Release reason code; see EVENTS.md for the codes set by each path
+    pub reason: Symbol,
+    pub token: Address,
     pub timestamp: u64,
 }
 
 pub fn emit_funds_released(env: &Env, event: FundsReleased) {
-    let topics = (symbol_short!("f_rel"), event.bounty_id);
+    if transfer_events_muted(env) {
+        return;
+    }
+    let topics = versioned_topics(symbol_short!("release"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FundsReleasedSplit {
+    pub version: u32,
+    pub bounty_id: u64,
+    /// Total net amount received by all recipients.
+    pub amount: i128,
+    /// Total platform fee deducted across all legs.
+    pub fee: i128,
+    /// Net amount received by each recipient.
+    pub recipients: Vec<(Address, i128)>,
+    pub token: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_funds_released_split(env: &Env, event: FundsReleasedSplit) {
+    if transfer_events_muted(env) {
+        return;
+    }
+    let topics = versioned_topics(symbol_short!("split"), event.bounty_id);
     env.events().publish(topics, event.clone());
 }
 
@@ -52,12 +269,156 @@ pub struct FundsRefunded {
     pub version: u32,
     pub bounty_id: u64,
     pub amount: i128,
+    /// Depositor of the escrow; differs from `refund_to` when a refund address is set.
+    pub depositor: Address,
     pub refund_to: Address,
+    pub token: Address,
     pub timestamp: u64,
 }
 
 pub fn emit_funds_refunded(env: &Env, event: FundsRefunded) {
-    let topics = (symbol_short!("f_ref"), event.bounty_id);
+    if transfer_events_muted(env) {
+        return;
+    }
+    let topics = versioned_topics(symbol_short!("refund"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RefundRequested {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub depositor: Address,
+    pub reason_code: u32,
+    pub timestamp: u64,
+}
+
+pub fn emit_refund_requested(env: &Env, event: RefundRequested) {
+    let topics = versioned_topics(symbol_short!("ref_req"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RefundApproved {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub amount: i128,
+    pub recipient: Address,
+    pub mode: crate::RefundMode,
+    pub approved_by: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_refund_approved(env: &Env, event: RefundApproved) {
+    let topics = versioned_topics(symbol_short!("ref_appr"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RefundAddressSet {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub depositor: Address,
+    pub refund_to: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_refund_address_set(env: &Env, event: RefundAddressSet) {
+    let topics = versioned_topics(symbol_short!("ref_addr"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RefundRequestRejected {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub reason_code: u32,
+    pub timestamp: u64,
+}
+
+pub fn emit_refund_request_rejected(env: &Env, event: RefundRequestRejected) {
+    let topics = versioned_topics(symbol_short!("ref_rej"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReleaseQueued {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub recipient: Address,
+    pub amount: i128,
+    pub executable_at: u64,
+    pub timestamp: u64,
+}
+
+pub fn emit_release_queued(env: &Env, event: ReleaseQueued) {
+    let topics = versioned_topics(symbol_short!("rel_queue"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReleaseCancelled {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub recipient: Address,
+    pub amount: i128,
+    pub cancelled_by: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_release_cancelled(env: &Env, event: ReleaseCancelled) {
+    let topics = versioned_topics(symbol_short!("rel_cncl"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ClaimSubmitted {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub contributor: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_claim_submitted(env: &Env, event: ClaimSubmitted) {
+    let topics = versioned_topics(symbol_short!("clm_sub"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ClaimRejected {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub contributor: Address,
+    pub rejected_by: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_claim_rejected(env: &Env, event: ClaimRejected) {
+    let topics = versioned_topics(symbol_short!("clm_rej"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EscrowCancelled {
+    pub version: u32,
+    pub bounty_id: u64,
+    pub amount: i128,
+    pub depositor: Address,
+    pub token: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_escrow_cancelled(env: &Env, event: EscrowCancelled) {
+    let topics = versioned_topics(symbol_short!("cancel"), event.bounty_id);
     env.events().publish(topics, event.clone());
 }
 
@@ -92,6 +453,9 @@ pub struct BatchFundsLocked {
 }
 
 pub fn emit_batch_funds_locked(env: &Env, event: BatchFundsLocked) {
+    if transfer_events_muted(env) {
+        return;
+    }
     let topics = (symbol_short!("b_lock"),);
     env.events().publish(topics, event.clone());
 }
@@ -120,6 +484,9 @@ pub struct BatchFundsReleased {
 }
 
 pub fn emit_batch_funds_released(env: &Env, event: BatchFundsReleased) {
+    if transfer_events_muted(env) {
+        return;
+    }
     let topics = (symbol_short!("b_rel"),);
     env.events().publish(topics, event.clone());
 }
@@ -127,6 +494,7 @@ pub fn emit_batch_funds_released(env: &Env, event: BatchFundsReleased) {
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct ApprovalAdded {
+    pub version: u32,
     pub bounty_id: u64,
     pub contributor: Address,
     pub approver: Address,
@@ -134,31 +502,30 @@ pub struct ApprovalAdded {
 }
 
 pub fn emit_approval_added(env: &Env, event: ApprovalAdded) {
-    let topics = (symbol_short!("approval"), event.bounty_id);
+    let topics = versioned_topics(symbol_short!("approval"), event.bounty_id);
     env.events().publish(topics, event.clone());
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ClaimCreated {
-    pub bounty_id: u64, // use program_id+schedule_id equivalent in program-escrow
+    pub version: u32,
+    pub bounty_id: u64,
     pub recipient: Address,
     pub amount: i128,
     pub expires_at: u64,
+    pub timestamp: u64,
 }
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ClaimExecuted {
-    pub bounty_id: u64,
-    pub recipient: Address,
-    pub amount: i128,
-    pub claimed_at: u64,
+pub fn emit_claim_created(env: &Env, event: ClaimCreated) {
+    let topics = versioned_topics(symbol_short!("clm_new"), event.bounty_id);
+    env.events().publish(topics, event.clone());
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ClaimCancelled {
+    pub version: u32,
     pub bounty_id: u64,
     pub recipient: Address,
     pub amount: i128,
@@ -166,7 +533,59 @@ pub struct ClaimCancelled {
     pub cancelled_by: Address,
 }
 
+pub fn emit_claim_cancelled(env: &Env, event: ClaimCancelled) {
+    let topics = versioned_topics(symbol_short!("clm_cncl"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
 pub fn emit_pause_state_changed(env: &Env, event: crate::PauseStateChanged) {
     let topics = (symbol_short!("pause"), event.operation.clone());
     env.events().publish(topics, event);
 }
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ContractPauseToggled {
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+/// A running stat was about to go below zero and was held at zero instead,
+/// which means an earlier update was missed. `value` is the stat before the
+/// change that was refused.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StatsAnomaly {
+    pub stat: Symbol,
+    pub subject: Address,
+    pub value: i128,
+    pub change: i128,
+    pub timestamp: u64,
+}
+
+pub fn emit_stats_anomaly(env: &Env, event: StatsAnomaly) {
+    env.events()
+        .publish((symbol_short!("anomaly"), event.stat.clone()), event);
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EventEmissionToggled {
+    pub enabled: bool,
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+/// Published whichever way the toggle goes, so indexers can tell a gap in
+/// the lock, release and refund events from a quiet period.
+pub fn emit_event_emission_toggled(env: &Env, event: EventEmissionToggled) {
+    env.events().publish((symbol_short!("evt_emit"),), event);
+}
+
+pub fn emit_contract_paused(env: &Env, event: ContractPauseToggled) {
+    env.events().publish((symbol_short!("paused"),), event);
+}
+
+pub fn emit_contract_unpaused(env: &Env, event: ContractPauseToggled) {
+    env.events().publish((symbol_short!("unpaused"),), event);
+}
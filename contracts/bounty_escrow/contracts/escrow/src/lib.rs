@@ -7,14 +7,19 @@ mod test_bounty_escrow;
 mod test_rbac;
 
 use events::{
-    emit_batch_funds_locked, emit_batch_funds_released, emit_bounty_initialized, emit_funds_locked,
-    emit_funds_refunded, emit_funds_released, BatchFundsLocked, BatchFundsReleased,
-    BountyEscrowInitialized, ClaimCancelled, ClaimCreated, ClaimExecuted, FundsLocked,
-    FundsRefunded, FundsReleased, EVENT_VERSION_V2,
+    emit_batch_funds_locked, emit_batch_funds_released, emit_bounty_initialized,
+    emit_claim_cancelled, emit_claim_created, emit_claim_rejected, emit_claim_submitted,
+    emit_contributor_blocked, emit_contributor_unblocked, emit_deadline_extended,
+    emit_escrow_cancelled, emit_funds_locked, emit_funds_refunded, emit_funds_released,
+    emit_funds_released_split, emit_funds_topped_up, emit_release_cancelled, emit_release_queued,
+    BatchFundsLocked, BatchFundsReleased, BountyEscrowInitialized, ClaimCancelled, ClaimCreated,
+    ClaimRejected, ClaimSubmitted, ContributorBlocked, DeadlineExtended, EscrowCancelled,
+    FundsLocked, FundsRefunded, FundsReleased, FundsReleasedSplit, FundsToppedUp, ReleaseCancelled,
+    ReleaseQueued, EVENT_VERSION_V2, EVENT_VERSION_V3,
 };
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token, vec, Address, Env,
-    Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, log, panic_with_error, symbol_short,
+    token, vec, Address, Bytes, BytesN, Env, Symbol, Vec, I256,
 };
 
 // ==================== MONITORING MODULE ====================
@@ -208,6 +213,7 @@ mod monitoring {
 
 // ==================== ANTI-ABUSE MODULE ====================
 mod anti_abuse {
+    use crate::Error;
     use soroban_sdk::{contracttype, symbol_short, Address, Env};
 
     #[contracttype]
@@ -233,6 +239,7 @@ mod anti_abuse {
         State(Address),
         Whitelist(Address),
         Admin,
+        ExemptPrivileged, // bool — the admin and allowlisted depositors skip the limit
     }
 
     pub fn get_config(env: &Env) -> AntiAbuseConfig {
@@ -272,13 +279,32 @@ mod anti_abuse {
         env.storage().instance().get(&AntiAbuseKey::Admin)
     }
 
+    pub fn exempts_privileged(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&AntiAbuseKey::ExemptPrivileged)
+            .unwrap_or(false)
+    }
+
+    pub fn set_exempt_privileged(env: &Env, exempt: bool) {
+        env.storage()
+            .instance()
+            .set(&AntiAbuseKey::ExemptPrivileged, &exempt);
+    }
+
+    pub fn get_state(env: &Env, address: Address) -> Option<AddressState> {
+        env.storage()
+            .persistent()
+            .get(&AntiAbuseKey::State(address))
+    }
+
     pub fn set_admin(env: &Env, admin: Address) {
         env.storage().instance().set(&AntiAbuseKey::Admin, &admin);
     }
 
-    pub fn check_rate_limit(env: &Env, address: Address) {
+    pub fn check_rate_limit(env: &Env, address: Address) -> Result<(), Error> {
         if is_whitelisted(env, address.clone()) {
-            return;
+            return Ok(());
         }
 
         let config = get_config(env);
@@ -306,7 +332,7 @@ mod anti_abuse {
                 (symbol_short!("abuse"), symbol_short!("cooldown")),
                 (address.clone(), now),
             );
-            panic!("Operation in cooldown period");
+            return Err(Error::RateLimitExceeded);
         }
 
         // 2. Window check
@@ -325,7 +351,7 @@ mod anti_abuse {
                     (symbol_short!("abuse"), symbol_short!("limit")),
                     (address.clone(), now),
                 );
-                panic!("Rate limit exceeded");
+                return Err(Error::RateLimitExceeded);
             }
             state.operation_count += 1;
         }
@@ -335,14 +361,27 @@ mod anti_abuse {
 
         // Extend TTL for state (approx 1 day)
         env.storage().persistent().extend_ttl(&key, 17280, 17280);
+        Ok(())
     }
 }
 // ==================== END ANTI-ABUSE MODULE ====================
 
 // ==================== CONSTANTS ====================
 const BASIS_POINTS: i128 = 10_000;
-const MAX_FEE_RATE: i128 = 5_000; // 50% max fee
+const MAX_FEE_RATE: i128 = 5_000; // 50% max lock fee
+const MAX_PROTOCOL_FEE_BPS: i128 = 1_000; // 10% cap on the release fee, whichever setter is used
 const MAX_BATCH_SIZE: u32 = 20;
+const MAX_PAGE_SIZE: u32 = 100;
+const MAX_INFO_BATCH_SIZE: u32 = 50;
+const MAX_SORTED_RESULTS: u32 = 100; // matches query_escrows will sort by amount or deadline
+const MAX_TOP_CONTRIBUTORS: u32 = 10;
+const SECONDS_PER_DAY: u64 = 86_400;
+const MAX_DAILY_RANGE: u64 = 90; // days get_daily_stats_range returns per call
+const MAX_ASSIGNMENT_HISTORY: u32 = 20;
+const MAX_ESCROW_HISTORY: u32 = 200; // entries get_escrow_history keeps per bounty
+const MAX_REFUND_SCAN: u32 = 50; // EscrowIndex entries refund_expired looks at per call
+const EMERGENCY_REFUND_WINDOW: u64 = 86_400; // one ledger-day, in seconds
+const DEFAULT_MAX_EMERGENCY_REFUNDS: u32 = 5;
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -350,29 +389,107 @@ const MAX_BATCH_SIZE: u32 = 20;
 pub enum Error {
     AlreadyInitialized = 1,
     NotInitialized = 2,
-    BountyExists = 3,
+    /// Returned when an escrow record already exists for the bounty_id,
+    /// whether it is Locked, Released, or Refunded
+    BountyAlreadyExists = 3,
     BountyNotFound = 4,
     FundsNotLocked = 5,
     DeadlineNotPassed = 6,
     Unauthorized = 7,
     InvalidFeeRate = 8,
-    FeeRecipientNotSet = 9,
+    // 9 (FeeRecipientNotSet) and 11 (BatchSizeMismatch) were never returned
+    // and were retired to make room under the 50-case cap noted below.
     InvalidBatchSize = 10,
-    BatchSizeMismatch = 11,
     DuplicateBountyId = 12,
     /// Returned when amount is invalid (zero, negative, or exceeds available)
     InvalidAmount = 13,
     /// Returned when deadline is invalid (in the past or too far in the future)
     InvalidDeadline = 14,
-    /// Returned when contract has insufficient funds for the operation
-    InsufficientFunds = 16,
-    /// Returned when refund is attempted without admin approval
-    RefundNotApproved = 17,
-    FundsPaused = 18,
+    /// Returned when a release asks for more than the escrow still holds
+    InsufficientEscrowBalance = 16,
+    // 17 (RefundNotApproved) was never returned either.
+    // 18 was FundsPaused, replaced by LockPaused, ReleasePaused and RefundPaused.
+    // Soroban caps an error enum at 50 cases, so retired codes are not kept.
     /// Returned when lock amount is below the configured policy minimum (Issue #62)
     AmountBelowMinimum = 19,
     /// Returned when lock amount is above the configured policy maximum (Issue #62)
     AmountAboveMaximum = 20,
+    /// Returned when split payout amounts do not sum to the locked amount
+    SplitAmountMismatch = 21,
+    /// Returned when a milestone index is out of range for the escrow
+    MilestoneNotFound = 22,
+    /// Returned when a milestone has already been paid out
+    MilestoneAlreadyReleased = 23,
+    /// Returned when an arbiter-only call targets an escrow without an arbiter
+    ArbiterNotSet = 24,
+    /// Returned when cancelling a bounty that already has a contributor lined up
+    ContributorAssigned = 25,
+    /// Returned by lock and release paths while the admin has paused the contract
+    ContractPaused = 26,
+    /// Returned when a pull-based claim is attempted after its claim window
+    ClaimExpired = 27,
+    /// Returned when accepting or cancelling an admin transfer that was not proposed
+    NotPendingAdmin = 28,
+    /// Returned by lock paths while `lock_paused` is set
+    LockPaused = 29,
+    /// Returned by release paths while `release_paused` is set
+    ReleasePaused = 30,
+    /// Returned by refund paths while `refund_paused` is set
+    RefundPaused = 31,
+    /// Returned when a guarded entrypoint is re-entered mid-call
+    ReentrancyDetected = 32,
+    /// Returned by `lock_funds_native` before the admin has set the native asset contract
+    NativeTokenNotSet = 33,
+    /// Returned when `require_assignment` is on and funds go to someone other than the assignee
+    NotAssignedContributor = 34,
+    /// Returned by release and refund paths while the escrow is under dispute
+    EscrowDisputed = 35,
+    /// Returned when the depositor already has a refund request awaiting the admin
+    RefundAlreadyRequested = 36,
+    /// Returned when approving or rejecting a refund request that does not exist
+    RefundNotRequested = 37,
+    /// Returned when requesting an early refund after the deadline has passed
+    DeadlinePassed = 38,
+    /// Returned by `emergency_refund` after the admin has switched it off
+    EmergencyRefundDisabled = 39,
+    /// Returned by `emergency_refund` once the per-day limit has been used up
+    EmergencyRefundLimitReached = 40,
+    // 41 (InvalidStatus) was only returned by set_refund_address, which now
+    // returns FundsNotLocked like every other path that needs a Locked escrow.
+    /// Returned when locking with a deadline that is not in the future
+    DeadlineInPast = 42,
+    /// Returned when locking with a deadline past `now + max_deadline_duration`
+    DeadlineTooFar = 43,
+    /// Returned by lock paths when the allowlist is on and the depositor is not on it
+    DepositorNotAllowed = 44,
+    /// Returned by release paths when the recipient has been blocked by the admin
+    ContributorBlocked = 45,
+    /// Returned by lock paths when the depositor is over the rate limit or in cooldown
+    RateLimitExceeded = 46,
+    /// Returned by execute_release before the release timelock has passed, and
+    /// by admin payouts above the timelock threshold that cannot be queued
+    ReleaseTimelocked = 47,
+    /// Returned by cancel_pending_release once the release is executable
+    ReleaseWindowClosed = 48,
+    /// Returned by vesting calls on an escrow locked without a vesting schedule
+    NotVesting = 49,
+    /// Returned by release and refund paths on an escrow that pays out through vesting
+    VestingEscrow = 50,
+    /// Returned by claim_vested when nothing has vested since the last claim
+    NothingToClaim = 51,
+    /// Returned by aggregate views when a running total would overflow an i128
+    ArithmeticOverflow = 52,
+    /// Returned by submit_claim while another claim on the bounty awaits the depositor
+    ClaimAlreadySubmitted = 53,
+    /// Returned by approve_claim and reject_claim when no claim has been submitted
+    ClaimNotSubmitted = 54,
+    /// Returned when a capped collection would grow past its cap: an amount
+    /// or deadline sort in query_escrows matching more than
+    /// `MAX_SORTED_RESULTS` escrows, or an escrow history that is full
+    LimitExceeded = 55,
+    /// Returned by lock paths that would take what the escrows in a token
+    /// hold past `max_total_locked`
+    AmountExceedsMax = 56,
 }
 
 #[contracttype]
@@ -382,6 +499,21 @@ pub enum EscrowStatus {
     Released,
     Refunded,
     PartiallyRefunded,
+    /// Some, but not all, milestones have been released.
+    PartiallyReleased,
+    /// Withdrawn by the depositor with `cancel_escrow` before any work started.
+    Cancelled,
+    /// Authorized by `authorize_claim` and waiting for the contributor to call
+    /// `claim`. The funds are still held by the contract.
+    PendingClaim,
+    /// Closed by `refund_partial`: part refunded to the depositor, the rest
+    /// released to the contributor. `remaining_amount` holds the refunded part.
+    Settled,
+    /// Frozen by `open_dispute` until the arbiter calls `resolve_dispute`.
+    Disputed,
+    /// A release above the timelock threshold is waiting for `execute_release`.
+    /// The funds are still held by the contract.
+    PendingRelease,
 }
 
 #[contracttype]
@@ -390,30 +522,141 @@ pub struct Escrow {
     pub depositor: Address,
     /// Total amount originally locked into this escrow.
     pub amount: i128,
-    /// Amount still available for release; decremented on each partial_release.
-    /// Reaches 0 when fully paid out, at which point status becomes Released.
+    /// Amount still available for release; decremented on each partial_release
+    /// and claim_vested. Reaches 0 when fully paid out, at which point status
+    /// becomes Released.
     pub remaining_amount: i128,
     pub status: EscrowStatus,
     pub deadline: u64,
     pub refund_history: Vec<RefundRecord>,
+    /// Per-milestone payout slices; empty for single-payout escrows.
+    pub milestones: Vec<Milestone>,
+    /// Optional third party allowed to release or refund at any time.
+    pub arbiter: Option<Address>,
+    /// Set once by `lock_funds_with_memo` and never changed afterwards.
+    pub memo: Option<Symbol>,
+    /// Grant program the escrow belongs to; 0 when locked without one.
+    pub project_id: u32,
+    /// Reason code of the latest release, e.g. the one passed to
+    /// `release_funds_with_reason`; None until funds are first released.
+    pub release_reason: Option<Symbol>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub amount: i128,
+    /// After this timestamp an unreleased milestone can be refunded.
+    pub deadline: u64,
+    pub status: MilestoneStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MilestoneStatus {
+    Pending,
+    Released,
+    Refunded,
 }
 
 #[contracttype]
 pub enum DataKey {
     Admin,
     Token,
-    Escrow(u64),             // bounty_id
-    EscrowIndex,             // Vec<u64> of all bounty_ids
-    DepositorIndex(Address), // Vec<u64> of bounty_ids by depositor
-    FeeConfig,               // Fee configuration
-    RefundApproval(u64),     // bounty_id -> RefundApproval
+    Escrow(u64),               // bounty_id
+    EscrowIndex,               // Vec<u64> of all bounty_ids
+    DepositorIndex(Address),   // Vec<u64> of bounty_ids by depositor
+    ContributorIndex(Address), // Vec<u64> of bounty_ids paid out to a contributor
+    FeeConfig,                 // Fee configuration
+    RefundApproval(u64),       // bounty_id -> RefundApproval
+    RefundRequest(u64),        // bounty_id -> RefundRequest awaiting the admin
     ReentrancyGuard,
     MultisigConfig,
-    ReleaseApproval(u64), // bounty_id -> ReleaseApproval
-    PendingClaim(u64),    // bounty_id -> ClaimRecord
-    ClaimWindow,          // u64 seconds (global config)
-    PauseFlags,           // PauseFlags struct
-    AmountPolicy, // Option<(i128, i128)> — (min_amount, max_amount) set by set_amount_policy
+    ReleaseApproval(u64),          // bounty_id -> ReleaseApproval
+    PendingClaim(u64),             // bounty_id -> ClaimRecord
+    ClaimWindow,                   // u64 seconds (global config)
+    PauseFlags,                    // PauseFlags struct
+    AmountPolicy, // Option<(i128, i128)> — (min_amount, max_amount) set by set_amount_policy or set_amount_limits
+    Paused,       // bool — contract-wide emergency switch set by pause/unpause
+    PendingAdmin, // Address proposed by propose_admin, awaiting accept_admin
+    FeeStats,     // FeeStats — running total of fees collected on release
+    ReleaseBreakdown(u64), // bounty_id -> Vec<(Address, i128)> legs of a split release
+    EscrowToken(u64), // bounty_id -> Address, only for escrows not in the init token
+    NativeToken,  // Address of the native XLM Stellar asset contract
+    Contributions(u64), // bounty_id -> Vec<(Address, i128)> amount put in by each funder
+    Assignee(u64), // bounty_id -> Address set by assign_contributor
+    RequireAssignment, // bool — releases must go to the assignee when set
+    AssignmentHistory(u64), // bounty_id -> Vec<AssignmentRecord>, newest last
+    Arbiter,      // Address that resolves disputes on escrows without their own arbiter
+    Dispute(u64), // bounty_id -> DisputeRecord while the escrow is Disputed
+    EmergencyRefundConfig, // EmergencyRefundConfig set by set_emergency_refund_config
+    EmergencyRefundWindow, // (u64, u32) — current day window and refunds made in it
+    RefundAddress(u64), // bounty_id -> Address the depositor's refunds go to instead
+    MaxDeadlineDuration, // u64 seconds from now a new escrow's deadline may be; 0 = no limit
+    AllowlistEnabled, // bool — only allowed depositors can lock funds when set
+    AllowedDepositors, // Vec<Address> of depositors on the allowlist
+    FeeOverride(u64), // bounty_id -> i128 release fee in bps set by lock_funds_with_fee
+    BlockedContributor(Address), // bool — no releases may be paid to this address
+    LockedAt(u64), // bounty_id -> u64 ledger time the escrow was locked
+    ReleaseLog(u64), // bounty_id -> Vec<ReleaseRecord> of every payout from the escrow
+    RefundGracePeriod, // u64 seconds after a deadline before refunds open; 0 when unset
+    ClaimSubmission(u64), // bounty_id -> ClaimSubmission awaiting approve_claim
+    DepositorStats(Address), // DepositorStats updated on every lock, release and refund
+    ContributorEarnings(Address), // ContributorEarnings updated on every release payout
+    TopContributors, // Vec<(Address, i128)> of the highest earners, largest first
+    DailyVolume(u64, DailyMetric), // (day, metric) -> (i128 amount, u32 count) moved that day
+    ReleaseTimelock, // (i128, u64) — amount above which releases wait, and for how long
+    PendingRelease(u64), // bounty_id -> PendingRelease queued by release_funds
+    Vesting(u64), // bounty_id -> VestingSchedule set by lock_funds_vesting
+    Metadata(u64), // bounty_id -> EscrowMetadata
+    RepoIndex(BytesN<32>), // sha256 of EscrowMetadata.repo -> Vec<u64> of bounty_ids
+    ProjectIndex(u32), // project_id -> Vec<u64> of bounty_ids; project 0 is not indexed
+}
+
+/// Storage keys added after `DataKey` reached the 50 case limit of a
+/// contract type. Variant names must not repeat a `DataKey` or
+/// `AntiAbuseKey` name, or the two keys would share a slot.
+#[contracttype]
+pub enum DataKeyExt {
+    UniqueDepositors, // u32 — addresses seen locking, counted when their DepositorStats is created
+    UniqueContributors, // u32 — addresses paid, counted when their ContributorEarnings is created
+    EscrowHistory(u64), // bounty_id -> Vec<(u64, EscrowAction, Address, i128)>, oldest first
+    MaxTotalLocked,   // i128 cap on what the escrows in a token hold after a lock; 0 = no cap
+    FailureCount(ErrorCategory), // u32 — failures absorbed since the last reset_error_stats
+    TransferEventsMuted, // present while set_emit_events(false) is in force
+    RefundCursor,     // u32 — EscrowIndex position refund_expired resumes from
+}
+
+/// Kinds of failure counted by `get_error_stats`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorCategory {
+    /// DeadlineNotPassed: the escrow, or every pending milestone, is not refundable yet
+    RefundBeforeDeadline,
+    /// FundsNotLocked: the escrow was already released, refunded or cancelled
+    NotLocked,
+    /// EscrowDisputed
+    Disputed,
+    /// Any other error
+    Other,
+}
+
+/// What happened to an escrow, as recorded by `get_escrow_history`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EscrowAction {
+    Locked,
+    /// A top-up or a contribution from another funder
+    ToppedUp,
+    Assigned,
+    Unassigned,
+    DeadlineExtended,
+    Disputed,
+    /// One payout to a contributor, gross of the fee
+    Released,
+    /// One refund, whoever the funds went back to
+    Refunded,
+    Cancelled,
 }
 
 #[contracttype]
@@ -423,6 +666,59 @@ pub struct EscrowWithId {
     pub escrow: Escrow,
 }
 
+/// One page of results from `query_escrows_by_status_paged` or
+/// `query_escrows_by_depositor_paged`. `next_index` is the index position to
+/// pass back for the next page, or `None` once the end of the index has been
+/// reached.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowPage {
+    pub items: Vec<EscrowWithId>,
+    pub next_index: Option<u32>,
+}
+
+/// Criteria for `query_escrows`. Every field that is set must match; unset
+/// fields match everything.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowFilter {
+    /// Matches an escrow in any of these statuses; empty matches every
+    /// status. A list rather than an `Option` because contract types cannot
+    /// hold an optional enum.
+    pub statuses: Vec<EscrowStatus>,
+    /// Compared like `query_escrows_by_amount`: active escrows by what is
+    /// still locked, the rest by their original amount.
+    pub min_amount: Option<i128>,
+    pub max_amount: Option<i128>,
+    pub min_deadline: Option<u64>,
+    pub max_deadline: Option<u64>,
+    /// Escrows this address locked or contributed to, as in
+    /// `query_escrows_by_depositor`.
+    pub depositor: Option<Address>,
+}
+
+/// Result order for `query_escrows`. Created order is the order escrows
+/// were locked in.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SortBy {
+    CreatedAsc,
+    CreatedDesc,
+    /// Largest first, by the same amount the filter compares
+    AmountDesc,
+    /// Ending soonest first
+    DeadlineAsc,
+}
+
+/// One page of ids from `get_escrow_ids_by_status_paged`; `next_index`
+/// works as in `EscrowPage`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowIdPage {
+    pub ids: Vec<u64>,
+    pub next_index: Option<u32>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PauseFlags {
@@ -440,6 +736,201 @@ pub struct AggregateStats {
     pub count_locked: u32,
     pub count_released: u32,
     pub count_refunded: u32,
+    pub total_cancelled: i128,
+    pub count_cancelled: u32,
+    pub count_settled: u32,
+    pub total_disputed: i128,
+    pub count_disputed: u32,
+}
+
+/// Lifetime totals for the escrows one depositor has locked, returned by
+/// `get_depositor_stats`. `total_locked` includes top-ups and contributions,
+/// so `total_locked - total_released - total_refunded` is what the
+/// depositor's escrows still hold. Released and refunded counts are escrows
+/// closed that way; a split settlement counts as released.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositorStats {
+    pub total_locked: i128,
+    pub total_released: i128,
+    pub total_refunded: i128,
+    pub count_locked: u32,
+    pub count_released: u32,
+    pub count_refunded: u32,
+    /// Escrows that still hold funds
+    pub active_bounty_ids_count: u32,
+    /// Ledger time of the depositor's latest lock; 0 if they never locked
+    pub last_lock_at: u64,
+}
+
+/// What one contributor has been paid, returned by `get_contributor_earnings`.
+/// Amounts are net of the release fee.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributorEarnings {
+    pub total_earned: i128,
+    /// Distinct bounties that have paid the contributor
+    pub bounty_count: u32,
+    /// Ledger time of the latest payout; 0 if never paid
+    pub last_payout_at: u64,
+}
+
+/// What the escrows in `token` should hold next to what the contract holds,
+/// returned by `get_balance_reconciliation`. `surplus` is
+/// `actual_balance - expected_locked`: above 0 means funds the escrows do not
+/// account for (e.g. sent straight to the contract), below 0 means the
+/// escrows are owed more than the contract holds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceReconciliation {
+    pub token: Address,
+    pub expected_locked: i128,
+    pub actual_balance: i128,
+    pub surplus: i128,
+    /// The contract holds at least what its escrows are owed (`surplus >= 0`)
+    pub is_solvent: bool,
+}
+
+/// Money movement tracked per day by `get_daily_stats`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DailyMetric {
+    /// Locks, top-ups and contributions
+    Locked,
+    /// Release payouts, gross of the fee
+    Released,
+    /// Refund payouts
+    Refunded,
+}
+
+/// Volume for one day, where `day` is `timestamp / 86_400` (UTC days since
+/// the Unix epoch). Counts are operations, not escrows: an escrow released
+/// in two parts adds two releases.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DailyStats {
+    pub day: u64,
+    pub total_locked: i128,
+    pub count_locked: u32,
+    pub total_released: i128,
+    pub count_released: u32,
+    pub total_refunded: i128,
+    pub count_refunded: u32,
+}
+
+/// Activity whose timestamp falls inside a window, returned by
+/// `get_stats_in_window`. Counts are escrows, totals are gross amounts.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WindowStats {
+    pub count_locked: u32,
+    pub total_locked: i128,
+    pub count_released: u32,
+    pub total_released: i128,
+    pub count_refunded: u32,
+    pub total_refunded: i128,
+}
+
+/// Where a depositor stands against the lock rate limit, returned by
+/// `get_rate_limit_status`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimitStatus {
+    /// True when the depositor is not subject to the limit at all.
+    pub exempt: bool,
+    pub max_locks: u32,
+    /// Locks still allowed in the current window.
+    pub remaining: u32,
+    /// When the current window ends and the quota resets; 0 if no window is open.
+    pub window_resets_at: u64,
+    /// Earliest time the next lock is allowed, given the cooldown.
+    pub next_allowed_at: u64,
+}
+
+/// Where an escrow's bounty lives off-chain, set by `lock_funds_with_metadata`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowMetadata {
+    /// Repository, e.g. `owner/name`. Escrows are grouped by its sha256.
+    pub repo: Bytes,
+    pub issue_number: u64,
+    pub title_hash: BytesN<32>,
+}
+
+/// Linear vesting window of an escrow locked with `lock_funds_vesting`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Progress of a vesting escrow, returned by `get_vesting_info`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingInfo {
+    pub start: u64,
+    pub end: u64,
+    /// Amount vested so far, claimed or not.
+    pub vested: i128,
+    /// Amount already paid out by `claim_vested`.
+    pub claimed: i128,
+    /// Amount the contributor can claim right now.
+    pub claimable: i128,
+}
+
+/// Distribution of the amounts still held by Locked escrows, returned by
+/// `get_amount_percentiles`. All zero when nothing is locked.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AmountPercentiles {
+    pub count: u32,
+    pub min: i128,
+    pub median: i128,
+    pub max: i128,
+    pub average: i128,
+}
+
+/// Shape of the escrows that still hold funds, plus how many distinct
+/// addresses have used the contract, returned by `get_extended_stats`.
+/// Active escrows are the ones `AggregateStats` counts in `count_locked`;
+/// amounts are what each still holds. Amounts are zero when none are active.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtendedStats {
+    pub active_count: u32,
+    pub average_active: i128,
+    pub largest_active: i128,
+    pub smallest_active: i128,
+    /// Addresses that have locked at least one escrow
+    pub unique_depositors: u32,
+    /// Addresses that have received at least one release payout
+    pub unique_contributors: u32,
+}
+
+/// Failed operations per category, returned by `get_error_stats`.
+///
+/// A failure that makes a call return an error is rolled back together with
+/// the rest of the call, so only failures a call absorbs and carries on from
+/// can be counted. Today that is the ids `refund_expired_batch` skips; a
+/// failed `lock_funds`, `release_funds` or `refund` is not counted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ErrorStats {
+    pub refund_before_deadline: u32,
+    pub not_locked: u32,
+    pub disputed: u32,
+    pub other: u32,
+    /// Sum of the counters above
+    pub total: u32,
+}
+
+/// Cumulative protocol fees taken on release, returned by `get_fee_stats`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeStats {
+    pub total_collected: i128,
+    pub collection_count: u32,
 }
 
 #[contracttype]
@@ -485,6 +976,36 @@ pub struct ClaimRecord {
     pub claimed: bool,
 }
 
+/// A request for payment made by a contributor with `submit_claim`, waiting
+/// for the depositor's `approve_claim`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimSubmission {
+    pub contributor: Address,
+    pub submitted_at: u64,
+}
+
+/// A release queued by `release_funds` because it was above the timelock
+/// threshold.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingRelease {
+    pub recipient: Address,
+    pub amount: i128,
+    pub executable_at: u64,
+    /// Release reason code, applied when the release is executed
+    pub reason: Symbol,
+}
+
+/// Asset an escrow is held in, as reported by `get_escrow_asset`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EscrowAsset {
+    /// Native XLM, via the asset contract set with `set_native_token`.
+    Native,
+    Token(Address),
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum RefundMode {
@@ -503,13 +1024,122 @@ pub struct RefundApproval {
     pub approved_at: u64,
 }
 
+/// Outcome chosen by the arbiter in `resolve_dispute`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DisputeRuling {
+    /// Pay everything still held to the contributor who opened the dispute.
+    Release,
+    /// Return everything still held to the depositor (and co-funders).
+    Refund,
+    /// Pay this many basis points of what is held to the contributor and
+    /// refund the rest. Both legs must be non-zero.
+    Split(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeRecord {
+    pub contributor: Address,
+    pub opened_at: u64,
+}
+
+/// One payout from an escrow. `amount` is gross, before any release fee.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseRecord {
+    pub amount: i128,
+    pub recipient: Address,
+    pub timestamp: u64,
+}
+
+/// One change of assignee. `contributor` is None when the bounty was unassigned.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssignmentRecord {
+    pub contributor: Option<Address>,
+    pub changed_by: Address,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RefundRecord {
     pub amount: i128,
+    /// Depositor of the escrow, even when the refund went to another address.
+    pub depositor: Address,
     pub recipient: Address,
     pub timestamp: u64,
     pub mode: RefundMode,
+    /// Why the refund happened, e.g. `cancel` for `cancel_escrow`.
+    pub reason: Symbol,
+    /// Code the depositor gave in `request_refund`; 0 for every other path.
+    pub reason_code: u32,
+}
+
+/// Refund path a `RefundRecord` came from, worked out from its `reason`
+/// symbol so records written before this existed read the same way.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RefundReason {
+    /// `refund` and the keeper paths once the deadline had passed
+    DeadlinePassed,
+    /// Let through early by the admin, an arbiter or a dispute ruling
+    Approved,
+    /// `emergency_refund` or `admin_refund`
+    Emergency,
+    /// `cancel_escrow` or `cancel_vesting`
+    Cancelled,
+    /// A reason symbol this version does not know
+    Other,
+}
+
+/// One refund returned by the refund history views.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundHistoryEntry {
+    pub bounty_id: u64,
+    pub reason: RefundReason,
+    pub record: RefundRecord,
+}
+
+/// Limits on `emergency_refund`. Without a stored config it is enabled with
+/// `DEFAULT_MAX_EMERGENCY_REFUNDS` per day.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyRefundConfig {
+    pub enabled: bool,
+    /// Most escrows that can be emergency-refunded in one ledger-day window.
+    pub max_per_day: u32,
+}
+
+/// Early refund asked for by the depositor, waiting for the admin.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundRequest {
+    pub bounty_id: u64,
+    pub reason_code: u32,
+    pub requested_at: u64,
+}
+
+/// Refund state reported by `get_refund_eligibility`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RefundEligibility {
+    /// `refund` would succeed now.
+    Eligible,
+    /// The depositor asked for an early refund and the admin has not decided.
+    PendingApproval,
+    /// Still locked, but the deadline has not passed.
+    DeadlineNotPassed,
+    /// The deadline has passed, but the refund grace period has not.
+    InGracePeriod,
+    /// Already released, refunded, cancelled, disputed, vesting or awaiting
+    /// a claim.
+    NotRefundable,
+    /// No escrow has this id. Only `get_refund_eligibility_batch` reports
+    /// it; `get_refund_eligibility` returns BountyNotFound.
+    NotFound,
 }
 
 #[contracttype]
@@ -554,6 +1184,97 @@ impl BountyEscrowContract {
         Ok(())
     }
 
+    /// Propose a new admin (admin only). The transfer only takes effect once
+    /// the candidate calls `accept_admin`; until then the current admin keeps
+    /// every permission. Proposing again replaces the previous candidate.
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PendingAdmin, &new_admin);
+
+        events::emit_admin_transfer_proposed(
+            &env,
+            events::AdminTransferProposed {
+                current_admin: admin,
+                proposed_admin: new_admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Complete an admin transfer. `new_admin` must be the pending candidate
+    /// and must authorize the call.
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let pending: Option<Address> = env.storage().instance().get(&DataKey::PendingAdmin);
+        if pending != Some(new_admin.clone()) {
+            return Err(Error::NotPendingAdmin);
+        }
+        new_admin.require_auth();
+
+        let previous_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        events::emit_admin_transferred(
+            &env,
+            events::AdminTransferred {
+                previous_admin,
+                new_admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Withdraw a pending admin proposal (admin only).
+    pub fn cancel_admin_transfer(env: Env) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let pending: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PendingAdmin)
+            .ok_or(Error::NotPendingAdmin)?;
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        events::emit_admin_transfer_cancelled(
+            &env,
+            events::AdminTransferCancelled {
+                admin,
+                cancelled_admin: pending,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// view function to get the current admin
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)
+    }
+
+    /// view function to get the admin candidate awaiting acceptance, if any
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+
     /// Calculate fee amount based on rate (in basis points)
     fn calculate_fee(amount: i128, fee_rate: i128) -> i128 {
         if fee_rate == 0 {
@@ -580,21 +1301,496 @@ impl BountyEscrowContract {
             })
     }
 
-    /// Update fee configuration (admin only)
-    pub fn update_fee_config(
-        env: Env,
-        lock_fee_rate: Option<i128>,
-        release_fee_rate: Option<i128>,
-        fee_recipient: Option<Address>,
-        fee_enabled: Option<bool>,
-    ) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
+    /// Record `bounty_id` under the contributor's index the first time funds
+    /// from that bounty are paid to them.
+    fn index_contributor(env: &Env, bounty_id: u64, contributor: &Address) {
+        let key = DataKey::ContributorIndex(contributor.clone());
+        let mut index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        if !index.contains(bounty_id) {
+            index.push_back(bounty_id);
+            env.storage().persistent().set(&key, &index);
         }
+    }
+
+    /// view function to get the running stats of the escrows `depositor` locked
+    pub fn get_depositor_stats(env: Env, depositor: Address) -> DepositorStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DepositorStats(depositor))
+            .unwrap_or(DepositorStats {
+                total_locked: 0,
+                total_released: 0,
+                total_refunded: 0,
+                count_locked: 0,
+                count_released: 0,
+                count_refunded: 0,
+                active_bounty_ids_count: 0,
+                last_lock_at: 0,
+            })
+    }
+
+    fn update_depositor_stats(
+        env: &Env,
+        depositor: &Address,
+        update: impl FnOnce(&mut DepositorStats),
+    ) {
+        let mut stats = Self::get_depositor_stats(env.clone(), depositor.clone());
+        update(&mut stats);
+        env.storage()
+            .persistent()
+            .set(&DataKey::DepositorStats(depositor.clone()), &stats);
+    }
+
+    /// `total + amount` for a running stat. An overflow panics with
+    /// ArithmeticOverflow, failing the whole call instead of wrapping, so a
+    /// stat can never end up corrupted.
+    fn add_to_stat(env: &Env, total: i128, amount: i128) -> i128 {
+        total
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticOverflow))
+    }
+
+    /// `count + 1` for a stored counter, failing like `add_to_stat` on overflow.
+    fn bump_count(env: &Env, count: u32) -> u32 {
+        count
+            .checked_add(1)
+            .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticOverflow))
+    }
+
+    /// Counts a lock of `depositor`'s own. Co-funding someone else's escrow
+    /// adds to `total_locked` only, so a depositor is counted as unique on
+    /// their first own lock.
+    fn record_depositor_lock(env: &Env, depositor: &Address, amount: i128) {
+        let now = env.ledger().timestamp();
+        let mut first_lock = false;
+        Self::update_depositor_stats(env, depositor, |stats| {
+            first_lock = stats.count_locked == 0;
+            stats.total_locked = Self::add_to_stat(env, stats.total_locked, amount);
+            stats.count_locked = Self::bump_count(env, stats.count_locked);
+            stats.active_bounty_ids_count = Self::bump_count(env, stats.active_bounty_ids_count);
+            stats.last_lock_at = now;
+        });
+        if first_lock {
+            Self::bump_unique_count(env, DataKeyExt::UniqueDepositors);
+        }
+    }
+
+    /// Count `amount` paid out of one of `depositor`'s escrows.
+    fn record_depositor_payout(env: &Env, depositor: &Address, amount: i128, refunded: bool) {
+        Self::update_depositor_stats(env, depositor, |stats| {
+            if refunded {
+                stats.total_refunded = Self::add_to_stat(env, stats.total_refunded, amount);
+            } else {
+                stats.total_released = Self::add_to_stat(env, stats.total_released, amount);
+            }
+        });
+    }
+
+    /// Call once, when one of `depositor`'s escrows reaches a final status.
+    /// The active count stays at zero, with a StatsAnomaly event, if it was
+    /// already zero.
+    fn record_depositor_close(env: &Env, depositor: &Address, refunded: bool) {
+        Self::update_depositor_stats(env, depositor, |stats| {
+            if stats.active_bounty_ids_count == 0 {
+                events::emit_stats_anomaly(
+                    env,
+                    events::StatsAnomaly {
+                        stat: symbol_short!("active"),
+                        subject: depositor.clone(),
+                        value: 0,
+                        change: -1,
+                        timestamp: env.ledger().timestamp(),
+                    },
+                );
+            }
+            stats.active_bounty_ids_count = stats.active_bounty_ids_count.saturating_sub(1);
+            if refunded {
+                stats.count_refunded = Self::bump_count(env, stats.count_refunded);
+            } else {
+                stats.count_released = Self::bump_count(env, stats.count_released);
+            }
+        });
+    }
+
+    /// view function to get what `contributor` has been paid across all escrows
+    pub fn get_contributor_earnings(env: Env, contributor: Address) -> ContributorEarnings {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ContributorEarnings(contributor))
+            .unwrap_or(ContributorEarnings {
+                total_earned: 0,
+                bounty_count: 0,
+                last_payout_at: 0,
+            })
+    }
+
+    /// view function to get up to `limit` of the highest earning contributors
+    /// with their total earnings, largest first. Only the top
+    /// `MAX_TOP_CONTRIBUTORS` (10) are kept.
+    pub fn get_top_contributors(env: Env, limit: u32) -> Vec<(Address, i128)> {
+        let board: Vec<(Address, i128)> = env
+            .storage()
+            .instance()
+            .get(&DataKey::TopContributors)
+            .unwrap_or(Vec::new(&env));
+        board.slice(0..limit.min(board.len()))
+    }
+
+    /// Credit `amount` paid from `bounty_id` to `recipient` and move them up
+    /// the leaderboard. `first_from_bounty` is false when the bounty has paid
+    /// them before.
+    fn record_earnings(env: &Env, recipient: &Address, amount: i128, first_from_bounty: bool) {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::ContributorEarnings(recipient.clone()))
+        {
+            Self::bump_unique_count(env, DataKeyExt::UniqueContributors);
+        }
+        let mut earnings = Self::get_contributor_earnings(env.clone(), recipient.clone());
+        earnings.total_earned = Self::add_to_stat(env, earnings.total_earned, amount);
+        if first_from_bounty {
+            earnings.bounty_count = Self::bump_count(env, earnings.bounty_count);
+        }
+        earnings.last_payout_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::ContributorEarnings(recipient.clone()), &earnings);
+
+        // Totals only grow, so re-inserting the recipient keeps the board sorted
+        let mut board: Vec<(Address, i128)> = env
+            .storage()
+            .instance()
+            .get(&DataKey::TopContributors)
+            .unwrap_or(Vec::new(env));
+        if let Some(i) = board.iter().position(|(addr, _)| addr == *recipient) {
+            board.remove(i as u32);
+        }
+        let at = board
+            .iter()
+            .position(|(_, total)| total < earnings.total_earned)
+            .unwrap_or(board.len() as usize) as u32;
+        if at < MAX_TOP_CONTRIBUTORS {
+            board.insert(at, (recipient.clone(), earnings.total_earned));
+            if board.len() > MAX_TOP_CONTRIBUTORS {
+                board.pop_back();
+            }
+            env.storage()
+                .instance()
+                .set(&DataKey::TopContributors, &board);
+        }
+    }
+
+    /// Append an entry to the bounty's history. Once it holds
+    /// `MAX_ESCROW_HISTORY` entries, assignment changes and deadline
+    /// extensions panic with LimitExceeded, which fails the whole call.
+    /// Entries that move funds are always written so a full history can
+    /// never block a release, refund or vesting claim.
+    fn record_history(
+        env: &Env,
+        bounty_id: u64,
+        action: EscrowAction,
+        actor: &Address,
+        amount: i128,
+    ) {
+        let key = DataKeyExt::EscrowHistory(bounty_id);
+        let mut history: Vec<(u64, EscrowAction, Address, i128)> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        let moves_funds = !matches!(
+            action,
+            EscrowAction::Assigned | EscrowAction::Unassigned | EscrowAction::DeadlineExtended
+        );
+        if !moves_funds && history.len() >= MAX_ESCROW_HISTORY {
+            panic_with_error!(env, Error::LimitExceeded);
+        }
+        history.push_back((env.ledger().timestamp(), action, actor.clone(), amount));
+        env.storage().persistent().set(&key, &history);
+    }
+
+    /// view function to get a page of a bounty's history as
+    /// `(timestamp, action, actor, amount)`, oldest first. Returns at most
+    /// `limit` entries, capped at `MAX_PAGE_SIZE`.
+    ///
+    /// `actor` is the funder for locks and top-ups, the caller for
+    /// assignment changes and deadline extensions, the contributor for a
+    /// dispute, the recipient for a release and the depositor for a refund
+    /// or cancellation. `amount` is 0 for actions that move no funds. Once a
+    /// bounty has `MAX_ESCROW_HISTORY` (200) entries, assignment changes and
+    /// deadline extensions fail with LimitExceeded; fund movements are
+    /// still recorded.
+    pub fn get_escrow_history(
+        env: Env,
+        bounty_id: u64,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(u64, EscrowAction, Address, i128)> {
+        let history: Vec<(u64, EscrowAction, Address, i128)> = env
+            .storage()
+            .persistent()
+            .get(&DataKeyExt::EscrowHistory(bounty_id))
+            .unwrap_or(Vec::new(&env));
+        let start = offset.min(history.len());
+        let end = offset
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(history.len());
+        history.slice(start..end)
+    }
+
+    /// view function to get how many distinct addresses have ever locked
+    /// funds. Never decreases, even once all of an address's escrows have
+    /// been released or refunded.
+    pub fn get_unique_depositor_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::UniqueDepositors)
+            .unwrap_or(0)
+    }
+
+    fn bump_unique_count(env: &Env, key: DataKeyExt) {
+        let count: u32 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&key, &Self::bump_count(env, count));
+    }
+
+    /// Add `amount` to today's bucket for `metric`. Buckets add up every
+    /// token, so they saturate instead of failing a lock or payout that each
+    /// token's own totals can hold.
+    fn record_daily_volume(env: &Env, metric: DailyMetric, amount: i128) {
+        let key = DataKey::DailyVolume(env.ledger().timestamp() / SECONDS_PER_DAY, metric);
+        let (total, count): (i128, u32) = env.storage().persistent().get(&key).unwrap_or((0, 0));
+        env.storage().persistent().set(
+            &key,
+            &(total.saturating_add(amount), count.saturating_add(1)),
+        );
+    }
+
+    /// view function to get the volume locked, released and refunded on
+    /// `day` (`timestamp / 86_400`); days without activity are all zeros
+    pub fn get_daily_stats(env: Env, day: u64) -> DailyStats {
+        let bucket = |metric| -> (i128, u32) {
+            env.storage()
+                .persistent()
+                .get(&DataKey::DailyVolume(day, metric))
+                .unwrap_or((0, 0))
+        };
+        let (total_locked, count_locked) = bucket(DailyMetric::Locked);
+        let (total_released, count_released) = bucket(DailyMetric::Released);
+        let (total_refunded, count_refunded) = bucket(DailyMetric::Refunded);
+        DailyStats {
+            day,
+            total_locked,
+            count_locked,
+            total_released,
+            count_released,
+            total_refunded,
+            count_refunded,
+        }
+    }
+
+    /// view function to get `get_daily_stats` for every day from `from_day`
+    /// to `to_day` inclusive, oldest first. At most `MAX_DAILY_RANGE` (90)
+    /// days are returned; continue from the last day + 1 for more.
+    pub fn get_daily_stats_range(env: Env, from_day: u64, to_day: u64) -> Vec<DailyStats> {
+        let mut days = Vec::new(&env);
+        if from_day > to_day {
+            return days;
+        }
+        let last = to_day.min(from_day.saturating_add(MAX_DAILY_RANGE - 1));
+        for day in from_day..=last {
+            days.push_back(Self::get_daily_stats(env.clone(), day));
+        }
+        days
+    }
+
+    /// Admin payouts other than `release_funds` cannot be queued, so they
+    /// return ReleaseTimelocked when the release timelock is on and `amount`
+    /// is above its threshold.
+    fn check_release_timelock(env: &Env, amount: i128) -> Result<(), Error> {
+        let (threshold, delay) = Self::get_release_timelock(env.clone());
+        if delay > 0 && amount > threshold {
+            return Err(Error::ReleaseTimelocked);
+        }
+        Ok(())
+    }
+
+    /// Pay `gross` out of escrow to `recipient`, deducting the release fee when
+    /// fees are enabled or the escrow has a fee override. The fee goes to the
+    /// configured fee recipient.
+    /// Returns `(net, fee)`. Refund paths never go through here.
+    ///
+    /// Vesting escrows only pay out through `claim_vested`, so every other
+    /// release path is rejected here with `VestingEscrow`.
+    fn pay_release(
+        env: &Env,
+        bounty_id: u64,
+        client: &token::Client,
+        recipient: &Address,
+        gross: i128,
+    ) -> (i128, i128) {
+        if let Err(err) = Self::check_not_vesting(env, bounty_id) {
+            panic_with_error!(env, err);
+        }
+        Self::transfer_release(env, bounty_id, client, recipient, gross)
+    }
+
+    /// Fee-deducting payout behind `pay_release`, without the vesting guard.
+    /// Only `claim_vested` calls this directly.
+    fn transfer_release(
+        env: &Env,
+        bounty_id: u64,
+        client: &token::Client,
+        recipient: &Address,
+        gross: i128,
+    ) -> (i128, i128) {
+        let mut fee_config = Self::get_fee_config_internal(env);
+        if let Some(rate) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, i128>(&DataKey::FeeOverride(bounty_id))
+        {
+            fee_config.release_fee_rate = rate;
+            fee_config.fee_enabled = true;
+        }
+        let fee = if fee_config.fee_enabled {
+            Self::calculate_fee(gross, fee_config.release_fee_rate)
+        } else {
+            0
+        };
+        let net = gross - fee;
+        let contract_address = env.current_contract_address();
+
+        if fee > 0 {
+            client.transfer(&contract_address, &fee_config.fee_recipient, &fee);
+
+            let mut stats = Self::get_fee_stats(env.clone());
+            stats.total_collected = Self::add_to_stat(env, stats.total_collected, fee);
+            stats.collection_count = Self::bump_count(env, stats.collection_count);
+            env.storage().instance().set(&DataKey::FeeStats, &stats);
+
+            events::emit_fee_collected(
+                env,
+                events::FeeCollected {
+                    operation_type: events::FeeOperationType::Release,
+                    amount: fee,
+                    fee_rate: fee_config.release_fee_rate,
+                    recipient: fee_config.fee_recipient,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+        }
+        client.transfer(&contract_address, recipient, &net);
+        Self::record_history(env, bounty_id, EscrowAction::Released, recipient, gross);
+
+        if let Some(escrow) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+        {
+            Self::record_depositor_payout(env, &escrow.depositor, gross, false);
+        }
+        Self::record_daily_volume(env, DailyMetric::Released, gross);
+
+        let key = DataKey::ReleaseLog(bounty_id);
+        let mut log: Vec<ReleaseRecord> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        let first_from_bounty = !log.iter().any(|r| r.recipient == *recipient);
+        Self::record_earnings(env, recipient, net, first_from_bounty);
+        log.push_back(ReleaseRecord {
+            amount: gross,
+            recipient: recipient.clone(),
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &log);
+
+        (net, fee)
+    }
+
+    /// Set the protocol fee taken on every release (admin only).
+    ///
+    /// `fee_bps` is in basis points and capped at MAX_PROTOCOL_FEE_BPS; the fee
+    /// is sent to `treasury` and the contributor receives the remainder.
+    /// Passing 0 turns the fee off, which is also the default.
+    pub fn set_fee(
+        env: Env,
+        admin: Address,
+        fee_bps: i128,
+        treasury: Address,
+    ) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        admin.require_auth();
+
+        if !(0..=MAX_PROTOCOL_FEE_BPS).contains(&fee_bps) {
+            return Err(Error::InvalidFeeRate);
+        }
+
+        let mut fee_config = Self::get_fee_config_internal(&env);
+        fee_config.release_fee_rate = fee_bps;
+        fee_config.fee_recipient = treasury;
+        fee_config.fee_enabled = fee_bps > 0;
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeConfig, &fee_config);
+
+        events::emit_fee_config_updated(
+            &env,
+            events::FeeConfigUpdated {
+                lock_fee_rate: fee_config.lock_fee_rate,
+                release_fee_rate: fee_config.release_fee_rate,
+                fee_recipient: fee_config.fee_recipient,
+                fee_enabled: fee_config.fee_enabled,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Cumulative release fees collected since deployment.
+    pub fn get_fee_stats(env: Env) -> FeeStats {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeStats)
+            .unwrap_or(FeeStats {
+                total_collected: 0,
+                collection_count: 0,
+            })
+    }
+
+    /// Update fee configuration (admin only)
+    ///
+    /// `lock_fee_rate` is capped at MAX_FEE_RATE. `release_fee_rate` is the
+    /// same fee `set_fee` sets and shares its MAX_PROTOCOL_FEE_BPS cap.
+    pub fn update_fee_config(
+        env: Env,
+        lock_fee_rate: Option<i128>,
+        release_fee_rate: Option<i128>,
+        fee_recipient: Option<Address>,
+        fee_enabled: Option<bool>,
+    ) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
-
         let mut fee_config = Self::get_fee_config_internal(&env);
 
         if let Some(rate) = lock_fee_rate {
@@ -605,7 +1801,7 @@ impl BountyEscrowContract {
         }
 
         if let Some(rate) = release_fee_rate {
-            if rate < 0 || rate > MAX_FEE_RATE {
+            if !(0..=MAX_PROTOCOL_FEE_BPS).contains(&rate) {
                 return Err(Error::InvalidFeeRate);
             }
             fee_config.release_fee_rate = rate;
@@ -693,6 +1889,88 @@ impl BountyEscrowContract {
         Ok(())
     }
 
+    /// Set all three pause flags at once (admin only). Each entrypoint only
+    /// checks its own flag, so e.g. pausing locks still lets existing escrows
+    /// drain through releases and refunds.
+    pub fn set_pause_flags(
+        env: Env,
+        lock_paused: bool,
+        release_paused: bool,
+        refund_paused: bool,
+    ) -> Result<(), Error> {
+        Self::set_paused(
+            env,
+            Some(lock_paused),
+            Some(release_paused),
+            Some(refund_paused),
+        )
+    }
+
+    /// Emergency stop for the whole contract (admin only).
+    ///
+    /// While paused every lock and release path, and every path that returns
+    /// funds (`refund`, `cancel_escrow`, `arbiter_refund`, `cancel_vesting`),
+    /// returns ContractPaused. Views and queries keep working.
+    pub fn pause(env: Env) -> Result<(), Error> {
+        Self::set_contract_paused(&env, true)
+    }
+
+    /// Lift a pause set with `pause` (admin only).
+    pub fn unpause(env: Env) -> Result<(), Error> {
+        Self::set_contract_paused(&env, false)
+    }
+
+    /// Whether the contract-wide pause is active.
+    pub fn is_paused(env: Env) -> bool {
+        Self::is_contract_paused(&env)
+    }
+
+    fn set_contract_paused(env: &Env, paused: bool) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Paused, &paused);
+
+        let event = events::ContractPauseToggled {
+            admin,
+            timestamp: env.ledger().timestamp(),
+        };
+        if paused {
+            events::emit_contract_paused(env, event);
+        } else {
+            events::emit_contract_unpaused(env, event);
+        }
+        Ok(())
+    }
+
+    fn is_contract_paused(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    /// Mark a token-moving entrypoint as in progress. Any nested call into a
+    /// guarded entrypoint fails with ReentrancyDetected until `exit_guard`.
+    /// An early `Err` return rolls the flag back along with the other writes.
+    fn enter_guard(env: &Env) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::ReentrancyGuard) {
+            return Err(Error::ReentrancyDetected);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &true);
+        Ok(())
+    }
+
+    fn exit_guard(env: &Env) {
+        env.storage().instance().remove(&DataKey::ReentrancyGuard);
+    }
+
     /// Get current pause flags
     pub fn get_pause_flags(env: &Env) -> PauseFlags {
         env.storage()
@@ -816,6 +2094,7 @@ impl BountyEscrowContract {
         events::emit_approval_added(
             &env,
             events::ApprovalAdded {
+                version: EVENT_VERSION_V3,
                 bounty_id,
                 contributor: contributor.clone(),
                 approver,
@@ -834,428 +2113,629 @@ impl BountyEscrowContract {
         amount: i128,
         deadline: u64,
     ) -> Result<(), Error> {
-        // Apply rate limiting
-        anti_abuse::check_rate_limit(&env, depositor.clone());
-
-        if Self::check_paused(&env, symbol_short!("lock")) {
-            return Err(Error::FundsPaused);
-        }
-
-        let _start = env.ledger().timestamp();
-        let _caller = depositor.clone();
-
-        // Verify depositor authorization
-        depositor.require_auth();
-
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
-        }
-
-        if env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyExists);
-        }
-
-        // Enforce min/max amount policy if one has been configured (Issue #62).
-        // When no policy is set this block is skipped entirely, preserving
-        // backward-compatible behaviour for callers that never call set_amount_policy.
-        if let Some((min_amount, max_amount)) = env
-            .storage()
-            .instance()
-            .get::<DataKey, (i128, i128)>(&DataKey::AmountPolicy)
-        {
-            if amount < min_amount {
-                return Err(Error::AmountBelowMinimum);
-            }
-            if amount > max_amount {
-                return Err(Error::AmountAboveMaximum);
-            }
-        }
-
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
-
-        // Transfer funds from depositor to contract
-        client.transfer(&depositor, &env.current_contract_address(), &amount);
-
         let escrow = Escrow {
-            depositor: depositor.clone(),
+            depositor,
             amount,
             status: EscrowStatus::Locked,
             deadline,
             refund_history: vec![&env],
             remaining_amount: amount,
+            milestones: vec![&env],
+            arbiter: None,
+            memo: None,
+            project_id: 0,
+            release_reason: None,
         };
+        Self::lock_escrow(&env, bounty_id, escrow, None)
+    }
 
-        // Extend the TTL of the storage entry to ensure it lives long enough
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+    /// Lock funds for a bounty with a short memo, e.g. the off-chain bounty
+    /// reference, returned by `get_escrow_info` and fixed for the life of
+    /// the escrow.
+    ///
+    /// The memo is a Soroban `Symbol`: at most 32 characters from
+    /// `[a-zA-Z0-9_]`. Longer strings cannot be turned into a `Symbol`, so
+    /// store a hash or ID rather than a full URL.
+    pub fn lock_funds_with_memo(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        memo: Option<Symbol>,
+    ) -> Result<(), Error> {
+        let escrow = Escrow {
+            depositor,
+            amount,
+            status: EscrowStatus::Locked,
+            deadline,
+            refund_history: vec![&env],
+            remaining_amount: amount,
+            milestones: vec![&env],
+            arbiter: None,
+            memo,
+            project_id: 0,
+            release_reason: None,
+        };
+        Self::lock_escrow(&env, bounty_id, escrow, None)
+    }
 
-        // Update indexes
+    /// Lock funds for a bounty and record the repository and issue it pays
+    /// for, so off-chain services do not need their own mapping.
+    pub fn lock_funds_with_metadata(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        metadata: EscrowMetadata,
+    ) -> Result<(), Error> {
+        Self::lock_funds(env.clone(), depositor, bounty_id, amount, deadline)?;
+        Self::store_metadata(&env, bounty_id, metadata);
+        Ok(())
+    }
+
+    /// Replace the metadata of an escrow (depositor only, while Locked).
+    pub fn update_metadata(
+        env: Env,
+        bounty_id: u64,
+        metadata: EscrowMetadata,
+    ) -> Result<(), Error> {
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        escrow.depositor.require_auth();
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+
+        if let Some(old) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, EscrowMetadata>(&DataKey::Metadata(bounty_id))
+        {
+            let key = DataKey::RepoIndex(env.crypto().sha256(&old.repo).into());
+            let mut index: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(Vec::new(&env));
+            if let Some(pos) = index.first_index_of(bounty_id) {
+                index.remove(pos);
+                env.storage().persistent().set(&key, &index);
+            }
+        }
+        Self::store_metadata(&env, bounty_id, metadata);
+        Ok(())
+    }
+
+    fn store_metadata(env: &Env, bounty_id: u64, metadata: EscrowMetadata) {
+        let key = DataKey::RepoIndex(env.crypto().sha256(&metadata.repo).into());
         let mut index: Vec<u64> = env
             .storage()
             .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
+            .get(&key)
+            .unwrap_or(Vec::new(env));
         index.push_back(bounty_id);
+        env.storage().persistent().set(&key, &index);
         env.storage()
             .persistent()
-            .set(&DataKey::EscrowIndex, &index);
+            .set(&DataKey::Metadata(bounty_id), &metadata);
+    }
 
-        let mut depositor_index: Vec<u64> = env
-            .storage()
+    /// view function to get the metadata of an escrow, if it has any
+    pub fn get_escrow_metadata(env: Env, bounty_id: u64) -> Option<EscrowMetadata> {
+        env.storage()
             .persistent()
-            .get(&DataKey::DepositorIndex(depositor.clone()))
-            .unwrap_or(Vec::new(&env));
-        depositor_index.push_back(bounty_id);
-        env.storage().persistent().set(
-            &DataKey::DepositorIndex(depositor.clone()),
-            &depositor_index,
-        );
-
-        // Emit value allows for off-chain indexing
-        emit_funds_locked(
-            &env,
-            FundsLocked {
-                version: EVENT_VERSION_V2,
-                bounty_id,
-                amount,
-                depositor: depositor.clone(),
-                deadline,
-            },
-        );
+            .get(&DataKey::Metadata(bounty_id))
+    }
 
+    /// Lock funds for a bounty under a grant program so its escrows can be
+    /// queried and reported on separately. Escrows locked any other way
+    /// belong to project 0.
+    pub fn lock_funds_with_project(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        project_id: u32,
+    ) -> Result<(), Error> {
+        let escrow = Escrow {
+            depositor,
+            amount,
+            status: EscrowStatus::Locked,
+            deadline,
+            refund_history: vec![&env],
+            remaining_amount: amount,
+            milestones: vec![&env],
+            arbiter: None,
+            memo: None,
+            project_id,
+            release_reason: None,
+        };
+        Self::lock_escrow(&env, bounty_id, escrow, None)?;
+        if project_id != 0 {
+            let key = DataKey::ProjectIndex(project_id);
+            let mut index: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(Vec::new(&env));
+            index.push_back(bounty_id);
+            env.storage().persistent().set(&key, &index);
+        }
         Ok(())
     }
 
-    /// Release funds to the contributor.
-    /// Only the admin (backend) can authorize this.
-    pub fn release_funds(env: Env, bounty_id: u64, contributor: Address) -> Result<(), Error> {
-        if Self::check_paused(&env, symbol_short!("release")) {
-            return Err(Error::FundsPaused);
-        }
-        let _start = env.ledger().timestamp();
+    /// Bounty ids that may belong to `project_id`. Project 0 has no index of
+    /// its own, so its candidates are every escrow and callers must check
+    /// `project_id` on each one.
+    fn project_candidates(env: &Env, project_id: u32) -> Vec<u64> {
+        let key = if project_id == 0 {
+            DataKey::EscrowIndex
+        } else {
+            DataKey::ProjectIndex(project_id)
+        };
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env))
+    }
 
-        // Ensure contract is initialized
-        if env.storage().instance().has(&DataKey::ReentrancyGuard) {
-            panic!("Reentrancy detected");
+    /// Lock funds for a bounty in a specific token. `None` uses the token set
+    /// in `init`, exactly like `lock_funds`. Release, refund and top-up
+    /// transfers for the bounty all go through the token it was locked in.
+    pub fn lock_funds_with_token(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        token: Option<Address>,
+    ) -> Result<(), Error> {
+        let escrow = Escrow {
+            depositor,
+            amount,
+            status: EscrowStatus::Locked,
+            deadline,
+            refund_history: vec![&env],
+            remaining_amount: amount,
+            milestones: vec![&env],
+            arbiter: None,
+            memo: None,
+            project_id: 0,
+            release_reason: None,
+        };
+        Self::lock_escrow(&env, bounty_id, escrow, token)
+    }
+
+    /// Lock native XLM for a bounty. Uses the native asset contract configured
+    /// with `set_native_token`; release and refund go through it like any
+    /// other per-bounty token.
+    pub fn lock_funds_native(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+    ) -> Result<(), Error> {
+        let native: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::NativeToken)
+            .ok_or(Error::NativeTokenNotSet)?;
+        Self::lock_funds_with_token(env, depositor, bounty_id, amount, deadline, Some(native))
+    }
+
+    /// Set the native XLM Stellar asset contract address (admin only).
+    pub fn set_native_token(env: Env, native_token: Address) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
         }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
         env.storage()
             .instance()
-            .set(&DataKey::ReentrancyGuard, &true);
+            .set(&DataKey::NativeToken, &native_token);
+        Ok(())
+    }
+
+    /// Lock funds for a bounty with an arbiter who can settle disputes.
+    ///
+    /// The arbiter may call `arbiter_release_funds` or `arbiter_refund` at any
+    /// time, including before the deadline. Admin release and deadline refund
+    /// keep working as for a plain escrow.
+    pub fn lock_funds_with_arbiter(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        arbiter: Address,
+    ) -> Result<(), Error> {
+        let escrow = Escrow {
+            depositor,
+            amount,
+            status: EscrowStatus::Locked,
+            deadline,
+            refund_history: vec![&env],
+            remaining_amount: amount,
+            milestones: vec![&env],
+            arbiter: Some(arbiter),
+            memo: None,
+            project_id: 0,
+            release_reason: None,
+        };
+        Self::lock_escrow(&env, bounty_id, escrow, None)
+    }
+
+    /// Lock funds for a bounty with its own release fee, e.g. a reduced rate
+    /// for an approved sponsor.
+    ///
+    /// `fee_bps` (0..=10000) is charged on every release from this escrow and
+    /// takes precedence over the global fee config, including when global
+    /// fees are disabled. Refunds are never charged. Both the depositor and
+    /// the admin must sign, so depositors cannot pick their own rate.
+    pub fn lock_funds_with_fee(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        fee_bps: u32,
+    ) -> Result<(), Error> {
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::NotInitialized);
         }
-
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
 
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
+        if fee_bps as i128 > BASIS_POINTS {
+            return Err(Error::InvalidFeeRate);
+        }
+        let escrow = Escrow {
+            depositor,
+            amount,
+            status: EscrowStatus::Locked,
+            deadline,
+            refund_history: vec![&env],
+            remaining_amount: amount,
+            milestones: vec![&env],
+            arbiter: None,
+            memo: None,
+            project_id: 0,
+            release_reason: None,
+        };
+        Self::lock_escrow(&env, bounty_id, escrow, None)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::FeeOverride(bounty_id), &(fee_bps as i128));
+        Ok(())
+    }
+
+    /// Lock funds that vest linearly to the assigned contributor between
+    /// `start` and `end`, e.g. for a monthly retainer.
+    ///
+    /// The contributor set with `assign_contributor` withdraws whatever has
+    /// vested with `claim_vested`; the depositor can stop the schedule with
+    /// `cancel_vesting`. The escrow deadline is `end`. `release_funds`,
+    /// `partial_release` and the deadline refund are not available for
+    /// vesting escrows.
+    pub fn lock_funds_vesting(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        start: u64,
+        end: u64,
+    ) -> Result<(), Error> {
+        if end <= start {
+            return Err(Error::InvalidDeadline);
+        }
+        let escrow = Escrow {
+            depositor,
+            amount,
+            status: EscrowStatus::Locked,
+            deadline: end,
+            refund_history: vec![&env],
+            remaining_amount: amount,
+            milestones: vec![&env],
+            arbiter: None,
+            memo: None,
+            project_id: 0,
+            release_reason: None,
+        };
+        Self::lock_escrow(&env, bounty_id, escrow, None)?;
+        env.storage().persistent().set(
+            &DataKey::Vesting(bounty_id),
+            &VestingSchedule { start, end },
+        );
+        Ok(())
+    }
+
+    /// Pay the assigned contributor everything that has vested and not been
+    /// claimed yet. Returns the gross amount paid.
+    pub fn claim_vested(env: Env, bounty_id: u64) -> Result<i128, Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::ReleasePaused);
         }
 
+        Self::enter_guard(&env)?;
+
         let mut escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
-
+            .ok_or(Error::BountyNotFound)?;
+        let schedule: VestingSchedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Vesting(bounty_id))
+            .ok_or(Error::NotVesting)?;
         if escrow.status != EscrowStatus::Locked {
             return Err(Error::FundsNotLocked);
         }
 
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
-
-        // Transfer funds to contributor
-        client.transfer(
-            &env.current_contract_address(),
-            &contributor,
-            &escrow.amount,
-        );
+        let contributor: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Assignee(bounty_id))
+            .ok_or(Error::NotAssignedContributor)?;
+        contributor.require_auth();
+        Self::check_not_blocked(&env, &contributor)?;
+
+        let claimed = escrow.amount - escrow.remaining_amount;
+        let claimable = Self::vested_amount(&env, &escrow, &schedule) - claimed;
+        if claimable <= 0 {
+            return Err(Error::NothingToClaim);
+        }
 
-        escrow.status = EscrowStatus::Released;
+        escrow.remaining_amount -= claimable;
+        escrow.release_reason = Some(symbol_short!("vesting"));
+        if escrow.remaining_amount == 0 {
+            escrow.status = EscrowStatus::Released;
+            Self::record_depositor_close(&env, &escrow.depositor, false);
+        }
         env.storage()
             .persistent()
             .set(&DataKey::Escrow(bounty_id), &escrow);
+        Self::index_contributor(&env, bounty_id, &contributor);
 
+        let token_addr = Self::escrow_token(&env, bounty_id);
+        let client = token::Client::new(&env, &token_addr);
+        let (net, fee) = Self::transfer_release(&env, bounty_id, &client, &contributor, claimable);
         emit_funds_released(
             &env,
             FundsReleased {
-                version: EVENT_VERSION_V2,
+                version: EVENT_VERSION_V3,
+                token: token_addr.clone(),
                 bounty_id,
-                amount: escrow.amount,
-                recipient: contributor.clone(),
+                depositor: escrow.depositor.clone(),
+                gross: claimable,
+                amount: net,
+                fee,
+                recipient: contributor,
+                reason: symbol_short!("vesting"),
                 timestamp: env.ledger().timestamp(),
             },
         );
 
-        // Clear reentrancy guard
-        env.storage().instance().remove(&DataKey::ReentrancyGuard);
-
-        Ok(())
-    }
-
-    /// Set the claim window duration (admin only).
-    /// claim_window: seconds beneficiary has to claim after release is authorized.
-    pub fn set_claim_window(env: Env, claim_window: u64) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
-        }
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
-        env.storage()
-            .instance()
-            .set(&DataKey::ClaimWindow, &claim_window);
-        Ok(())
+        Self::exit_guard(&env);
+        Ok(claimable)
     }
 
-    /// Authorize a release as a pending claim instead of immediate transfer.
-    /// Admin calls this instead of release_funds when claim period is active.
-    /// Beneficiary must call claim() within the window to receive funds.
-    pub fn authorize_claim(env: Env, bounty_id: u64, recipient: Address) -> Result<(), Error> {
-        if Self::check_paused(&env, symbol_short!("release")) {
-            return Err(Error::FundsPaused);
+    /// Depositor stops a vesting escrow. Whatever has vested but not been
+    /// claimed is paid to the assigned contributor and the unvested rest is
+    /// refunded. Without an assignee, or when the assignee has been blocked,
+    /// the whole remaining amount is refunded.
+    pub fn cancel_vesting(env: Env, bounty_id: u64) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
         }
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::RefundPaused);
         }
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
 
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
-        }
+        Self::enter_guard(&env)?;
 
         let escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
-
+            .ok_or(Error::BountyNotFound)?;
+        escrow.depositor.require_auth();
+        let schedule: VestingSchedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Vesting(bounty_id))
+            .ok_or(Error::NotVesting)?;
         if escrow.status != EscrowStatus::Locked {
             return Err(Error::FundsNotLocked);
         }
 
-        let now = env.ledger().timestamp();
-        let claim_window: u64 = env
+        let claimed = escrow.amount - escrow.remaining_amount;
+        let unclaimed = Self::vested_amount(&env, &escrow, &schedule) - claimed;
+        let assignee: Option<Address> = env
             .storage()
-            .instance()
-            .get(&DataKey::ClaimWindow)
-            .unwrap_or(0);
-        let claim = ClaimRecord {
-            bounty_id,
-            recipient: recipient.clone(),
-            amount: escrow.amount,
-            expires_at: now.saturating_add(claim_window),
-            claimed: false,
-        };
-
-        env.storage()
             .persistent()
-            .set(&DataKey::PendingClaim(bounty_id), &claim);
+            .get(&DataKey::Assignee(bounty_id));
+        match assignee {
+            Some(contributor)
+                if unclaimed > 0 && !Self::is_blocked(env.clone(), contributor.clone()) =>
+            {
+                let refund_amount = escrow.remaining_amount - unclaimed;
+                Self::settle_split(
+                    &env,
+                    bounty_id,
+                    escrow,
+                    refund_amount,
+                    &contributor,
+                    symbol_short!("vesting"),
+                );
+            }
+            _ => Self::settle_refund(&env, bounty_id, escrow, symbol_short!("vesting"), 0),
+        }
 
-        env.events().publish(
-            (symbol_short!("claim"), symbol_short!("created")),
-            ClaimCreated {
-                bounty_id,
-                recipient,
-                amount: escrow.amount,
-                expires_at: claim.expires_at,
-            },
-        );
+        Self::exit_guard(&env);
         Ok(())
     }
 
-    /// Beneficiary calls this to claim their authorized funds within the window.
-    pub fn claim(env: Env, bounty_id: u64) -> Result<(), Error> {
-        if Self::check_paused(&env, symbol_short!("release")) {
-            return Err(Error::FundsPaused);
-        }
-        if !env
+    /// view function to get the vesting progress of an escrow, or None if it
+    /// was not locked with `lock_funds_vesting`
+    pub fn get_vesting_info(env: Env, bounty_id: u64) -> Option<VestingInfo> {
+        let schedule: VestingSchedule = env
             .storage()
             .persistent()
-            .has(&DataKey::PendingClaim(bounty_id))
-        {
-            return Err(Error::BountyNotFound);
-        }
-        let mut claim: ClaimRecord = env
+            .get(&DataKey::Vesting(bounty_id))?;
+        let escrow: Escrow = env
             .storage()
             .persistent()
-            .get(&DataKey::PendingClaim(bounty_id))
-            .unwrap();
-
-        claim.recipient.require_auth();
+            .get(&DataKey::Escrow(bounty_id))?;
+        let vested = Self::vested_amount(&env, &escrow, &schedule);
+        let claimed = escrow.amount - escrow.remaining_amount;
+        // Nothing more can be claimed once the escrow has been cancelled
+        let claimable = if escrow.status == EscrowStatus::Locked {
+            vested - claimed
+        } else {
+            0
+        };
+        Some(VestingInfo {
+            start: schedule.start,
+            end: schedule.end,
+            vested,
+            claimed,
+            claimable,
+        })
+    }
 
+    /// Amount of `escrow` vested at the current ledger time, rounded down.
+    fn vested_amount(env: &Env, escrow: &Escrow, schedule: &VestingSchedule) -> i128 {
         let now = env.ledger().timestamp();
-        if now > claim.expires_at {
-            return Err(Error::DeadlineNotPassed); // reuse or add ClaimExpired error
-        }
-        if claim.claimed {
-            return Err(Error::FundsNotLocked);
+        if now <= schedule.start {
+            0
+        } else if now >= schedule.end {
+            escrow.amount
+        } else {
+            Self::mul_div(
+                env,
+                escrow.amount,
+                (now - schedule.start) as i128,
+                (schedule.end - schedule.start) as i128,
+            )
         }
-
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
-        client.transfer(
-            &env.current_contract_address(),
-            &claim.recipient,
-            &claim.amount,
-        );
-
-        // Update escrow status
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
-        escrow.status = EscrowStatus::Released;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
-
-        claim.claimed = true;
-        env.storage()
-            .persistent()
-            .set(&DataKey::PendingClaim(bounty_id), &claim);
-
-        env.events().publish(
-            (symbol_short!("claim"), symbol_short!("done")),
-            ClaimExecuted {
-                bounty_id,
-                recipient: claim.recipient.clone(),
-                amount: claim.amount,
-                claimed_at: now,
-            },
-        );
-        Ok(())
     }
 
-    /// Admin can cancel an expired or unwanted pending claim, returning escrow to Locked.
-    pub fn cancel_pending_claim(env: Env, bounty_id: u64) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
-        }
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
-
-        if !env
-            .storage()
-            .persistent()
-            .has(&DataKey::PendingClaim(bounty_id))
-        {
-            return Err(Error::BountyNotFound);
+    /// `value * numerator / denominator`, rounded down. The product is taken
+    /// in 256 bits when it does not fit in an i128, so this cannot overflow
+    /// as long as `numerator <= denominator`.
+    fn mul_div(env: &Env, value: i128, numerator: i128, denominator: i128) -> i128 {
+        match value.checked_mul(numerator) {
+            Some(product) => product / denominator,
+            None => I256::from_i128(env, value)
+                .mul(&I256::from_i128(env, numerator))
+                .div(&I256::from_i128(env, denominator))
+                .to_i128()
+                .unwrap(),
         }
-        let claim: ClaimRecord = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PendingClaim(bounty_id))
-            .unwrap();
+    }
 
-        if claim.claimed {
-            return Err(Error::FundsNotLocked);
+    fn check_not_vesting(env: &Env, bounty_id: u64) -> Result<(), Error> {
+        if env.storage().persistent().has(&DataKey::Vesting(bounty_id)) {
+            return Err(Error::VestingEscrow);
         }
-
-        env.storage()
-            .persistent()
-            .remove(&DataKey::PendingClaim(bounty_id));
-
-        env.events().publish(
-            (symbol_short!("claim"), symbol_short!("cancel")),
-            ClaimCancelled {
-                bounty_id,
-                recipient: claim.recipient,
-                amount: claim.amount,
-                cancelled_at: env.ledger().timestamp(),
-                cancelled_by: admin,
-            },
-        );
         Ok(())
     }
 
-    /// View: get pending claim for a bounty.
-    pub fn get_pending_claim(env: Env, bounty_id: u64) -> Result<ClaimRecord, Error> {
+    /// view function to get the release fee override of an escrow, in bps
+    pub fn get_fee_override(env: Env, bounty_id: u64) -> Option<i128> {
         env.storage()
             .persistent()
-            .get(&DataKey::PendingClaim(bounty_id))
-            .ok_or(Error::BountyNotFound)
+            .get(&DataKey::FeeOverride(bounty_id))
     }
 
-    /// Approve a refund before deadline (admin only).
-    /// This allows early refunds with admin approval.
-    pub fn approve_refund(
+    /// Lock funds for a bounty that pays out in stages.
+    ///
+    /// `milestones` holds (amount, deadline) pairs; the escrow amount is the sum
+    /// of the amounts and the escrow deadline is the latest milestone deadline.
+    /// Each slice is paid with `release_milestone`, and `refund` only returns
+    /// slices that are still unpaid after their own deadline.
+    pub fn lock_funds_with_milestones(
         env: Env,
+        depositor: Address,
         bounty_id: u64,
-        amount: i128,
-        recipient: Address,
-        mode: RefundMode,
+        milestones: Vec<(i128, u64)>,
     ) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
-        }
-
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
-
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
-        }
-
-        let escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
-
-        if escrow.status != EscrowStatus::Locked && escrow.status != EscrowStatus::PartiallyRefunded
-        {
-            return Err(Error::FundsNotLocked);
+        if milestones.is_empty() {
+            return Err(Error::InvalidAmount);
         }
 
-        if amount <= 0 || amount > escrow.remaining_amount {
-            return Err(Error::InvalidAmount);
+        let mut amount: i128 = 0;
+        let mut deadline: u64 = 0;
+        let mut slices: Vec<Milestone> = vec![&env];
+        for (milestone_amount, milestone_deadline) in milestones.iter() {
+            if milestone_amount <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            amount = amount
+                .checked_add(milestone_amount)
+                .ok_or(Error::InvalidAmount)?;
+            deadline = deadline.max(milestone_deadline);
+            slices.push_back(Milestone {
+                amount: milestone_amount,
+                deadline: milestone_deadline,
+                status: MilestoneStatus::Pending,
+            });
         }
 
-        let approval = RefundApproval {
-            bounty_id,
+        let escrow = Escrow {
+            depositor,
             amount,
-            recipient: recipient.clone(),
-            mode: mode.clone(),
-            approved_by: admin.clone(),
-            approved_at: env.ledger().timestamp(),
+            status: EscrowStatus::Locked,
+            deadline,
+            refund_history: vec![&env],
+            remaining_amount: amount,
+            milestones: slices,
+            arbiter: None,
+            memo: None,
+            project_id: 0,
+            release_reason: None,
         };
-
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
-
-        env.storage()
-            .persistent()
-            .set(&DataKey::RefundApproval(bounty_id), &approval);
-
-        Ok(())
+        Self::lock_escrow(&env, bounty_id, escrow, None)
     }
 
-    /// Release a partial amount of the locked funds to the contributor.
-    /// Only the admin (backend) can authorize this.
+    /// Add more funds to an escrow that is still Locked.
     ///
-    /// - `payout_amount` must be > 0 and <= `remaining_amount`.
-    /// - `remaining_amount` is decremented by `payout_amount` after each call.
-    /// - When `remaining_amount` reaches 0 the escrow status is set to Released.
-    /// - The bounty stays Locked while any funds remain unreleased.
-    pub fn partial_release(
+    /// Only the original depositor may top up. Both `amount` and `remaining_amount`
+    /// grow by `additional_amount`, which is checked like a `contribute`.
+    /// Milestone escrows cannot be topped up because their total is fixed by
+    /// the milestone slices.
+    pub fn top_up(
         env: Env,
+        depositor: Address,
         bounty_id: u64,
-        contributor: Address,
-        payout_amount: i128,
+        additional_amount: i128,
     ) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("lock")) {
+            return Err(Error::LockPaused);
+        }
+
+        Self::enter_guard(&env)?;
+
+        depositor.require_auth();
+
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::NotInitialized);
         }
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
-
         if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
             return Err(Error::BountyNotFound);
         }
@@ -1266,141 +2746,4499 @@ impl BountyEscrowContract {
             .get(&DataKey::Escrow(bounty_id))
             .unwrap();
 
+        if escrow.depositor != depositor {
+            return Err(Error::Unauthorized);
+        }
+
         if escrow.status != EscrowStatus::Locked {
             return Err(Error::FundsNotLocked);
         }
 
-        // Guard: zero or negative payout makes no sense and would corrupt state
-        if payout_amount <= 0 {
+        if additional_amount <= 0 || !escrow.milestones.is_empty() {
             return Err(Error::InvalidAmount);
         }
-
-        // Guard: prevent overpayment — payout cannot exceed what is still owed
-        if payout_amount > escrow.remaining_amount {
-            return Err(Error::InsufficientFunds);
-        }
-
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let old_amount = escrow.amount;
+        let new_amount = old_amount
+            .checked_add(additional_amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::check_added_amount(&env, additional_amount, new_amount)?;
+        Self::check_total_locked(&env, bounty_id, None, additional_amount)?;
+
+        Self::record_contribution(&env, bounty_id, &escrow, &depositor, additional_amount);
+        escrow.amount = new_amount;
+        escrow.remaining_amount = escrow
+            .remaining_amount
+            .checked_add(additional_amount)
+            .ok_or(Error::InvalidAmount)?;
+
+        let token_addr: Address = Self::escrow_token(&env, bounty_id);
         let client = token::Client::new(&env, &token_addr);
-
-        // Transfer only the requested partial amount to the contributor
         client.transfer(
+            &depositor,
             &env.current_contract_address(),
-            &contributor,
-            &payout_amount,
+            &additional_amount,
+        );
+        Self::update_depositor_stats(&env, &escrow.depositor, |stats| {
+            stats.total_locked = Self::add_to_stat(&env, stats.total_locked, additional_amount)
+        });
+        Self::record_daily_volume(&env, DailyMetric::Locked, additional_amount);
+        Self::record_history(
+            &env,
+            bounty_id,
+            EscrowAction::ToppedUp,
+            &depositor,
+            additional_amount,
         );
-
-        // Decrement remaining; this is always an exact integer subtraction — no rounding
-        escrow.remaining_amount -= payout_amount;
-
-        // Automatically transition to Released once fully paid out
-        if escrow.remaining_amount == 0 {
-            escrow.status = EscrowStatus::Released;
-        }
 
         env.storage()
             .persistent()
             .set(&DataKey::Escrow(bounty_id), &escrow);
 
-        events::emit_funds_released(
+        emit_funds_topped_up(
             &env,
-            FundsReleased {
-                version: EVENT_VERSION_V2,
+            FundsToppedUp {
+                version: EVENT_VERSION_V3,
                 bounty_id,
-                amount: payout_amount,
-                recipient: contributor.clone(),
+                depositor,
+                old_amount,
+                new_amount: escrow.amount,
+                token: token_addr,
                 timestamp: env.ledger().timestamp(),
             },
         );
 
+        Self::exit_guard(&env);
         Ok(())
     }
 
-    /// Refund funds to the original depositor if the deadline has passed.
-    /// Refunds the full remaining_amount (accounts for any prior partial releases).
-    pub fn refund(env: Env, bounty_id: u64) -> Result<(), Error> {
-        if Self::check_paused(&env, symbol_short!("refund")) {
-            return Err(Error::FundsPaused);
+    /// Co-fund an existing Locked escrow from another wallet.
+    ///
+    /// Each funder's share is tracked, so refunds and cancellations pay every
+    /// funder back pro-rata to what they put in, and the bounty shows up in
+    /// `query_escrows_by_depositor` for each of them. Release still goes to
+    /// the contributor as usual. Milestone escrows cannot be co-funded.
+    ///
+    /// `amount` must meet the minimum lock amount, and the escrow's new total
+    /// must stay within the maximum.
+    pub fn contribute(
+        env: Env,
+        bounty_id: u64,
+        contributor_depositor: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
         }
-
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
+        if Self::check_paused(&env, symbol_short!("lock")) {
+            return Err(Error::LockPaused);
         }
 
+        Self::enter_guard(&env)?;
+
+        contributor_depositor.require_auth();
+
         let mut escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+            .ok_or(Error::BountyNotFound)?;
 
         if escrow.status != EscrowStatus::Locked {
             return Err(Error::FundsNotLocked);
         }
+        if amount <= 0 || !escrow.milestones.is_empty() {
+            return Err(Error::InvalidAmount);
+        }
+        Self::check_depositor_allowed(&env, &contributor_depositor)?;
+        let old_amount = escrow.amount;
+        let new_amount = old_amount.checked_add(amount).ok_or(Error::InvalidAmount)?;
+        Self::check_added_amount(&env, amount, new_amount)?;
+        Self::check_total_locked(&env, bounty_id, None, amount)?;
+
+        Self::record_contribution(&env, bounty_id, &escrow, &contributor_depositor, amount);
+        escrow.amount = new_amount;
+        escrow.remaining_amount = escrow
+            .remaining_amount
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
 
-        let now = env.ledger().timestamp();
-        if now < escrow.deadline {
-            return Err(Error::DeadlineNotPassed);
+        let index_key = DataKey::DepositorIndex(contributor_depositor.clone());
+        let mut depositor_index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or(Vec::new(&env));
+        if !depositor_index.contains(bounty_id) {
+            depositor_index.push_back(bounty_id);
+            env.storage().persistent().set(&index_key, &depositor_index);
         }
 
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_addr = Self::escrow_token(&env, bounty_id);
         let client = token::Client::new(&env, &token_addr);
-
-        // Refund only what is still remaining (partial releases may have already gone out)
         client.transfer(
+            &contributor_depositor,
             &env.current_contract_address(),
-            &escrow.depositor,
-            &escrow.remaining_amount,
+            &amount,
+        );
+        // The co-funder's money is counted in their own stats, not the
+        // original depositor's
+        Self::update_depositor_stats(&env, &contributor_depositor, |stats| {
+            stats.total_locked = Self::add_to_stat(&env, stats.total_locked, amount)
+        });
+        Self::record_daily_volume(&env, DailyMetric::Locked, amount);
+        Self::record_history(
+            &env,
+            bounty_id,
+            EscrowAction::ToppedUp,
+            &contributor_depositor,
+            amount,
         );
 
-        escrow.status = EscrowStatus::Refunded;
+        emit_funds_topped_up(
+            &env,
+            FundsToppedUp {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                depositor: contributor_depositor,
+                old_amount,
+                new_amount: escrow.amount,
+                token: token_addr,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    /// view function listing (funder, amount) pairs for a bounty, original
+    /// depositor first
+    pub fn get_contributions(env: Env, bounty_id: u64) -> Result<Vec<(Address, i128)>, Error> {
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        Ok(Self::funder_shares(&env, bounty_id, &escrow))
+    }
+
+    /// Add `amount` to `funder`'s share. `escrow` is the state before the
+    /// new funds are added.
+    fn record_contribution(
+        env: &Env,
+        bounty_id: u64,
+        escrow: &Escrow,
+        funder: &Address,
+        amount: i128,
+    ) {
+        let mut shares = Self::funder_shares(env, bounty_id, escrow);
+        let mut found = false;
+        for i in 0..shares.len() {
+            let (addr, share) = shares.get(i).unwrap();
+            if addr == *funder {
+                shares.set(i, (addr, share + amount));
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            shares.push_back((funder.clone(), amount));
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Contributions(bounty_id), &shares);
+    }
+
+    /// Record who is working on a Locked bounty. `caller` must be the
+    /// depositor or the admin. Assigning again replaces the previous assignee.
+    ///
+    /// The bounty is added to the contributor's `query_escrows_by_contributor`
+    /// index, and a depositor can no longer `cancel_escrow` it.
+    pub fn assign_contributor(
+        env: Env,
+        caller: Address,
+        bounty_id: u64,
+        contributor: Address,
+    ) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        if caller != escrow.depositor && caller != admin {
+            return Err(Error::Unauthorized);
+        }
+        caller.require_auth();
+
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+
+        Self::set_assignee(&env, &caller, bounty_id, Some(contributor));
+        Ok(())
+    }
+
+    /// Replace the contributor on a Locked bounty that already has one, e.g.
+    /// when the original assignee abandoned the work. Same permissions as
+    /// `assign_contributor`; returns NotAssignedContributor if nobody is
+    /// assigned.
+    pub fn reassign_contributor(
+        env: Env,
+        caller: Address,
+        bounty_id: u64,
+        new_contributor: Address,
+    ) -> Result<(), Error> {
+        Self::check_assignment_change(&env, &caller, bounty_id)?;
+        Self::set_assignee(&env, &caller, bounty_id, Some(new_contributor));
+        Ok(())
+    }
+
+    /// Clear the assignee of a Locked bounty. Under `require_assignment`,
+    /// releases fail until someone is assigned again.
+    pub fn unassign_contributor(env: Env, caller: Address, bounty_id: u64) -> Result<(), Error> {
+        Self::check_assignment_change(&env, &caller, bounty_id)?;
+        Self::set_assignee(&env, &caller, bounty_id, None);
+        Ok(())
+    }
+
+    /// view function to get every assignment change for a bounty, oldest first.
+    /// Only the last `MAX_ASSIGNMENT_HISTORY` (20) changes are kept.
+    pub fn get_assignment_history(env: Env, bounty_id: u64) -> Vec<AssignmentRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AssignmentHistory(bounty_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    fn check_assignment_change(env: &Env, caller: &Address, bounty_id: u64) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        if *caller != escrow.depositor && *caller != admin {
+            return Err(Error::Unauthorized);
+        }
+        caller.require_auth();
+
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Assignee(bounty_id))
+        {
+            return Err(Error::NotAssignedContributor);
+        }
+        Ok(())
+    }
+
+    fn set_assignee(env: &Env, caller: &Address, bounty_id: u64, contributor: Option<Address>) {
+        let timestamp = env.ledger().timestamp();
+        match &contributor {
+            Some(addr) => {
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::Assignee(bounty_id), addr);
+                Self::index_contributor(env, bounty_id, addr);
+                events::emit_bounty_assigned(
+                    env,
+                    events::BountyAssigned {
+                        version: EVENT_VERSION_V3,
+                        bounty_id,
+                        contributor: addr.clone(),
+                        assigned_by: caller.clone(),
+                        timestamp,
+                    },
+                );
+            }
+            None => {
+                let previous: Address = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Assignee(bounty_id))
+                    .unwrap();
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::Assignee(bounty_id));
+                events::emit_bounty_unassigned(
+                    env,
+                    events::BountyUnassigned {
+                        version: EVENT_VERSION_V3,
+                        bounty_id,
+                        contributor: previous,
+                        unassigned_by: caller.clone(),
+                        timestamp,
+                    },
+                );
+            }
+        }
+
+        let action = if contributor.is_some() {
+            EscrowAction::Assigned
+        } else {
+            EscrowAction::Unassigned
+        };
+        Self::record_history(env, bounty_id, action, caller, 0);
+
+        let mut history: Vec<AssignmentRecord> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AssignmentHistory(bounty_id))
+            .unwrap_or(Vec::new(env));
+        if history.len() >= MAX_ASSIGNMENT_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(AssignmentRecord {
+            contributor,
+            changed_by: caller.clone(),
+            timestamp,
+        });
+        env.storage()
+            .persistent()
+            .set(&DataKey::AssignmentHistory(bounty_id), &history);
+    }
+
+    /// view function to get the contributor assigned to a bounty, if any
+    pub fn get_assigned_contributor(env: Env, bounty_id: u64) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Assignee(bounty_id))
+    }
+
+    /// When enabled, every release path only pays the assigned contributor
+    /// (admin only). Split releases require every leg to go to the assignee.
+    pub fn set_require_assignment(env: Env, required: bool) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::RequireAssignment, &required);
+        Ok(())
+    }
+
+    fn check_assignee(env: &Env, bounty_id: u64, contributor: &Address) -> Result<(), Error> {
+        let required: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::RequireAssignment)
+            .unwrap_or(false);
+        if !required {
+            return Ok(());
+        }
+        let assignee: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Assignee(bounty_id));
+        if assignee.as_ref() != Some(contributor) {
+            return Err(Error::NotAssignedContributor);
+        }
+        Ok(())
+    }
+
+    fn check_not_blocked(env: &Env, recipient: &Address) -> Result<(), Error> {
+        if Self::is_blocked(env.clone(), recipient.clone()) {
+            return Err(Error::ContributorBlocked);
+        }
+        Ok(())
+    }
+
+    /// Block all future payouts to `contributor` (admin only). Every release
+    /// path, including pending claims, then fails with ContributorBlocked.
+    /// Escrows already released are not affected.
+    pub fn block_contributor(env: Env, contributor: Address) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage()
+            .persistent()
+            .set(&DataKey::BlockedContributor(contributor.clone()), &true);
+        emit_contributor_blocked(
+            &env,
+            ContributorBlocked {
+                version: EVENT_VERSION_V2,
+                contributor,
+                admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Lift a block set by `block_contributor` (admin only).
+    pub fn unblock_contributor(env: Env, contributor: Address) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::BlockedContributor(contributor.clone()));
+        emit_contributor_unblocked(
+            &env,
+            ContributorBlocked {
+                version: EVENT_VERSION_V2,
+                contributor,
+                admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// view function to check whether payouts to `contributor` are blocked
+    pub fn is_blocked(env: Env, contributor: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::BlockedContributor(contributor))
+    }
+
+    /// Push back the deadline of a Locked escrow.
+    ///
+    /// `caller` must be the depositor or the admin. `new_deadline` must be later
+    /// than both the current deadline and the current ledger time, otherwise
+    /// InvalidDeadline is returned, and within `max_deadline_duration` of now,
+    /// otherwise DeadlineTooFar. Milestone escrows keep a deadline per
+    /// milestone and cannot be extended this way.
+    pub fn extend_deadline(
+        env: Env,
+        caller: Address,
+        bounty_id: u64,
+        new_deadline: u64,
+    ) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        if caller != escrow.depositor && caller != admin {
+            return Err(Error::Unauthorized);
+        }
+        caller.require_auth();
+
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+
+        if !escrow.milestones.is_empty()
+            || new_deadline <= escrow.deadline
+            || new_deadline <= env.ledger().timestamp()
+        {
+            return Err(Error::InvalidDeadline);
+        }
+        Self::check_deadline(&env, new_deadline)?;
+
+        let old_deadline = escrow.deadline;
+        escrow.deadline = new_deadline;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+        Self::record_history(&env, bounty_id, EscrowAction::DeadlineExtended, &caller, 0);
+
+        emit_deadline_extended(
+            &env,
+            DeadlineExtended {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                old_deadline,
+                new_deadline,
+                extended_by: caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Shared lock path: validates, pulls `escrow.amount` from the depositor,
+    /// stores the record and updates the indexes.
+    fn lock_escrow(
+        env: &Env,
+        bounty_id: u64,
+        escrow: Escrow,
+        token: Option<Address>,
+    ) -> Result<(), Error> {
+        let depositor = escrow.depositor.clone();
+        let amount = escrow.amount;
+        let deadline = escrow.deadline;
+
+        // Apply rate limiting
+        Self::check_lock_rate(env, &depositor)?;
+
+        if Self::is_contract_paused(env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(env, symbol_short!("lock")) {
+            return Err(Error::LockPaused);
+        }
+        Self::enter_guard(env)?;
+
+        // Verify depositor authorization
+        depositor.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        if env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyAlreadyExists);
+        }
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::check_depositor_allowed(env, &depositor)?;
+        Self::check_amount_policy(env, amount)?;
+        Self::check_total_locked(env, bounty_id, token.clone(), amount)?;
+        Self::check_deadline(env, deadline)?;
+
+        // Extend the TTL of the storage entry to ensure it lives long enough
         env.storage()
             .persistent()
             .set(&DataKey::Escrow(bounty_id), &escrow);
+        env.storage()
+            .persistent()
+            .set(&DataKey::LockedAt(bounty_id), &env.ledger().timestamp());
+
+        // Update indexes
+        let mut index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(env));
+        index.push_back(bounty_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::EscrowIndex, &index);
+
+        let mut depositor_index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DepositorIndex(depositor.clone()))
+            .unwrap_or(Vec::new(env));
+        depositor_index.push_back(bounty_id);
+        env.storage().persistent().set(
+            &DataKey::DepositorIndex(depositor.clone()),
+            &depositor_index,
+        );
+
+        // Same answer as escrow_token, without reading back what was just written
+        let token_addr = match token {
+            Some(token) => {
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::EscrowToken(bounty_id), &token);
+                token
+            }
+            None => env.storage().instance().get(&DataKey::Token).unwrap(),
+        };
+
+        // Transfer funds from depositor to contract once the escrow is recorded
+        let client = token::Client::new(env, &token_addr);
+        client.transfer(&depositor, &env.current_contract_address(), &amount);
+        Self::record_depositor_lock(env, &depositor, amount);
+        Self::record_history(env, bounty_id, EscrowAction::Locked, &depositor, amount);
+        Self::record_daily_volume(env, DailyMetric::Locked, amount);
+
+        // Emit value allows for off-chain indexing
+        emit_funds_locked(
+            env,
+            FundsLocked {
+                version: EVENT_VERSION_V3,
+                token: token_addr.clone(),
+                timestamp: env.ledger().timestamp(),
+                bounty_id,
+                amount,
+                depositor,
+                deadline,
+            },
+        );
+
+        Self::exit_guard(env);
+        Ok(())
+    }
+
+    /// Token an escrow is held in. Escrows without an explicit token, including
+    /// every escrow locked before per-bounty tokens existed, use the init token.
+    fn escrow_token(env: &Env, bounty_id: u64) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EscrowToken(bounty_id))
+            .unwrap_or_else(|| env.storage().instance().get(&DataKey::Token).unwrap())
+    }
+
+    /// Enforce min/max amount policy if one has been configured (Issue #62).
+    /// When no policy is set this check is skipped entirely, preserving
+    /// backward-compatible behaviour for callers that never call set_amount_policy.
+    fn check_amount_policy(env: &Env, amount: i128) -> Result<(), Error> {
+        // A limit of 0 means no limit on that side
+        let (min_amount, max_amount) = Self::get_amount_limits(env.clone());
+        if min_amount > 0 && amount < min_amount {
+            return Err(Error::AmountBelowMinimum);
+        }
+        if max_amount > 0 && amount > max_amount {
+            return Err(Error::AmountAboveMaximum);
+        }
+        Ok(())
+    }
+
+    /// Lock limits for funds added to an escrow that is already locked, by
+    /// `top_up` or `contribute`: the added `amount` must meet the minimum and
+    /// the escrow's `new_total` must stay within the maximum.
+    fn check_added_amount(env: &Env, amount: i128, new_total: i128) -> Result<(), Error> {
+        let (min_amount, max_amount) = Self::get_amount_limits(env.clone());
+        if min_amount > 0 && amount < min_amount {
+            return Err(Error::AmountBelowMinimum);
+        }
+        if max_amount > 0 && new_total > max_amount {
+            return Err(Error::AmountAboveMaximum);
+        }
+        Ok(())
+    }
+
+    /// With `max_total_locked` set, what the escrows in the escrow's token
+    /// still hold plus `amount` must stay within it. `token` is looked up from
+    /// the bounty when not given, and only once a cap is set.
+    fn check_total_locked(
+        env: &Env,
+        bounty_id: u64,
+        token: Option<Address>,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let max_total = Self::get_max_total_locked(env.clone());
+        if max_total == 0 {
+            return Ok(());
+        }
+        let token = token.unwrap_or_else(|| Self::escrow_token(env, bounty_id));
+        let held = Self::expected_locked(env, token)?;
+        if held.saturating_add(amount) > max_total {
+            return Err(Error::AmountExceedsMax);
+        }
+        Ok(())
+    }
+
+    /// A new or extended deadline must be in the future and, when a maximum
+    /// duration is set, no later than `now + max_deadline_duration`.
+    fn check_deadline(env: &Env, deadline: u64) -> Result<(), Error> {
+        let (earliest, latest) = Self::get_deadline_limits(env.clone());
+        if deadline < earliest {
+            return Err(Error::DeadlineInPast);
+        }
+        if let Some(latest) = latest {
+            if deadline > latest {
+                return Err(Error::DeadlineTooFar);
+            }
+        }
+        Ok(())
+    }
+
+    /// Set how far in the future, in seconds, a new escrow's deadline may be
+    /// (admin only). 0 removes the limit. Escrows already locked keep their
+    /// deadline, but `extend_deadline` cannot push it past the limit.
+    pub fn set_max_deadline_duration(env: Env, max_duration: u64) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxDeadlineDuration, &max_duration);
+        Ok(())
+    }
+
+    /// view function to get the maximum deadline duration; 0 means no limit
+    pub fn get_max_deadline_duration(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxDeadlineDuration)
+            .unwrap_or(0)
+    }
+
+    /// Set how long after a deadline, in seconds, refunds stay closed so the
+    /// depositor can still release to a late contributor (admin only). 0
+    /// opens refunds at the deadline. Applies to escrows already locked.
+    pub fn set_refund_grace_period(env: Env, grace_period: u64) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::RefundGracePeriod, &grace_period);
+        Ok(())
+    }
+
+    /// view function to get the refund grace period in seconds
+    pub fn get_refund_grace_period(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RefundGracePeriod)
+            .unwrap_or(0)
+    }
+
+    /// Ledger time from which an escrow or milestone with `deadline` can be
+    /// refunded.
+    fn refund_opens_at(env: &Env, deadline: u64) -> u64 {
+        deadline.saturating_add(Self::get_refund_grace_period(env.clone()))
+    }
+
+    /// view function to get the earliest and latest deadline a lock made now
+    /// would accept; the latest is None when there is no maximum duration
+    pub fn get_deadline_limits(env: Env) -> (u64, Option<u64>) {
+        let now = env.ledger().timestamp();
+        let max_duration = Self::get_max_deadline_duration(env);
+        let latest = if max_duration == 0 {
+            None
+        } else {
+            Some(now.saturating_add(max_duration))
+        };
+        (now + 1, latest)
+    }
+
+    /// Release funds to the contributor.
+    /// Only the admin (backend) can authorize this.
+    pub fn release_funds(env: Env, bounty_id: u64, contributor: Address) -> Result<(), Error> {
+        Self::release_funds_with_reason(env, bounty_id, contributor, None)
+    }
+
+    /// Release funds to the contributor and record why, e.g. `completed`,
+    /// `early` or `dispute`. The reason is kept on the escrow, returned by
+    /// `get_escrow_info` and carried by the release event; None records the
+    /// generic `release` code used by `release_funds`.
+    /// Only the admin (backend) can authorize this.
+    pub fn release_funds_with_reason(
+        env: Env,
+        bounty_id: u64,
+        contributor: Address,
+        reason: Option<Symbol>,
+    ) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::ReleasePaused);
+        }
+        let _start = env.ledger().timestamp();
+
+        Self::enter_guard(&env)?;
+
+        // Ensure contract is initialized
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        if escrow.status == EscrowStatus::Disputed {
+            return Err(Error::EscrowDisputed);
+        }
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        Self::check_not_vesting(&env, bounty_id)?;
+        Self::check_assignee(&env, bounty_id, &contributor)?;
+        Self::check_not_blocked(&env, &contributor)?;
+
+        let reason = reason.unwrap_or(symbol_short!("release"));
+        let (threshold, delay) = Self::get_release_timelock(env.clone());
+        if delay > 0 && escrow.remaining_amount > threshold {
+            Self::queue_release(&env, bounty_id, escrow, &contributor, delay, reason);
+        } else {
+            Self::settle_release(&env, bounty_id, escrow, &contributor, reason);
+        }
+
+        Self::exit_guard(&env);
+
+        Ok(())
+    }
+
+    /// Make `release_funds` wait `delay_seconds` before paying out escrows
+    /// whose amount is above `threshold_amount` (admin only). The depositor
+    /// can call `cancel_pending_release` during the delay. A delay of 0
+    /// turns the timelock off; releases already queued keep their time.
+    ///
+    /// The other admin payouts (`partial_release`, the split releases,
+    /// `release_milestone`, `authorize_claim` and `batch_release_funds`)
+    /// return ReleaseTimelocked above the threshold, so a large payout always
+    /// goes through the queue.
+    pub fn set_release_timelock(
+        env: Env,
+        threshold_amount: i128,
+        delay_seconds: u64,
+    ) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        if threshold_amount < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        env.storage().instance().set(
+            &DataKey::ReleaseTimelock,
+            &(threshold_amount, delay_seconds),
+        );
+        Ok(())
+    }
+
+    /// view function to get the release timelock as (threshold_amount, delay_seconds)
+    pub fn get_release_timelock(env: Env) -> (i128, u64) {
+        env.storage()
+            .instance()
+            .get(&DataKey::ReleaseTimelock)
+            .unwrap_or((0, 0))
+    }
+
+    fn queue_release(
+        env: &Env,
+        bounty_id: u64,
+        mut escrow: Escrow,
+        contributor: &Address,
+        delay: u64,
+        reason: Symbol,
+    ) {
+        let pending = PendingRelease {
+            recipient: contributor.clone(),
+            amount: escrow.remaining_amount,
+            executable_at: env.ledger().timestamp().saturating_add(delay),
+            reason,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingRelease(bounty_id), &pending);
+
+        escrow.status = EscrowStatus::PendingRelease;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+
+        emit_release_queued(
+            env,
+            ReleaseQueued {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                recipient: pending.recipient,
+                amount: pending.amount,
+                executable_at: pending.executable_at,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Pay out a release queued by `release_funds` once its timelock has
+    /// passed. Anyone may call this; the recipient was fixed when the
+    /// release was queued.
+    pub fn execute_release(env: Env, bounty_id: u64) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::ReleasePaused);
+        }
+
+        Self::enter_guard(&env)?;
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        if escrow.status != EscrowStatus::PendingRelease {
+            return Err(Error::FundsNotLocked);
+        }
+        let pending: PendingRelease = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingRelease(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        if env.ledger().timestamp() < pending.executable_at {
+            return Err(Error::ReleaseTimelocked);
+        }
+        Self::check_not_blocked(&env, &pending.recipient)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingRelease(bounty_id));
+        Self::settle_release(&env, bounty_id, escrow, &pending.recipient, pending.reason);
+
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    /// Depositor stops a queued release before it becomes executable,
+    /// returning the escrow to Locked.
+    pub fn cancel_pending_release(env: Env, bounty_id: u64) -> Result<(), Error> {
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::PendingRelease {
+            return Err(Error::FundsNotLocked);
+        }
+        let pending: PendingRelease = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingRelease(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        let now = env.ledger().timestamp();
+        if now >= pending.executable_at {
+            return Err(Error::ReleaseWindowClosed);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingRelease(bounty_id));
+        escrow.status = EscrowStatus::Locked;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+
+        emit_release_cancelled(
+            &env,
+            ReleaseCancelled {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                recipient: pending.recipient,
+                amount: pending.amount,
+                cancelled_by: escrow.depositor,
+                timestamp: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// view function to get the release queued for a bounty, if any
+    pub fn get_pending_release(env: Env, bounty_id: u64) -> Option<PendingRelease> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingRelease(bounty_id))
+    }
+
+    /// Release the locked funds to the contributor on the arbiter's authority.
+    /// Only valid for escrows locked with `lock_funds_with_arbiter`.
+    pub fn arbiter_release_funds(
+        env: Env,
+        bounty_id: u64,
+        contributor: Address,
+    ) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::ReleasePaused);
+        }
+
+        Self::enter_guard(&env)?;
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        let arbiter = escrow.arbiter.clone().ok_or(Error::ArbiterNotSet)?;
+        arbiter.require_auth();
+
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        Self::check_assignee(&env, bounty_id, &contributor)?;
+        Self::check_not_blocked(&env, &contributor)?;
+
+        Self::settle_release(
+            &env,
+            bounty_id,
+            escrow,
+            &contributor,
+            symbol_short!("arbiter"),
+        );
+
+        Self::exit_guard(&env);
+
+        Ok(())
+    }
+
+    /// Pays whatever is still held in escrow to `contributor` and marks it Released,
+    /// recording `reason` as the release reason.
+    fn settle_release(
+        env: &Env,
+        bounty_id: u64,
+        mut escrow: Escrow,
+        contributor: &Address,
+        reason: Symbol,
+    ) {
+        for i in 0..escrow.milestones.len() {
+            let mut milestone = escrow.milestones.get(i).unwrap();
+            milestone.status = MilestoneStatus::Released;
+            escrow.milestones.set(i, milestone);
+        }
+        // Pay out what is still held; partial releases may already have
+        // drawn down part of `escrow.amount`.
+        let amount = escrow.remaining_amount;
+        escrow.remaining_amount = 0;
+        escrow.status = EscrowStatus::Released;
+        escrow.release_reason = Some(reason.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+        Self::index_contributor(env, bounty_id, contributor);
+        Self::record_depositor_close(env, &escrow.depositor, false);
+
+        // Transfer funds to contributor, less the platform fee
+        let token_addr: Address = Self::escrow_token(env, bounty_id);
+        let client = token::Client::new(env, &token_addr);
+        let (net, fee) = Self::pay_release(env, bounty_id, &client, contributor, amount);
+
+        emit_funds_released(
+            env,
+            FundsReleased {
+                version: EVENT_VERSION_V3,
+                token: token_addr.clone(),
+                bounty_id,
+                depositor: escrow.depositor.clone(),
+                gross: net + fee,
+                amount: net,
+                fee,
+                recipient: contributor.clone(),
+                reason,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Release the locked funds to several contributors in a single transaction.
+    /// Only the admin (backend) can authorize this.
+    ///
+    /// - `payouts` is a list of (recipient, amount) pairs; every amount must be > 0.
+    /// - The amounts must sum exactly to `remaining_amount`, otherwise
+    ///   SplitAmountMismatch is returned and nothing is transferred.
+    /// - The escrow is marked Released only after every transfer has gone out,
+    ///   and a single split release event lists all recipients.
+    /// - The legs are kept for `get_release_breakdown`.
+    pub fn release_funds_split(
+        env: Env,
+        bounty_id: u64,
+        payouts: Vec<(Address, i128)>,
+    ) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::ReleasePaused);
+        }
+
+        Self::enter_guard(&env)?;
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+
+        if payouts.is_empty() {
+            return Err(Error::InvalidAmount);
+        }
+
+        // Validate every leg before moving any tokens
+        let mut total: i128 = 0;
+        for (recipient, amount) in payouts.iter() {
+            if amount <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            Self::check_assignee(&env, bounty_id, &recipient)?;
+            Self::check_not_blocked(&env, &recipient)?;
+            total = total.checked_add(amount).ok_or(Error::InvalidAmount)?;
+        }
+        if total != escrow.remaining_amount {
+            return Err(Error::SplitAmountMismatch);
+        }
+        Self::check_release_timelock(&env, total)?;
+
+        let token_addr: Address = Self::escrow_token(&env, bounty_id);
+        let client = token::Client::new(&env, &token_addr);
+
+        // Each leg pays its own share of the platform fee
+        let mut net_payouts: Vec<(Address, i128)> = vec![&env];
+        let mut total_net: i128 = 0;
+        let mut total_fee: i128 = 0;
+        for (recipient, amount) in payouts.iter() {
+            let (net, fee) = Self::pay_release(&env, bounty_id, &client, &recipient, amount);
+            Self::index_contributor(&env, bounty_id, &recipient);
+            net_payouts.push_back((recipient, net));
+            total_net += net;
+            total_fee += fee;
+        }
+
+        escrow.remaining_amount = 0;
+        escrow.status = EscrowStatus::Released;
+        escrow.release_reason = Some(symbol_short!("split"));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+        Self::record_depositor_close(&env, &escrow.depositor, false);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReleaseBreakdown(bounty_id), &payouts);
+
+        emit_funds_released_split(
+            &env,
+            FundsReleasedSplit {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                amount: total_net,
+                fee: total_fee,
+                recipients: net_payouts,
+                token: token_addr,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Self::exit_guard(&env);
+
+        Ok(())
+    }
+
+    /// Release the locked funds to several contributors by basis-point share.
+    /// Only the admin (backend) can authorize this.
+    ///
+    /// - `recipients` is a list of (recipient, share) pairs; every share must be
+    ///   > 0 and the shares must sum to exactly 10_000.
+    /// - Each payout is `amount * share / 10_000`. Rounding dust goes to the
+    ///   first recipient so the full amount leaves escrow.
+    /// - The computed legs are then released exactly like `release_funds_split`.
+    pub fn release_split(
+        env: Env,
+        bounty_id: u64,
+        recipients: Vec<(Address, u32)>,
+    ) -> Result<(), Error> {
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        if recipients.is_empty() {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut total_shares: i128 = 0;
+        for (recipient, share) in recipients.iter() {
+            if share == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            Self::check_assignee(&env, bounty_id, &recipient)?;
+            Self::check_not_blocked(&env, &recipient)?;
+            total_shares += share as i128;
+        }
+        if total_shares != BASIS_POINTS {
+            return Err(Error::SplitAmountMismatch);
+        }
+
+        let amount = escrow.remaining_amount;
+        let mut payouts: Vec<(Address, i128)> = vec![&env];
+        let mut allocated: i128 = 0;
+        for (recipient, share) in recipients.iter() {
+            let payout = amount
+                .checked_mul(share as i128)
+                .and_then(|x| x.checked_div(BASIS_POINTS))
+                .ok_or(Error::InvalidAmount)?;
+            payouts.push_back((recipient, payout));
+            allocated += payout;
+        }
+
+        let (first, first_payout) = payouts.get(0).unwrap();
+        payouts.set(0, (first, first_payout + (amount - allocated)));
+
+        Self::release_funds_split(env, bounty_id, payouts)
+    }
+
+    /// Returns the (recipient, gross amount) legs recorded by the last split
+    /// release of a bounty, or an empty list if it was not released by split.
+    pub fn get_release_breakdown(env: Env, bounty_id: u64) -> Result<Vec<(Address, i128)>, Error> {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseBreakdown(bounty_id))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    /// Set the claim window duration (admin only).
+    /// claim_window: seconds beneficiary has to claim after release is authorized.
+    pub fn set_claim_window(env: Env, claim_window: u64) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::ClaimWindow, &claim_window);
+        Ok(())
+    }
+
+    /// Authorize a release as a pending claim instead of immediate transfer.
+    /// Admin calls this instead of release_funds when claim period is active.
+    /// Beneficiary must call claim() within the window to receive funds.
+    ///
+    /// Nothing is transferred yet: the escrow moves to PendingClaim, which
+    /// blocks every other release and refund path. Once the window expires
+    /// the escrow can be refunded under the usual rules.
+    pub fn authorize_claim(env: Env, bounty_id: u64, recipient: Address) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::ReleasePaused);
+        }
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        Self::check_not_vesting(&env, bounty_id)?;
+        Self::check_assignee(&env, bounty_id, &recipient)?;
+        Self::check_not_blocked(&env, &recipient)?;
+        Self::check_release_timelock(&env, escrow.remaining_amount)?;
+
+        let now = env.ledger().timestamp();
+        let claim_window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ClaimWindow)
+            .unwrap_or(0);
+        let claim = ClaimRecord {
+            bounty_id,
+            recipient: recipient.clone(),
+            amount: escrow.remaining_amount,
+            expires_at: now.saturating_add(claim_window),
+            claimed: false,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingClaim(bounty_id), &claim);
+
+        escrow.status = EscrowStatus::PendingClaim;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+
+        emit_claim_created(
+            &env,
+            ClaimCreated {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                recipient,
+                amount: claim.amount,
+                expires_at: claim.expires_at,
+                timestamp: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Beneficiary calls this to claim their authorized funds within the window.
+    pub fn claim(env: Env, bounty_id: u64) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::ReleasePaused);
+        }
+
+        Self::enter_guard(&env)?;
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        if escrow.status != EscrowStatus::PendingClaim {
+            return Err(Error::FundsNotLocked);
+        }
+
+        let mut claim: ClaimRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingClaim(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        claim.recipient.require_auth();
+
+        let now = env.ledger().timestamp();
+        if now > claim.expires_at {
+            return Err(Error::ClaimExpired);
+        }
+        Self::check_not_blocked(&env, &claim.recipient)?;
+
+        // settle_release publishes the one `release` event for the claim
+        Self::settle_release(
+            &env,
+            bounty_id,
+            escrow,
+            &claim.recipient,
+            symbol_short!("claim"),
+        );
+
+        claim.claimed = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingClaim(bounty_id), &claim);
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    fn is_claim_expired(env: &Env, bounty_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get::<DataKey, ClaimRecord>(&DataKey::PendingClaim(bounty_id))
+            .map(|claim| env.ledger().timestamp() > claim.expires_at)
+            .unwrap_or(true)
+    }
+
+    /// Admin can cancel an expired or unwanted pending claim, returning escrow to Locked.
+    pub fn cancel_pending_claim(env: Env, bounty_id: u64) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::PendingClaim(bounty_id))
+        {
+            return Err(Error::BountyNotFound);
+        }
+        let claim: ClaimRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingClaim(bounty_id))
+            .unwrap();
+
+        if claim.claimed {
+            return Err(Error::FundsNotLocked);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingClaim(bounty_id));
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+        if escrow.status == EscrowStatus::PendingClaim {
+            escrow.status = EscrowStatus::Locked;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Escrow(bounty_id), &escrow);
+        }
+
+        emit_claim_cancelled(
+            &env,
+            ClaimCancelled {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                recipient: claim.recipient,
+                amount: claim.amount,
+                cancelled_at: env.ledger().timestamp(),
+                cancelled_by: admin,
+            },
+        );
+        Ok(())
+    }
+
+    /// View: get pending claim for a bounty.
+    pub fn get_pending_claim(env: Env, bounty_id: u64) -> Result<ClaimRecord, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingClaim(bounty_id))
+            .ok_or(Error::BountyNotFound)
+    }
+
+    /// Ask to be paid for a Locked escrow (contributor only). The depositor
+    /// then pays out with `approve_claim` or turns it down with
+    /// `reject_claim`, so they never need the contributor's address up front.
+    ///
+    /// One claim can be pending per bounty; the usual release rules
+    /// (assignee, blocked contributors) are checked here and again on approval.
+    pub fn submit_claim(env: Env, bounty_id: u64, contributor: Address) -> Result<(), Error> {
+        contributor.require_auth();
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::ClaimSubmission(bounty_id))
+        {
+            return Err(Error::ClaimAlreadySubmitted);
+        }
+        Self::check_assignee(&env, bounty_id, &contributor)?;
+        Self::check_not_blocked(&env, &contributor)?;
+
+        let submitted_at = env.ledger().timestamp();
+        env.storage().persistent().set(
+            &DataKey::ClaimSubmission(bounty_id),
+            &ClaimSubmission {
+                contributor: contributor.clone(),
+                submitted_at,
+            },
+        );
+
+        emit_claim_submitted(
+            &env,
+            ClaimSubmitted {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                contributor,
+                timestamp: submitted_at,
+            },
+        );
+        Ok(())
+    }
+
+    /// Pay the escrow to the contributor who submitted a claim (depositor only).
+    /// The release timelock does not apply, since the depositor signs off here.
+    pub fn approve_claim(env: Env, bounty_id: u64) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::ReleasePaused);
+        }
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        escrow.depositor.require_auth();
+
+        let submission: ClaimSubmission = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ClaimSubmission(bounty_id))
+            .ok_or(Error::ClaimNotSubmitted)?;
+
+        if escrow.status == EscrowStatus::Disputed {
+            return Err(Error::EscrowDisputed);
+        }
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        Self::check_not_vesting(&env, bounty_id)?;
+        Self::check_assignee(&env, bounty_id, &submission.contributor)?;
+        Self::check_not_blocked(&env, &submission.contributor)?;
+
+        Self::enter_guard(&env)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ClaimSubmission(bounty_id));
+        // Published as a `release` event with reason `approved`
+        Self::settle_release(
+            &env,
+            bounty_id,
+            escrow,
+            &submission.contributor,
+            symbol_short!("approved"),
+        );
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    /// Turn down the pending claim so another contributor can submit one
+    /// (depositor only).
+    pub fn reject_claim(env: Env, bounty_id: u64) -> Result<(), Error> {
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        escrow.depositor.require_auth();
+
+        let submission: ClaimSubmission = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ClaimSubmission(bounty_id))
+            .ok_or(Error::ClaimNotSubmitted)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ClaimSubmission(bounty_id));
+
+        emit_claim_rejected(
+            &env,
+            ClaimRejected {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                contributor: submission.contributor,
+                rejected_by: escrow.depositor,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// view function to get the claim awaiting the depositor, if any
+    pub fn get_claim_submission(env: Env, bounty_id: u64) -> Option<ClaimSubmission> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ClaimSubmission(bounty_id))
+    }
+
+    /// Approve a refund before deadline (admin only).
+    /// This allows early refunds with admin approval.
+    pub fn approve_refund(
+        env: Env,
+        bounty_id: u64,
+        amount: i128,
+        recipient: Address,
+        mode: RefundMode,
+    ) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        if escrow.status != EscrowStatus::Locked && escrow.status != EscrowStatus::PartiallyRefunded
+        {
+            return Err(Error::FundsNotLocked);
+        }
+
+        if amount <= 0 || amount > escrow.remaining_amount {
+            return Err(Error::InvalidAmount);
+        }
+
+        let approval = RefundApproval {
+            bounty_id,
+            amount,
+            recipient: recipient.clone(),
+            mode: mode.clone(),
+            approved_by: admin.clone(),
+            approved_at: env.ledger().timestamp(),
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::RefundApproval(bounty_id), &approval);
+
+        events::emit_refund_approved(
+            &env,
+            events::RefundApproved {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                amount,
+                recipient,
+                mode,
+                approved_by: admin,
+                timestamp: approval.approved_at,
+            },
+        );
+        Ok(())
+    }
+
+    /// Ask the admin for an early refund of a Locked escrow (depositor only).
+    ///
+    /// Only one request can be pending per bounty, and only before the
+    /// deadline; after it the depositor can call `refund` directly.
+    /// `reason_code` is free-form and ends up in the refund history if the
+    /// admin approves.
+    pub fn request_refund(env: Env, bounty_id: u64, reason_code: u32) -> Result<(), Error> {
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        let now = env.ledger().timestamp();
+        if now >= escrow.deadline {
+            return Err(Error::DeadlinePassed);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::RefundRequest(bounty_id))
+        {
+            return Err(Error::RefundAlreadyRequested);
+        }
+
+        env.storage().persistent().set(
+            &DataKey::RefundRequest(bounty_id),
+            &RefundRequest {
+                bounty_id,
+                reason_code,
+                requested_at: now,
+            },
+        );
+        events::emit_refund_requested(
+            &env,
+            events::RefundRequested {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                depositor: escrow.depositor,
+                reason_code,
+                timestamp: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Send the depositor's refunds for `bounty_id` to `refund_to` instead of
+    /// the depositor, e.g. after rotating away from the wallet that funded it.
+    /// Depositor only, while the escrow is Locked.
+    pub fn set_refund_address(env: Env, bounty_id: u64, refund_to: Address) -> Result<(), Error> {
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::RefundAddress(bounty_id), &refund_to);
+
+        events::emit_refund_address_set(
+            &env,
+            events::RefundAddressSet {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                depositor: escrow.depositor,
+                refund_to,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// view function to get the address refunds for `bounty_id` are paid to
+    pub fn get_refund_address(env: Env, bounty_id: u64) -> Result<Address, Error> {
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        Ok(Self::refund_recipient(&env, bounty_id, &escrow.depositor))
+    }
+
+    /// view function to get the pending refund request for a bounty, if any
+    pub fn get_refund_request(env: Env, bounty_id: u64) -> Option<RefundRequest> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RefundRequest(bounty_id))
+    }
+
+    /// Approve a pending `request_refund` (admin only) and refund everything
+    /// still held, regardless of the deadline. The refund history records
+    /// reason `approved` with the depositor's reason code.
+    pub fn approve_refund_request(env: Env, bounty_id: u64) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::RefundPaused);
+        }
+        let request = Self::take_refund_request(&env, bounty_id)?;
+        Self::enter_guard(&env)?;
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+
+        Self::settle_refund(
+            &env,
+            bounty_id,
+            escrow,
+            symbol_short!("approved"),
+            request.reason_code,
+        );
+
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    /// Turn down a pending `request_refund` (admin only). The escrow stays
+    /// Locked and the depositor may ask again.
+    pub fn reject_refund_request(env: Env, bounty_id: u64) -> Result<(), Error> {
+        let request = Self::take_refund_request(&env, bounty_id)?;
+        events::emit_refund_request_rejected(
+            &env,
+            events::RefundRequestRejected {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                reason_code: request.reason_code,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Checks admin auth and removes the pending refund request for `bounty_id`.
+    fn take_refund_request(env: &Env, bounty_id: u64) -> Result<RefundRequest, Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let request: RefundRequest = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RefundRequest(bounty_id))
+            .ok_or(Error::RefundNotRequested)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RefundRequest(bounty_id));
+        Ok(request)
+    }
+
+    /// Release a partial amount of the locked funds to the contributor.
+    /// Only the admin (backend) can authorize this.
+    ///
+    /// - `payout_amount` must be > 0 and <= `remaining_amount`; larger payouts
+    ///   fail with InsufficientEscrowBalance.
+    /// - `remaining_amount` is decremented by `payout_amount` after each call.
+    /// - When `remaining_amount` reaches 0 the escrow status is set to Released.
+    /// - The bounty stays Locked while any funds remain unreleased.
+    /// - Milestone escrows are paid with `release_milestone` instead and
+    ///   return InvalidAmount here.
+    pub fn partial_release(
+        env: Env,
+        bounty_id: u64,
+        contributor: Address,
+        payout_amount: i128,
+    ) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::ReleasePaused);
+        }
+
+        Self::enter_guard(&env)?;
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        Self::check_not_vesting(&env, bounty_id)?;
+
+        // Guard: zero or negative payout makes no sense and would corrupt state,
+        // and a milestone escrow's balance is spoken for by its milestones
+        if payout_amount <= 0 || !escrow.milestones.is_empty() {
+            return Err(Error::InvalidAmount);
+        }
+
+        // Guard: prevent overpayment — payout cannot exceed what is still owed
+        if payout_amount > escrow.remaining_amount {
+            return Err(Error::InsufficientEscrowBalance);
+        }
+        Self::check_release_timelock(&env, payout_amount)?;
+        Self::check_assignee(&env, bounty_id, &contributor)?;
+        Self::check_not_blocked(&env, &contributor)?;
+
+        let token_addr: Address = Self::escrow_token(&env, bounty_id);
+        let client = token::Client::new(&env, &token_addr);
+
+        // Transfer only the requested partial amount to the contributor
+        let (net, fee) = Self::pay_release(&env, bounty_id, &client, &contributor, payout_amount);
+        Self::index_contributor(&env, bounty_id, &contributor);
+
+        // Decrement remaining; this is always an exact integer subtraction — no rounding
+        escrow.remaining_amount -= payout_amount;
+        escrow.release_reason = Some(symbol_short!("partial"));
+
+        // Automatically transition to Released once fully paid out
+        if escrow.remaining_amount == 0 {
+            escrow.status = EscrowStatus::Released;
+            Self::record_depositor_close(&env, &escrow.depositor, false);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+
+        events::emit_funds_released(
+            &env,
+            FundsReleased {
+                version: EVENT_VERSION_V3,
+                token: token_addr.clone(),
+                bounty_id,
+                depositor: escrow.depositor.clone(),
+                gross: net + fee,
+                amount: net,
+                fee,
+                recipient: contributor.clone(),
+                reason: symbol_short!("partial"),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    /// Release a single milestone of a milestone-based escrow to the contributor.
+    /// Only the admin (backend) can authorize this.
+    ///
+    /// The escrow moves to PartiallyReleased after the first milestone and to
+    /// Released once the last unpaid milestone goes out. A milestone larger
+    /// than what the escrow still holds fails with InsufficientEscrowBalance.
+    pub fn release_milestone(
+        env: Env,
+        bounty_id: u64,
+        milestone_index: u32,
+        contributor: Address,
+    ) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::ReleasePaused);
+        }
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        Self::enter_guard(&env)?;
+
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        if escrow.status != EscrowStatus::Locked
+            && escrow.status != EscrowStatus::PartiallyReleased
+            && escrow.status != EscrowStatus::PartiallyRefunded
+        {
+            return Err(Error::FundsNotLocked);
+        }
+
+        let mut milestone = escrow
+            .milestones
+            .get(milestone_index)
+            .ok_or(Error::MilestoneNotFound)?;
+        match milestone.status {
+            MilestoneStatus::Pending => {}
+            MilestoneStatus::Released => return Err(Error::MilestoneAlreadyReleased),
+            MilestoneStatus::Refunded => return Err(Error::FundsNotLocked),
+        }
+        let remaining_amount = escrow
+            .remaining_amount
+            .checked_sub(milestone.amount)
+            .filter(|remaining| *remaining >= 0)
+            .ok_or(Error::InsufficientEscrowBalance)?;
+        Self::check_release_timelock(&env, milestone.amount)?;
+        Self::check_assignee(&env, bounty_id, &contributor)?;
+        Self::check_not_blocked(&env, &contributor)?;
+
+        let token_addr: Address = Self::escrow_token(&env, bounty_id);
+        let client = token::Client::new(&env, &token_addr);
+        let (net, fee) =
+            Self::pay_release(&env, bounty_id, &client, &contributor, milestone.amount);
+        Self::index_contributor(&env, bounty_id, &contributor);
+
+        milestone.status = MilestoneStatus::Released;
+        escrow.milestones.set(milestone_index, milestone.clone());
+        escrow.remaining_amount = remaining_amount;
+        escrow.release_reason = Some(symbol_short!("milestone"));
+
+        let settled = escrow
+            .milestones
+            .iter()
+            .all(|m| m.status != MilestoneStatus::Pending);
+        escrow.status = if settled {
+            EscrowStatus::Released
+        } else {
+            EscrowStatus::PartiallyReleased
+        };
+        if settled {
+            Self::record_depositor_close(&env, &escrow.depositor, false);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+
+        emit_funds_released(
+            &env,
+            FundsReleased {
+                version: EVENT_VERSION_V3,
+                token: token_addr.clone(),
+                bounty_id,
+                depositor: escrow.depositor.clone(),
+                gross: net + fee,
+                amount: net,
+                fee,
+                recipient: contributor,
+                reason: symbol_short!("milestone"),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    /// Refund funds to the original depositor if the deadline has passed.
+    /// Refunds the full remaining_amount (accounts for any prior partial releases).
+    /// Milestone escrows instead refund every unpaid milestone whose own
+    /// deadline has passed, leaving later milestones locked.
+    pub fn refund(env: Env, bounty_id: u64) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::RefundPaused);
+        }
+        Self::enter_guard(&env)?;
+
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap();
+
+        Self::refund_escrow(&env, bounty_id, escrow)?;
+
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    /// Refund up to `limit` escrows whose deadline has passed, following the
+    /// same rules as `refund`: plain escrows are refunded in full, milestone
+    /// escrows only for their expired milestones. Anyone can call this.
+    ///
+    /// Each call looks at no more than `MAX_REFUND_SCAN` (50) escrows,
+    /// resuming where the previous call stopped and wrapping around to the
+    /// oldest escrow at the end of the index, so its cost does not grow with
+    /// the number of escrows ever locked.
+    ///
+    /// Returns how many escrows were refunded. A 0 only means none of the
+    /// escrows looked at were refundable; a full sweep takes
+    /// `get_escrow_count / 50` calls, rounded up.
+    pub fn refund_expired(env: Env, limit: u32) -> Result<u32, Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::RefundPaused);
+        }
+        Self::enter_guard(&env)?;
+
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let len = index.len();
+        let mut position: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKeyExt::RefundCursor)
+            .unwrap_or(0);
+        if position >= len {
+            position = 0;
+        }
+        let mut refunded: u32 = 0;
+        for _ in 0..len.min(MAX_REFUND_SCAN) {
+            if refunded >= limit {
+                break;
+            }
+            let bounty_id = index.get_unchecked(position);
+            position = (position + 1) % len;
+            let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            else {
+                continue;
+            };
+            // Ineligible escrows fail their checks before anything is written
+            if Self::refund_escrow(&env, bounty_id, escrow).is_ok() {
+                refunded += 1;
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::RefundCursor, &position);
+
+        Self::exit_guard(&env);
+        Ok(refunded)
+    }
+
+    /// Refund each of `bounty_ids` that `refund` would accept right now,
+    /// skipping unknown or ineligible ids instead of failing the call.
+    /// Anyone can call this.
+    ///
+    /// Returns the ids that were actually refunded. At most `MAX_BATCH_SIZE`
+    /// (20) ids per call. Each skipped id is counted in `get_error_stats`.
+    pub fn refund_expired_batch(env: Env, bounty_ids: Vec<u64>) -> Result<Vec<u64>, Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::RefundPaused);
+        }
+        if bounty_ids.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+        Self::enter_guard(&env)?;
+
+        let mut refunded: Vec<u64> = Vec::new(&env);
+        for bounty_id in bounty_ids.iter() {
+            let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            else {
+                Self::record_failure(&env, Error::BountyNotFound);
+                continue;
+            };
+            match Self::refund_escrow(&env, bounty_id, escrow) {
+                Ok(()) => refunded.push_back(bounty_id),
+                Err(err) => Self::record_failure(&env, err),
+            }
+        }
+
+        Self::exit_guard(&env);
+        Ok(refunded)
+    }
+
+    /// Count a failure that the caller absorbs instead of returning.
+    fn record_failure(env: &Env, err: Error) {
+        let category = match err {
+            Error::DeadlineNotPassed => ErrorCategory::RefundBeforeDeadline,
+            Error::FundsNotLocked => ErrorCategory::NotLocked,
+            Error::EscrowDisputed => ErrorCategory::Disputed,
+            _ => ErrorCategory::Other,
+        };
+        let key = DataKeyExt::FailureCount(category);
+        let count: u32 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &count.saturating_add(1));
+    }
+
+    /// view function to get how many failed operations were counted per
+    /// category since the last `reset_error_stats`; see `ErrorStats` for
+    /// which failures are countable
+    pub fn get_error_stats(env: Env) -> ErrorStats {
+        let count = |category: ErrorCategory| -> u32 {
+            env.storage()
+                .instance()
+                .get(&DataKeyExt::FailureCount(category))
+                .unwrap_or(0)
+        };
+        let refund_before_deadline = count(ErrorCategory::RefundBeforeDeadline);
+        let not_locked = count(ErrorCategory::NotLocked);
+        let disputed = count(ErrorCategory::Disputed);
+        let other = count(ErrorCategory::Other);
+        ErrorStats {
+            refund_before_deadline,
+            not_locked,
+            disputed,
+            other,
+            total: refund_before_deadline
+                .saturating_add(not_locked)
+                .saturating_add(disputed)
+                .saturating_add(other),
+        }
+    }
+
+    /// Zero every counter reported by `get_error_stats` (admin only).
+    pub fn reset_error_stats(env: Env) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        for category in [
+            ErrorCategory::RefundBeforeDeadline,
+            ErrorCategory::NotLocked,
+            ErrorCategory::Disputed,
+            ErrorCategory::Other,
+        ] {
+            env.storage()
+                .instance()
+                .remove(&DataKeyExt::FailureCount(category));
+        }
+        Ok(())
+    }
+
+    /// The checks `refund` runs before moving any funds, shared with the
+    /// refund eligibility views so they report exactly what `refund` accepts.
+    fn check_refundable(env: &Env, bounty_id: u64, escrow: &Escrow) -> Result<(), Error> {
+        // Vesting escrows are wound down with cancel_vesting instead
+        Self::check_not_vesting(env, bounty_id)?;
+        // An approved pull-claim blocks refunds until its window runs out,
+        // after which the escrow falls back to the normal refund rules.
+        let status = if escrow.status == EscrowStatus::PendingClaim {
+            if !Self::is_claim_expired(env, bounty_id) {
+                return Err(Error::FundsNotLocked);
+            }
+            EscrowStatus::Locked
+        } else {
+            escrow.status.clone()
+        };
+        if status == EscrowStatus::Disputed {
+            return Err(Error::EscrowDisputed);
+        }
+
+        let now = env.ledger().timestamp();
+        if !escrow.milestones.is_empty() {
+            if status != EscrowStatus::Locked
+                && status != EscrowStatus::PartiallyReleased
+                && status != EscrowStatus::PartiallyRefunded
+            {
+                return Err(Error::FundsNotLocked);
+            }
+            // At least one unpaid milestone must be past its own deadline
+            let expired = escrow.milestones.iter().any(|milestone| {
+                milestone.status == MilestoneStatus::Pending
+                    && now >= Self::refund_opens_at(env, milestone.deadline)
+            });
+            if !expired {
+                return Err(Error::DeadlineNotPassed);
+            }
+            return Ok(());
+        }
+
+        if status != EscrowStatus::Locked && status != EscrowStatus::PartiallyReleased {
+            return Err(Error::FundsNotLocked);
+        }
+        if now < Self::refund_opens_at(env, escrow.deadline) {
+            return Err(Error::DeadlineNotPassed);
+        }
+        Ok(())
+    }
+
+    /// Shared by `refund` and the keeper entry points; returns an error
+    /// without touching storage when the escrow cannot be refunded yet.
+    fn refund_escrow(env: &Env, bounty_id: u64, mut escrow: Escrow) -> Result<(), Error> {
+        Self::check_refundable(env, bounty_id, &escrow)?;
+        if escrow.status == EscrowStatus::PendingClaim {
+            escrow.status = EscrowStatus::Locked;
+        }
+
+        if !escrow.milestones.is_empty() {
+            return Self::refund_expired_milestones(env, bounty_id, escrow);
+        }
+
+        Self::settle_refund(env, bounty_id, escrow, symbol_short!("refund"), 0);
+        Ok(())
+    }
+
+    /// Refunds the pending milestones whose deadline has passed. The escrow is
+    /// Refunded once nothing is pending, and PartiallyRefunded until then.
+    /// The refund is split across the funders like any other refund.
+    fn refund_expired_milestones(
+        env: &Env,
+        bounty_id: u64,
+        mut escrow: Escrow,
+    ) -> Result<(), Error> {
+        if escrow.status != EscrowStatus::Locked
+            && escrow.status != EscrowStatus::PartiallyReleased
+            && escrow.status != EscrowStatus::PartiallyRefunded
+        {
+            return Err(Error::FundsNotLocked);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut refund_amount: i128 = 0;
+        let mut pending_left = false;
+        for i in 0..escrow.milestones.len() {
+            let mut milestone = escrow.milestones.get(i).unwrap();
+            if milestone.status != MilestoneStatus::Pending {
+                continue;
+            }
+            if now < Self::refund_opens_at(env, milestone.deadline) {
+                pending_left = true;
+                continue;
+            }
+            refund_amount = refund_amount
+                .checked_add(milestone.amount)
+                .ok_or(Error::InsufficientEscrowBalance)?;
+            milestone.status = MilestoneStatus::Refunded;
+            escrow.milestones.set(i, milestone);
+        }
+
+        if refund_amount == 0 {
+            return Err(Error::DeadlineNotPassed);
+        }
+        escrow.remaining_amount = escrow
+            .remaining_amount
+            .checked_sub(refund_amount)
+            .filter(|remaining| *remaining >= 0)
+            .ok_or(Error::InsufficientEscrowBalance)?;
+
+        let shares = Self::refund_shares(env, bounty_id, &escrow, refund_amount);
+        let mode = if pending_left {
+            RefundMode::Partial
+        } else {
+            RefundMode::Full
+        };
+        for (_, recipient, share) in shares.iter() {
+            escrow.refund_history.push_back(RefundRecord {
+                amount: share,
+                depositor: escrow.depositor.clone(),
+                recipient,
+                timestamp: now,
+                mode: mode.clone(),
+                reason: symbol_short!("milestone"),
+                reason_code: 0,
+            });
+        }
+        escrow.status = if pending_left {
+            EscrowStatus::PartiallyRefunded
+        } else {
+            EscrowStatus::Refunded
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+
+        Self::record_refund_payouts(env, &shares);
+        Self::record_daily_volume(env, DailyMetric::Refunded, refund_amount);
+        Self::record_history(
+            env,
+            bounty_id,
+            EscrowAction::Refunded,
+            &escrow.depositor,
+            refund_amount,
+        );
+        if !pending_left {
+            Self::record_depositor_close(env, &escrow.depositor, true);
+        }
+        Self::transfer_refund_shares(env, bounty_id, &escrow.depositor, &shares, now);
+
+        Ok(())
+    }
+
+    /// Short alias for `cancel_escrow`.
+    pub fn cancel(env: Env, bounty_id: u64) -> Result<(), Error> {
+        Self::cancel_escrow(env, bounty_id)
+    }
+
+    /// Cancel a bounty before anyone has started on it.
+    ///
+    /// Only the depositor can cancel, and only while the escrow is still Locked
+    /// with no pending claim or assigned contributor. The full amount goes straight back to the
+    /// depositor, regardless of the deadline, and the escrow is marked Cancelled.
+    pub fn cancel_escrow(env: Env, bounty_id: u64) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::RefundPaused);
+        }
+
+        Self::enter_guard(&env)?;
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        escrow.depositor.require_auth();
+
+        if escrow.status == EscrowStatus::PendingClaim {
+            return Err(Error::ContributorAssigned);
+        }
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        Self::check_not_vesting(&env, bounty_id)?;
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Assignee(bounty_id))
+        {
+            return Err(Error::ContributorAssigned);
+        }
+
+        let now = env.ledger().timestamp();
+        for i in 0..escrow.milestones.len() {
+            let mut milestone = escrow.milestones.get(i).unwrap();
+            milestone.status = MilestoneStatus::Refunded;
+            escrow.milestones.set(i, milestone);
+        }
+        let shares = Self::refund_shares(&env, bounty_id, &escrow, escrow.remaining_amount);
+        for (_, recipient, share) in shares.iter() {
+            escrow.refund_history.push_back(RefundRecord {
+                amount: share,
+                depositor: escrow.depositor.clone(),
+                recipient,
+                timestamp: now,
+                mode: RefundMode::Full,
+                reason: symbol_short!("cancel"),
+                reason_code: 0,
+            });
+        }
+        escrow.status = EscrowStatus::Cancelled;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+
+        let token_addr: Address = Self::escrow_token(&env, bounty_id);
+        let client = token::Client::new(&env, &token_addr);
+        for (_, recipient, share) in shares.iter() {
+            client.transfer(&env.current_contract_address(), &recipient, &share);
+        }
+        Self::record_refund_payouts(&env, &shares);
+        Self::record_daily_volume(&env, DailyMetric::Refunded, escrow.remaining_amount);
+        Self::record_history(
+            &env,
+            bounty_id,
+            EscrowAction::Cancelled,
+            &escrow.depositor,
+            escrow.remaining_amount,
+        );
+        Self::record_depositor_close(&env, &escrow.depositor, true);
+
+        emit_escrow_cancelled(
+            &env,
+            EscrowCancelled {
+                version: EVENT_VERSION_V3,
+                token: token_addr.clone(),
+                bounty_id,
+                amount: escrow.remaining_amount,
+                depositor: escrow.depositor,
+                timestamp: now,
+            },
+        );
+
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    /// Settle a bounty that was cancelled part-way through (admin only).
+    ///
+    /// Refunds `refund_amount` to the depositor and releases the rest of the
+    /// remaining balance to `contributor` in one call, so the two legs always
+    /// add up to what is still locked. Both legs must be non-zero, and
+    /// milestone escrows must go through `release_milestone`/`refund` instead.
+    /// The escrow ends in `Settled`.
+    pub fn refund_partial(
+        env: Env,
+        bounty_id: u64,
+        refund_amount: i128,
+        contributor: Address,
+    ) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::ReleasePaused);
+        }
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::RefundPaused);
+        }
+
+        Self::enter_guard(&env)?;
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        if !escrow.milestones.is_empty()
+            || refund_amount <= 0
+            || refund_amount >= escrow.remaining_amount
+        {
+            return Err(Error::InvalidAmount);
+        }
+        Self::check_not_blocked(&env, &contributor)?;
+
+        Self::settle_split(
+            &env,
+            bounty_id,
+            escrow,
+            refund_amount,
+            &contributor,
+            symbol_short!("settle"),
+        );
+
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    /// Split an expired Locked escrow between the contributor and the
+    /// depositor (admin only).
+    ///
+    /// `contributor_bps` of what is still held is released to `contributor`
+    /// and the rest refunded, so a bounty that was partly done by the
+    /// deadline can be closed fairly. 10_000 releases everything and 0
+    /// refunds everything; anything in between leaves the escrow Settled with
+    /// the refunded leg in `get_refund_history`. Milestone escrows are not
+    /// supported.
+    pub fn settle_expired(
+        env: Env,
+        bounty_id: u64,
+        contributor: Address,
+        contributor_bps: u32,
+    ) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::ReleasePaused);
+        }
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::RefundPaused);
+        }
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if contributor_bps > BASIS_POINTS as u32 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        if escrow.status == EscrowStatus::Disputed {
+            return Err(Error::EscrowDisputed);
+        }
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        if !escrow.milestones.is_empty() {
+            return Err(Error::InvalidAmount);
+        }
+        if env.ledger().timestamp() < escrow.deadline {
+            return Err(Error::DeadlineNotPassed);
+        }
+        if contributor_bps > 0 {
+            Self::check_not_blocked(&env, &contributor)?;
+        }
+
+        Self::enter_guard(&env)?;
+        let release_amount = Self::mul_div(
+            &env,
+            escrow.remaining_amount,
+            contributor_bps as i128,
+            BASIS_POINTS,
+        );
+        let refund_amount = escrow.remaining_amount - release_amount;
+        if refund_amount == 0 {
+            Self::settle_release(
+                &env,
+                bounty_id,
+                escrow,
+                &contributor,
+                symbol_short!("expired"),
+            );
+        } else if release_amount == 0 {
+            Self::settle_refund(&env, bounty_id, escrow, symbol_short!("expired"), 0);
+        } else {
+            Self::settle_split(
+                &env,
+                bounty_id,
+                escrow,
+                refund_amount,
+                &contributor,
+                symbol_short!("expired"),
+            );
+        }
+        Self::exit_guard(&env);
+
+        Ok(())
+    }
+
+    /// Refunds `refund_amount` and releases the rest of `remaining_amount` to
+    /// `contributor`, leaving the escrow Settled.
+    fn settle_split(
+        env: &Env,
+        bounty_id: u64,
+        mut escrow: Escrow,
+        refund_amount: i128,
+        contributor: &Address,
+        reason: Symbol,
+    ) {
+        let release_amount = escrow.remaining_amount - refund_amount;
+
+        let now = env.ledger().timestamp();
+        let shares = Self::refund_shares(env, bounty_id, &escrow, refund_amount);
+        for (_, recipient, share) in shares.iter() {
+            escrow.refund_history.push_back(RefundRecord {
+                amount: share,
+                depositor: escrow.depositor.clone(),
+                recipient,
+                timestamp: now,
+                mode: RefundMode::Partial,
+                reason: reason.clone(),
+                reason_code: 0,
+            });
+        }
+        escrow.remaining_amount = refund_amount;
+        escrow.status = EscrowStatus::Settled;
+        escrow.release_reason = Some(reason.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+        Self::index_contributor(env, bounty_id, contributor);
+        Self::record_refund_payouts(env, &shares);
+        Self::record_daily_volume(env, DailyMetric::Refunded, refund_amount);
+        Self::record_history(
+            env,
+            bounty_id,
+            EscrowAction::Refunded,
+            &escrow.depositor,
+            refund_amount,
+        );
+        Self::record_depositor_close(env, &escrow.depositor, false);
+
+        Self::transfer_refund_shares(env, bounty_id, &escrow.depositor, &shares, now);
+        let token_addr: Address = Self::escrow_token(env, bounty_id);
+        let client = token::Client::new(env, &token_addr);
+        // cancel_vesting is the one split allowed on a vesting escrow: it pays
+        // out what has already vested.
+        let (net, fee) = if reason == symbol_short!("vesting") {
+            Self::transfer_release(env, bounty_id, &client, contributor, release_amount)
+        } else {
+            Self::pay_release(env, bounty_id, &client, contributor, release_amount)
+        };
+        emit_funds_released(
+            env,
+            FundsReleased {
+                version: EVENT_VERSION_V3,
+                token: token_addr.clone(),
+                bounty_id,
+                depositor: escrow.depositor.clone(),
+                gross: release_amount,
+                amount: net,
+                fee,
+                recipient: contributor.clone(),
+                reason,
+                timestamp: now,
+            },
+        );
+    }
+
+    /// Set the arbiter who resolves disputes (admin only). The per-escrow
+    /// arbiter from `lock_funds_with_arbiter` is chosen by the depositor and
+    /// has no say in disputes.
+    pub fn set_arbiter(env: Env, arbiter: Address) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Arbiter, &arbiter);
+        Ok(())
+    }
+
+    /// view function to get the default dispute arbiter, if one is set
+    pub fn get_arbiter(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Arbiter)
+    }
+
+    /// Freeze a Locked escrow the depositor refuses to release.
+    ///
+    /// Only the assigned contributor can open a dispute. While Disputed,
+    /// `release_funds` and `refund` return EscrowDisputed and only the arbiter
+    /// can move the funds, through `resolve_dispute`.
+    pub fn open_dispute(env: Env, bounty_id: u64) -> Result<(), Error> {
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        let contributor: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Assignee(bounty_id))
+            .ok_or(Error::NotAssignedContributor)?;
+        contributor.require_auth();
+
+        if escrow.status == EscrowStatus::Disputed {
+            return Err(Error::EscrowDisputed);
+        }
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+
+        let now = env.ledger().timestamp();
+        escrow.status = EscrowStatus::Disputed;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+        env.storage().persistent().set(
+            &DataKey::Dispute(bounty_id),
+            &DisputeRecord {
+                contributor: contributor.clone(),
+                opened_at: now,
+            },
+        );
+        Self::record_history(&env, bounty_id, EscrowAction::Disputed, &contributor, 0);
+
+        events::emit_dispute_opened(
+            &env,
+            events::DisputeOpened {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                contributor,
+                depositor: escrow.depositor.clone(),
+                amount: escrow.remaining_amount,
+                token: Self::escrow_token(&env, bounty_id),
+                timestamp: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// view function to get the open dispute on a bounty, if any
+    pub fn get_dispute(env: Env, bounty_id: u64) -> Option<DisputeRecord> {
+        env.storage().persistent().get(&DataKey::Dispute(bounty_id))
+    }
+
+    /// Settle a Disputed escrow on the arbiter's authority.
+    ///
+    /// Only the arbiter set by the admin with `set_arbiter` can rule, even if
+    /// the escrow was locked with its own arbiter. `Split` is not available for
+    /// milestone escrows, and a split that rounds either leg to zero returns
+    /// InvalidAmount.
+    pub fn resolve_dispute(env: Env, bounty_id: u64, ruling: DisputeRuling) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        Self::enter_guard(&env)?;
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        let arbiter: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Arbiter)
+            .ok_or(Error::ArbiterNotSet)?;
+        arbiter.require_auth();
+
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(Error::FundsNotLocked);
+        }
+        let dispute: DisputeRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Dispute(bounty_id))
+            .unwrap();
+        if ruling != DisputeRuling::Refund {
+            Self::check_not_blocked(&env, &dispute.contributor)?;
+        }
+
+        let refund_amount = match ruling {
+            DisputeRuling::Release => 0,
+            DisputeRuling::Refund => escrow.remaining_amount,
+            DisputeRuling::Split(bps) => {
+                if !escrow.milestones.is_empty() || bps > BASIS_POINTS as u32 {
+                    return Err(Error::InvalidAmount);
+                }
+                let release_amount =
+                    Self::mul_div(&env, escrow.remaining_amount, bps as i128, BASIS_POINTS);
+                let refund_amount = escrow.remaining_amount - release_amount;
+                if release_amount == 0 || refund_amount == 0 {
+                    return Err(Error::InvalidAmount);
+                }
+                refund_amount
+            }
+        };
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Dispute(bounty_id));
+        escrow.status = EscrowStatus::Locked;
+        match ruling {
+            DisputeRuling::Release => Self::settle_release(
+                &env,
+                bounty_id,
+                escrow,
+                &dispute.contributor,
+                symbol_short!("dispute"),
+            ),
+            DisputeRuling::Refund => {
+                Self::settle_refund(&env, bounty_id, escrow, symbol_short!("dispute"), 0)
+            }
+            DisputeRuling::Split(_) => Self::settle_split(
+                &env,
+                bounty_id,
+                escrow,
+                refund_amount,
+                &dispute.contributor,
+                symbol_short!("dispute"),
+            ),
+        }
+
+        events::emit_dispute_resolved(
+            &env,
+            events::DisputeResolved {
+                version: EVENT_VERSION_V3,
+                bounty_id,
+                ruling,
+                arbiter,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    /// Refund the remaining funds to the depositor on the arbiter's authority.
+    /// Unlike `refund`, this does not wait for the deadline.
+    pub fn arbiter_refund(env: Env, bounty_id: u64) -> Result<(), Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::RefundPaused);
+        }
+
+        Self::enter_guard(&env)?;
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+
+        let arbiter = escrow.arbiter.clone().ok_or(Error::ArbiterNotSet)?;
+        arbiter.require_auth();
+
+        if escrow.status != EscrowStatus::Locked
+            && escrow.status != EscrowStatus::PartiallyReleased
+            && escrow.status != EscrowStatus::PartiallyRefunded
+        {
+            return Err(Error::FundsNotLocked);
+        }
+
+        Self::settle_refund(&env, bounty_id, escrow, symbol_short!("arbiter"), 0);
+
+        Self::exit_guard(&env);
+        Ok(())
+    }
+
+    /// Refund a Locked escrow right away, without waiting for the deadline
+    /// (admin only).
+    ///
+    /// Meant for bounties created by mistake. The refund is recorded with the
+    /// `emergency` reason and published as the usual `refund` events. Limited to
+    /// `max_per_day` calls per ledger-day and can be switched off with
+    /// `set_emergency_refund_config`. Vesting escrows return VestingEscrow.
+    pub fn emergency_refund(env: Env, bounty_id: u64) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let config = Self::get_emergency_refund_config(env.clone());
+        if !config.enabled {
+            return Err(Error::EmergencyRefundDisabled);
+        }
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        if escrow.status == EscrowStatus::Disputed {
+            return Err(Error::EscrowDisputed);
+        }
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        Self::check_not_vesting(&env, bounty_id)?;
+
+        let now = env.ledger().timestamp();
+        let day = now / EMERGENCY_REFUND_WINDOW;
+        let (window, used): (u64, u32) = env
+            .storage()
+            .instance()
+            .get(&DataKey::EmergencyRefundWindow)
+            .unwrap_or((day, 0));
+        let used = if window == day { used } else { 0 };
+        if used >= config.max_per_day {
+            return Err(Error::EmergencyRefundLimitReached);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::EmergencyRefundWindow, &(day, used + 1));
+
+        Self::enter_guard(&env)?;
+        Self::settle_refund(&env, bounty_id, escrow, symbol_short!("emergency"), 0);
+        Self::exit_guard(&env);
+
+        Ok(())
+    }
+
+    /// Refund a Locked escrow to its depositor right away, whatever the
+    /// deadline (admin only).
+    ///
+    /// For exceptional cases such as a wrong assignee or a legal hold. Unlike
+    /// `emergency_refund` it has no daily limit; the refund is recorded with
+    /// the `admin` reason and published as the usual `refund` events. Vesting
+    /// escrows return VestingEscrow and are stopped with `cancel_vesting`.
+    pub fn admin_refund(env: Env, bounty_id: u64) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .ok_or(Error::BountyNotFound)?;
+        if escrow.status == EscrowStatus::Disputed {
+            return Err(Error::EscrowDisputed);
+        }
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        Self::check_not_vesting(&env, bounty_id)?;
+
+        Self::enter_guard(&env)?;
+        Self::settle_refund(&env, bounty_id, escrow, symbol_short!("admin"), 0);
+        Self::exit_guard(&env);
+
+        Ok(())
+    }
+
+    /// Enable or disable `emergency_refund` and set its per-day limit (admin only).
+    pub fn set_emergency_refund_config(
+        env: Env,
+        enabled: bool,
+        max_per_day: u32,
+    ) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage().instance().set(
+            &DataKey::EmergencyRefundConfig,
+            &EmergencyRefundConfig {
+                enabled,
+                max_per_day,
+            },
+        );
+        Ok(())
+    }
+
+    /// view function to get the emergency refund limits
+    pub fn get_emergency_refund_config(env: Env) -> EmergencyRefundConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::EmergencyRefundConfig)
+            .unwrap_or(EmergencyRefundConfig {
+                enabled: true,
+                max_per_day: DEFAULT_MAX_EMERGENCY_REFUNDS,
+            })
+    }
+
+    /// Returns `remaining_amount` to the depositor and marks the escrow Refunded.
+    fn settle_refund(
+        env: &Env,
+        bounty_id: u64,
+        mut escrow: Escrow,
+        reason: Symbol,
+        reason_code: u32,
+    ) {
+        let now = env.ledger().timestamp();
+        for i in 0..escrow.milestones.len() {
+            let mut milestone = escrow.milestones.get(i).unwrap();
+            if milestone.status == MilestoneStatus::Pending {
+                milestone.status = MilestoneStatus::Refunded;
+                escrow.milestones.set(i, milestone);
+            }
+        }
+        // Refund only what is still remaining (partial releases may have already gone out)
+        let shares = Self::refund_shares(env, bounty_id, &escrow, escrow.remaining_amount);
+        for (_, recipient, share) in shares.iter() {
+            escrow.refund_history.push_back(RefundRecord {
+                amount: share,
+                depositor: escrow.depositor.clone(),
+                recipient,
+                timestamp: now,
+                mode: RefundMode::Full,
+                reason: reason.clone(),
+                reason_code,
+            });
+        }
+        escrow.status = EscrowStatus::Refunded;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(bounty_id), &escrow);
+        Self::record_refund_payouts(env, &shares);
+        Self::record_daily_volume(env, DailyMetric::Refunded, escrow.remaining_amount);
+        Self::record_history(
+            env,
+            bounty_id,
+            EscrowAction::Refunded,
+            &escrow.depositor,
+            escrow.remaining_amount,
+        );
+        Self::record_depositor_close(env, &escrow.depositor, true);
+
+        Self::transfer_refund_shares(env, bounty_id, &escrow.depositor, &shares, now);
+    }
+
+    /// Pay out refund shares from `refund_shares`, one transfer and one
+    /// FundsRefunded event per recipient.
+    fn transfer_refund_shares(
+        env: &Env,
+        bounty_id: u64,
+        depositor: &Address,
+        shares: &Vec<(Address, Address, i128)>,
+        now: u64,
+    ) {
+        let token_addr: Address = Self::escrow_token(env, bounty_id);
+        let client = token::Client::new(env, &token_addr);
+        for (_, recipient, share) in shares.iter() {
+            client.transfer(&env.current_contract_address(), &recipient, &share);
+            emit_funds_refunded(
+                env,
+                FundsRefunded {
+                    version: EVENT_VERSION_V3,
+                    token: token_addr.clone(),
+                    bounty_id,
+                    amount: share,
+                    depositor: depositor.clone(),
+                    refund_to: recipient,
+                    timestamp: now,
+                },
+            );
+        }
+    }
+
+    /// Amount each funder put into the escrow. Escrows nobody else has
+    /// contributed to report the depositor with the full amount.
+    fn funder_shares(env: &Env, bounty_id: u64, escrow: &Escrow) -> Vec<(Address, i128)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Contributions(bounty_id))
+            .unwrap_or(vec![env, (escrow.depositor.clone(), escrow.amount)])
+    }
+
+    /// Split `amount` across the funders pro-rata to what each put in, as
+    /// `(funder, recipient, share)`. Rounding dust goes to the original
+    /// depositor, who is always first.
+    fn refund_shares(
+        env: &Env,
+        bounty_id: u64,
+        escrow: &Escrow,
+        amount: i128,
+    ) -> Vec<(Address, Address, i128)> {
+        let funders = Self::funder_shares(env, bounty_id, escrow);
+        let mut shares: Vec<(Address, i128)> = Vec::new(env);
+        let mut allocated: i128 = 0;
+        for (funder, contributed) in funders.iter() {
+            let share = Self::mul_div(env, amount, contributed, escrow.amount);
+            allocated += share;
+            shares.push_back((funder, share));
+        }
+        let (first, first_share) = shares.get(0).unwrap();
+        shares.set(0, (first, first_share + amount - allocated));
+
+        // The depositor's share goes to their refund address, if they set one
+        let refund_to = Self::refund_recipient(env, bounty_id, &escrow.depositor);
+        let mut nonzero: Vec<(Address, Address, i128)> = Vec::new(env);
+        for (funder, share) in shares.iter() {
+            if share > 0 {
+                if funder == escrow.depositor {
+                    nonzero.push_back((funder, refund_to.clone(), share));
+                } else {
+                    nonzero.push_back((funder.clone(), funder, share));
+                }
+            }
+        }
+        nonzero
+    }
+
+    /// Count each refund share in the stats of the funder whose money it
+    /// returns.
+    fn record_refund_payouts(env: &Env, shares: &Vec<(Address, Address, i128)>) {
+        for (funder, _, share) in shares.iter() {
+            Self::record_depositor_payout(env, &funder, share, true);
+        }
+    }
+
+    /// Where the depositor's refunds are paid: the address set with
+    /// `set_refund_address`, or the depositor.
+    fn refund_recipient(env: &Env, bounty_id: u64, depositor: &Address) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RefundAddress(bounty_id))
+            .unwrap_or(depositor.clone())
+    }
+
+    /// view function to get escrow info
+    pub fn get_escrow_info(env: Env, bounty_id: u64) -> Result<Escrow, Error> {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(bounty_id))
+            .unwrap())
+    }
+
+    /// view function to check whether an escrow exists for `bounty_id`
+    pub fn escrow_exists(env: Env, bounty_id: u64) -> bool {
+        env.storage().persistent().has(&DataKey::Escrow(bounty_id))
+    }
+
+    /// view function to get the status of an escrow, or None if there is no
+    /// escrow for `bounty_id`
+    pub fn get_status(env: Env, bounty_id: u64) -> Option<EscrowStatus> {
+        env.storage()
+            .persistent()
+            .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            .map(|escrow| escrow.status)
+    }
+
+    /// Batch view of `get_escrow_info`, returned in the same order as `bounty_ids`.
+    ///
+    /// Unknown ids come back as `None` rather than failing the whole call.
+    /// At most `MAX_INFO_BATCH_SIZE` (50) ids are accepted per call; escrows
+    /// with many milestones or refund records make each entry larger, so
+    /// clients rendering heavy escrows should stay around 20 ids per call.
+    pub fn get_escrow_info_batch(
+        env: Env,
+        bounty_ids: Vec<u64>,
+    ) -> Result<Vec<Option<Escrow>>, Error> {
+        if bounty_ids.len() > MAX_INFO_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+
+        let mut results: Vec<Option<Escrow>> = Vec::new(&env);
+        for bounty_id in bounty_ids.iter() {
+            results.push_back(env.storage().persistent().get(&DataKey::Escrow(bounty_id)));
+        }
+        Ok(results)
+    }
+
+    /// view function to get the token a bounty is held in
+    pub fn get_escrow_token(env: Env, bounty_id: u64) -> Result<Address, Error> {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyNotFound);
+        }
+        Ok(Self::escrow_token(&env, bounty_id))
+    }
+
+    /// view function to tell native XLM escrows apart from token escrows
+    pub fn get_escrow_asset(env: Env, bounty_id: u64) -> Result<EscrowAsset, Error> {
+        let token = Self::get_escrow_token(env.clone(), bounty_id)?;
+        let native: Option<Address> = env.storage().instance().get(&DataKey::NativeToken);
+        if native == Some(token.clone()) {
+            Ok(EscrowAsset::Native)
+        } else {
+            Ok(EscrowAsset::Token(token))
+        }
+    }
+
+    /// view function to get contract balance of the token
+    pub fn get_balance(env: Env) -> Result<i128, Error> {
+        if !env.storage().instance().has(&DataKey::Token) {
+            return Err(Error::NotInitialized);
+        }
+        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let client = token::Client::new(&env, &token_addr);
+        Ok(client.balance(&env.current_contract_address()))
+    }
+
+    /// view function to check, before calling `lock_funds`, that `depositor`
+    /// holds at least `amount` of the `init` token. False for a non-positive
+    /// amount or before `init`. Only the balance is checked: amount limits,
+    /// pauses and rate limits can still reject the lock.
+    pub fn can_lock(env: Env, depositor: Address, amount: i128) -> bool {
+        let Some(token_addr) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::Token)
+        else {
+            return false;
+        };
+        amount > 0 && token::Client::new(&env, &token_addr).balance(&depositor) >= amount
+    }
+
+    /// Query escrows with filtering and pagination
+    /// Pass 0 for min values and i128::MAX/u64::MAX for max values to disable those filters
+    ///
+    /// `offset` counts matching escrows, so every call rescans the index from
+    /// the start. Large deployments should use `query_escrows_by_status_paged`.
+    pub fn query_escrows_by_status(
+        env: Env,
+        status: EscrowStatus,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut count = 0u32;
+        let mut skipped = 0u32;
+
+        for i in 0..index.len() {
+            if count >= limit {
+                break;
+            }
+
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if escrow.status == status {
+                    if skipped < offset {
+                        skipped += 1;
+                        continue;
+                    }
+                    results.push_back(EscrowWithId { bounty_id, escrow });
+                    count += 1;
+                }
+            }
+        }
+        results
+    }
+
+    /// Cursor-based variant of `query_escrows_by_status`.
+    ///
+    /// Scans the escrow index from `start_index` in lock order (which never
+    /// changes, since entries are only appended) and returns at most `limit`
+    /// matches, capped at `MAX_PAGE_SIZE`. Pass the returned `next_index` back
+    /// as `start_index` to fetch the following page.
+    pub fn query_escrows_by_status_paged(
+        env: Env,
+        status: EscrowStatus,
+        start_index: u32,
+        limit: u32,
+    ) -> EscrowPage {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let mut items = Vec::new(&env);
+        let mut next_index = None;
+
+        let mut i = start_index;
+        while i < index.len() {
+            if items.len() >= limit {
+                next_index = Some(i);
+                break;
+            }
+
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if escrow.status == status {
+                    items.push_back(EscrowWithId { bounty_id, escrow });
+                }
+            }
+            i += 1;
+        }
+
+        EscrowPage { items, next_index }
+    }
+
+    /// Query escrows with amount range filtering
+    ///
+    /// Amounts are compared as raw integers, so escrows held in different
+    /// tokens are not comparable. Check `get_escrow_token` on the results when
+    /// the contract holds more than one token.
+    ///
+    /// Results are in lock order; `query_escrows` can return them largest
+    /// first.
+    pub fn query_escrows_by_amount(
+        env: Env,
+        min_amount: i128,
+        max_amount: i128,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut count = 0u32;
+        let mut skipped = 0u32;
+
+        for i in 0..index.len() {
+            if count >= limit {
+                break;
+            }
+
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                let amount = Self::filter_amount(&escrow);
+                if amount >= min_amount && amount <= max_amount {
+                    if skipped < offset {
+                        skipped += 1;
+                        continue;
+                    }
+                    results.push_back(EscrowWithId { bounty_id, escrow });
+                    count += 1;
+                }
+            }
+        }
+        results
+    }
+
+    /// Query escrows with deadline range filtering
+    ///
+    /// Results are in lock order; `query_escrows` can return them ending
+    /// soonest first.
+    pub fn query_escrows_by_deadline(
+        env: Env,
+        min_deadline: u64,
+        max_deadline: u64,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let mut count = 0u32;
+        let mut skipped = 0u32;
+
+        for i in 0..index.len() {
+            if count >= limit {
+                break;
+            }
+
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if escrow.deadline >= min_deadline && escrow.deadline <= max_deadline {
+                    if skipped < offset {
+                        skipped += 1;
+                        continue;
+                    }
+                    results.push_back(EscrowWithId { bounty_id, escrow });
+                    count += 1;
+                }
+            }
+        }
+        results
+    }
+
+    /// Active escrows are matched on what is still locked, so partial
+    /// releases shrink the amount seen by the amount filters.
+    fn filter_amount(escrow: &Escrow) -> i128 {
+        match escrow.status {
+            EscrowStatus::Locked | EscrowStatus::PartiallyReleased => escrow.remaining_amount,
+            _ => escrow.amount,
+        }
+    }
+
+    /// Whether `escrow` meets every criterion set in `filter`; the depositor
+    /// is matched by the index the caller reads from.
+    fn matches_filter(filter: &EscrowFilter, escrow: &Escrow) -> bool {
+        if !filter.statuses.is_empty() && !filter.statuses.contains(&escrow.status) {
+            return false;
+        }
+        let amount = Self::filter_amount(escrow);
+        if filter.min_amount.is_some_and(|min| amount < min)
+            || filter.max_amount.is_some_and(|max| amount > max)
+        {
+            return false;
+        }
+        !(filter.min_deadline.is_some_and(|min| escrow.deadline < min)
+            || filter.max_deadline.is_some_and(|max| escrow.deadline > max))
+    }
+
+    /// Query escrows matching every criterion set in `filter`, in `sort`
+    /// order, skipping the first `offset` matches and returning at most
+    /// `limit` (capped at `MAX_PAGE_SIZE`).
+    ///
+    /// With a depositor set only that depositor's index is read; otherwise
+    /// every escrow is checked, since status, amount and deadline have no
+    /// index of their own.
+    ///
+    /// `CreatedAsc` and `CreatedDesc` follow lock order and page through any
+    /// number of matches. `AmountDesc` and `DeadlineAsc` sort every match
+    /// before paging, so a filter matching more than `MAX_SORTED_RESULTS`
+    /// (100) escrows returns LimitExceeded; narrow the filter and retry.
+    /// Ties keep lock order.
+    pub fn query_escrows(
+        env: Env,
+        filter: EscrowFilter,
+        sort: SortBy,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<EscrowWithId>, Error> {
+        let key = match &filter.depositor {
+            Some(depositor) => DataKey::DepositorIndex(depositor.clone()),
+            None => DataKey::EscrowIndex,
+        };
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let mut results = Vec::new(&env);
+
+        if sort == SortBy::CreatedAsc || sort == SortBy::CreatedDesc {
+            let mut skipped = 0u32;
+            for i in 0..index.len() {
+                if results.len() >= limit {
+                    break;
+                }
+                let bounty_id = if sort == SortBy::CreatedAsc {
+                    index.get(i).unwrap()
+                } else {
+                    index.get(index.len() - 1 - i).unwrap()
+                };
+                let Some(escrow) = env
+                    .storage()
+                    .persistent()
+                    .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+                else {
+                    continue;
+                };
+                if !Self::matches_filter(&filter, &escrow) {
+                    continue;
+                }
+                if skipped < offset {
+                    skipped += 1;
+                    continue;
+                }
+                results.push_back(EscrowWithId { bounty_id, escrow });
+            }
+            return Ok(results);
+        }
+
+        let mut matches: Vec<EscrowWithId> = Vec::new(&env);
+        for bounty_id in index.iter() {
+            let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            else {
+                continue;
+            };
+            if Self::matches_filter(&filter, &escrow) {
+                if matches.len() >= MAX_SORTED_RESULTS {
+                    return Err(Error::LimitExceeded);
+                }
+                matches.push_back(EscrowWithId { bounty_id, escrow });
+            }
+        }
+
+        // Sort (key, position) pairs on the stack rather than moving whole
+        // escrows around in host storage.
+        let mut keys = [(0i128, 0u32); MAX_SORTED_RESULTS as usize];
+        let count = matches.len() as usize;
+        for (i, item) in matches.iter().enumerate() {
+            let sort_key = match sort {
+                SortBy::AmountDesc => Self::filter_amount(&item.escrow),
+                _ => item.escrow.deadline as i128,
+            };
+            keys[i] = (sort_key, i as u32);
+        }
+        let keys = &mut keys[..count];
+        if sort == SortBy::AmountDesc {
+            keys.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        } else {
+            keys.sort_unstable();
+        }
+
+        for (_, position) in keys.iter().skip(offset as usize).take(limit as usize) {
+            results.push_back(matches.get(*position).unwrap());
+        }
+        Ok(results)
+    }
+
+    /// Locked escrows whose deadline falls in `(now, now + window_seconds]`.
+    /// Escrows past their deadline are left out; they are refund candidates.
+    fn expiring_filter(env: &Env, window_seconds: u64) -> EscrowFilter {
+        let now = env.ledger().timestamp();
+        EscrowFilter {
+            statuses: vec![env, EscrowStatus::Locked],
+            min_amount: None,
+            max_amount: None,
+            min_deadline: Some(now.saturating_add(1)),
+            max_deadline: Some(now.saturating_add(window_seconds)),
+            depositor: None,
+        }
+    }
+
+    /// view function to get up to `limit` Locked escrows whose deadline is
+    /// within the next `window_seconds` of the current ledger time, soonest
+    /// first. Escrows already past their deadline are not included.
+    ///
+    /// Runs `query_escrows` sorted by `DeadlineAsc`, so more than
+    /// `MAX_SORTED_RESULTS` (100) expiring escrows returns LimitExceeded;
+    /// use a shorter window.
+    pub fn get_escrows_expiring_within(
+        env: Env,
+        window_seconds: u64,
+        limit: u32,
+    ) -> Result<Vec<EscrowWithId>, Error> {
+        let filter = Self::expiring_filter(&env, window_seconds);
+        Self::query_escrows(env, filter, SortBy::DeadlineAsc, 0, limit)
+    }
+
+    /// view function to get the number of Locked escrows whose deadline is
+    /// within the next `window_seconds` of the current ledger time
+    pub fn count_escrows_expiring_within(env: Env, window_seconds: u64) -> u32 {
+        let filter = Self::expiring_filter(&env, window_seconds);
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut count = 0u32;
+        for bounty_id in index.iter() {
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if Self::matches_filter(&filter, &escrow) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// view function to get up to `limit` Locked escrows (capped at
+    /// `MAX_PAGE_SIZE`) with the earliest deadlines, soonest first. Escrows
+    /// already past their deadline come first, so a refund keeper can work
+    /// from the front. Equal deadlines are ordered by bounty_id.
+    ///
+    /// Every escrow is read once, but only the `limit` earliest are kept and
+    /// sorted, so any number of Locked escrows is fine.
+    pub fn get_escrows_expiring_soon(env: Env, limit: u32) -> Vec<EscrowWithId> {
+        let limit = limit.min(MAX_PAGE_SIZE) as usize;
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+
+        // (deadline, bounty_id) of the earliest escrows seen so far, sorted
+        let mut earliest = [(0u64, 0u64); MAX_PAGE_SIZE as usize];
+        let mut kept = 0usize;
+        for bounty_id in index.iter() {
+            let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            else {
+                continue;
+            };
+            if escrow.status != EscrowStatus::Locked {
+                continue;
+            }
+            let key = (escrow.deadline, bounty_id);
+            let at = earliest[..kept].partition_point(|k| *k < key);
+            if at >= limit {
+                continue;
+            }
+            if kept < limit {
+                kept += 1;
+            }
+            earliest.copy_within(at..kept - 1, at + 1);
+            earliest[at] = key;
+        }
+
+        let mut results = Vec::new(&env);
+        for (_, bounty_id) in earliest[..kept].iter() {
+            let escrow: Escrow = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Escrow(*bounty_id))
+                .unwrap();
+            results.push_back(EscrowWithId {
+                bounty_id: *bounty_id,
+                escrow,
+            });
+        }
+        results
+    }
+
+    /// Query escrows by depositor
+    ///
+    /// Returns at most `MAX_PAGE_SIZE` escrows whatever `limit` is; compare
+    /// with `get_depositor_escrow_count`, or use
+    /// `query_escrows_by_depositor_paged`, to tell whether more remain.
+    pub fn query_escrows_by_depositor(
+        env: Env,
+        depositor: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        Self::query_escrows_by_depositor_paged(env, depositor, offset, limit).items
+    }
+
+    /// Page through a depositor's escrows in the order they were locked.
+    /// Returns at most `limit` escrows, capped at `MAX_PAGE_SIZE`; pass
+    /// `next_index` back as `offset` for the following page. An offset past
+    /// the end returns an empty page.
+    pub fn query_escrows_by_depositor_paged(
+        env: Env,
+        depositor: Address,
+        offset: u32,
+        limit: u32,
+    ) -> EscrowPage {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DepositorIndex(depositor))
+            .unwrap_or(Vec::new(&env));
+        let mut items = Vec::new(&env);
+        let start = offset.min(index.len());
+        let end = offset
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(index.len());
+
+        for i in start..end {
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                items.push_back(EscrowWithId { bounty_id, escrow });
+            }
+        }
+        let next_index = if end < index.len() { Some(end) } else { None };
+        EscrowPage { items, next_index }
+    }
+
+    /// Query a depositor's escrows that are currently in `status`, in lock
+    /// order. Walks only the depositor's own index, and returns at most
+    /// `MAX_PAGE_SIZE` escrows. An empty vector means none match. (Contract
+    /// function names are limited to 32 characters, hence the short name.)
+    pub fn query_by_depositor_and_status(
+        env: Env,
+        depositor: Address,
+        status: EscrowStatus,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DepositorIndex(depositor))
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        for bounty_id in index.iter() {
+            if results.len() >= MAX_PAGE_SIZE {
+                break;
+            }
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if escrow.status == status {
+                    results.push_back(EscrowWithId { bounty_id, escrow });
+                }
+            }
+        }
+        results
+    }
+
+    /// Get the number of escrows a depositor has locked or contributed to.
+    pub fn get_depositor_escrow_count(env: Env, depositor: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get::<DataKey, Vec<u64>>(&DataKey::DepositorIndex(depositor))
+            .map(|index| index.len())
+            .unwrap_or(0)
+    }
+
+    /// Query escrows whose metadata repo hashes to `repo_hash` (sha256 of the
+    /// `repo` bytes), in the order they were tagged with it. At most `limit`
+    /// entries are returned, capped at `MAX_PAGE_SIZE`.
+    pub fn query_escrows_by_repo(
+        env: Env,
+        repo_hash: BytesN<32>,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RepoIndex(repo_hash))
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let start = offset.min(index.len());
+        let end = offset
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(index.len());
+
+        for i in start..end {
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                results.push_back(EscrowWithId { bounty_id, escrow });
+            }
+        }
+        results
+    }
+
+    /// Query the escrows of a project, in the order they were locked, at most
+    /// `limit` (capped at `MAX_PAGE_SIZE`) at a time.
+    /// Project 0 returns escrows locked without a project.
+    pub fn query_escrows_by_project(
+        env: Env,
+        project_id: u32,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let index = Self::project_candidates(&env, project_id);
+        let mut results = Vec::new(&env);
+        let mut skipped = 0u32;
+        for bounty_id in index.iter() {
+            if results.len() >= limit {
+                break;
+            }
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if escrow.project_id != project_id {
+                    continue;
+                }
+                if skipped < offset {
+                    skipped += 1;
+                    continue;
+                }
+                results.push_back(EscrowWithId { bounty_id, escrow });
+            }
+        }
+        results
+    }
+
+    /// Query escrows assigned to or paid out to `contributor`, in the order
+    /// the contributor was first linked to each bounty. At most `limit`
+    /// entries are returned, capped at `MAX_PAGE_SIZE`.
+    pub fn query_escrows_by_contributor(
+        env: Env,
+        contributor: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ContributorIndex(contributor))
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        let start = offset.min(index.len());
+        let end = offset
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(index.len());
+
+        for i in start..end {
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                results.push_back(EscrowWithId { bounty_id, escrow });
+            }
+        }
+        results
+    }
+
+    /// Get the ids of the bounties assigned to or paid out to `contributor`;
+    /// the id-only counterpart of `query_escrows_by_contributor`, capped at
+    /// `MAX_PAGE_SIZE` the same way
+    pub fn get_contributor_bounty_ids(
+        env: Env,
+        contributor: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ContributorIndex(contributor))
+            .unwrap_or(Vec::new(&env));
+        let start = offset.min(index.len());
+        let end = offset
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(index.len());
+        index.slice(start..end)
+    }
+
+    /// Get aggregate statistics across every escrow. Amounts from different
+    /// tokens are summed together; use `get_aggregate_stats_for_token` for
+    /// totals that mean something when several tokens are in use.
+    ///
+    /// Returns ArithmeticOverflow if a total would not fit in an i128.
+    pub fn get_aggregate_stats(env: Env) -> Result<AggregateStats, Error> {
+        let index = Self::project_candidates(&env, 0);
+        Self::aggregate_stats(&env, index, None, None)
+    }
+
+    /// Get aggregate statistics for the escrows held in `token` only.
+    pub fn get_aggregate_stats_for_token(
+        env: Env,
+        token: Address,
+    ) -> Result<AggregateStats, Error> {
+        let index = Self::project_candidates(&env, 0);
+        Self::aggregate_stats(&env, index, Some(token), None)
+    }
+
+    /// Sum of what the escrows in `token` still hold: `total_locked` plus
+    /// `total_disputed` from `get_aggregate_stats_for_token`.
+    fn expected_locked(env: &Env, token: Address) -> Result<i128, Error> {
+        let index = Self::project_candidates(env, 0);
+        let stats = Self::aggregate_stats(env, index, Some(token), None)?;
+        stats
+            .total_locked
+            .checked_add(stats.total_disputed)
+            .ok_or(Error::ArithmeticOverflow)
+    }
+
+    /// view function to get the amount the escrows in the `init` token still
+    /// hold, which the contract's balance of that token should cover
+    pub fn get_expected_locked_total(env: Env) -> Result<i128, Error> {
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(Error::NotInitialized)?;
+        Self::expected_locked(&env, token)
+    }
+
+    /// view function to compare what the escrows in `token` should hold with
+    /// the contract's actual balance of it, for audits. Scans every escrow,
+    /// like `get_aggregate_stats_for_token`.
+    pub fn get_balance_reconciliation(
+        env: Env,
+        token: Address,
+    ) -> Result<BalanceReconciliation, Error> {
+        let expected_locked = Self::expected_locked(&env, token.clone())?;
+        let actual_balance =
+            token::Client::new(&env, &token).balance(&env.current_contract_address());
+        let surplus = actual_balance - expected_locked;
+        Ok(BalanceReconciliation {
+            token,
+            expected_locked,
+            actual_balance,
+            surplus,
+            is_solvent: surplus >= 0,
+        })
+    }
+
+    /// view function to check the contract is solvent in every token it
+    /// holds escrows in: one `get_balance_reconciliation` per token, the
+    /// `init` token first and the rest in the order they were first locked.
+    /// Pending claims and queued releases count as locked.
+    pub fn get_health_check(env: Env) -> Result<Vec<BalanceReconciliation>, Error> {
+        let init_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(Error::NotInitialized)?;
+        let mut tokens = vec![&env, init_token];
+        for bounty_id in Self::project_candidates(&env, 0).iter() {
+            if let Some(token) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Address>(&DataKey::EscrowToken(bounty_id))
+            {
+                if !tokens.contains(&token) {
+                    tokens.push_back(token);
+                }
+            }
+        }
+
+        let mut report = Vec::new(&env);
+        for token in tokens.iter() {
+            report.push_back(Self::get_balance_reconciliation(env.clone(), token)?);
+        }
+        Ok(report)
+    }
+
+    /// Get aggregate statistics for the escrows of one project. Project 0
+    /// covers every escrow locked without a project.
+    pub fn get_aggregate_stats_for_project(
+        env: Env,
+        project_id: u32,
+    ) -> Result<AggregateStats, Error> {
+        let index = Self::project_candidates(&env, project_id);
+        Self::aggregate_stats(&env, index, None, Some(project_id))
+    }
+
+    /// Locks, releases and refunds whose timestamp falls in
+    /// `start_ts..=end_ts`.
+    ///
+    /// Each kind of activity is judged by its own timestamp, so an escrow
+    /// locked before the window and released inside it counts towards
+    /// releases only. An escrow counts once per kind however many payouts or
+    /// refunds it had in the window; the totals add up all of them. Escrows
+    /// locked before lock times were recorded never count as locks. Returns
+    /// all zeros when `start_ts > end_ts`.
+    pub fn get_stats_in_window(env: Env, start_ts: u64, end_ts: u64) -> WindowStats {
+        let mut stats = WindowStats {
+            count_locked: 0,
+            total_locked: 0,
+            count_released: 0,
+            total_released: 0,
+            count_refunded: 0,
+            total_refunded: 0,
+        };
+        if start_ts > end_ts {
+            return stats;
+        }
+        let in_window = |ts: u64| ts >= start_ts && ts <= end_ts;
+
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        for bounty_id in index.iter() {
+            let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            else {
+                continue;
+            };
+
+            if let Some(locked_at) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, u64>(&DataKey::LockedAt(bounty_id))
+            {
+                if in_window(locked_at) {
+                    stats.count_locked += 1;
+                    stats.total_locked = Self::add_to_stat(&env, stats.total_locked, escrow.amount);
+                }
+            }
+
+            let releases: Vec<ReleaseRecord> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::ReleaseLog(bounty_id))
+                .unwrap_or(Vec::new(&env));
+            let mut released: i128 = 0;
+            let mut any_release = false;
+            for record in releases.iter() {
+                if in_window(record.timestamp) {
+                    released = Self::add_to_stat(&env, released, record.amount);
+                    any_release = true;
+                }
+            }
+            if any_release {
+                stats.count_released += 1;
+                stats.total_released = Self::add_to_stat(&env, stats.total_released, released);
+            }
+
+            let mut refunded: i128 = 0;
+            let mut any_refund = false;
+            for record in escrow.refund_history.iter() {
+                if in_window(record.timestamp) {
+                    refunded = Self::add_to_stat(&env, refunded, record.amount);
+                    any_refund = true;
+                }
+            }
+            if any_refund {
+                stats.count_refunded += 1;
+                stats.total_refunded = Self::add_to_stat(&env, stats.total_refunded, refunded);
+            }
+        }
+        stats
+    }
+
+    /// Min, median, max and average of the amounts held by Locked escrows.
+    ///
+    /// The median of an even count is the mean of the two middle amounts,
+    /// rounded down. This reads every escrow in the index and sorts the
+    /// locked amounts, so its cost grows with the number of escrows; it is
+    /// meant for off-chain simulation by dashboards, and stays comfortably
+    /// within limits up to a few hundred escrows.
+    pub fn get_amount_percentiles(env: Env) -> AmountPercentiles {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+
+        // Kept sorted as we go
+        let mut amounts: Vec<i128> = Vec::new(&env);
+        let mut total: i128 = 0;
+        for bounty_id in index.iter() {
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if escrow.status == EscrowStatus::Locked {
+                    let amount = escrow.remaining_amount;
+                    let pos = match amounts.binary_search(amount) {
+                        Ok(i) | Err(i) => i,
+                    };
+                    amounts.insert(pos, amount);
+                    total = Self::add_to_stat(&env, total, amount);
+                }
+            }
+        }
+
+        let count = amounts.len();
+        if count == 0 {
+            return AmountPercentiles {
+                count: 0,
+                min: 0,
+                median: 0,
+                max: 0,
+                average: 0,
+            };
+        }
+        let mid = count / 2;
+        let median = if count % 2 == 1 {
+            amounts.get(mid).unwrap()
+        } else {
+            (amounts.get(mid - 1).unwrap() + amounts.get(mid).unwrap()) / 2
+        };
+        AmountPercentiles {
+            count,
+            min: amounts.first().unwrap(),
+            median,
+            max: amounts.last().unwrap(),
+            average: total / count as i128,
+        }
+    }
+
+    /// view function to get the average, largest and smallest amount still
+    /// held by active escrows, and the number of unique depositors and paid
+    /// contributors.
+    ///
+    /// The unique counts are kept as addresses are first seen, but the amounts
+    /// are recomputed from every escrow in the index on each call, so a
+    /// released or refunded extreme drops out straight away. Like
+    /// `get_amount_percentiles`, the cost grows with the number of escrows.
+    ///
+    /// Returns ArithmeticOverflow if the active total would not fit in an i128.
+    pub fn get_extended_stats(env: Env) -> Result<ExtendedStats, Error> {
+        let mut stats = ExtendedStats {
+            active_count: 0,
+            average_active: 0,
+            largest_active: 0,
+            smallest_active: 0,
+            unique_depositors: Self::get_unique_depositor_count(env.clone()),
+            unique_contributors: env
+                .storage()
+                .instance()
+                .get(&DataKeyExt::UniqueContributors)
+                .unwrap_or(0),
+        };
+
+        let mut total: i128 = 0;
+        for bounty_id in Self::project_candidates(&env, 0).iter() {
+            let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            else {
+                continue;
+            };
+            if !matches!(
+                escrow.status,
+                EscrowStatus::Locked
+                    | EscrowStatus::PartiallyReleased
+                    | EscrowStatus::PendingClaim
+                    | EscrowStatus::PendingRelease
+            ) {
+                continue;
+            }
+            let amount = escrow.remaining_amount;
+            Self::add_total(&mut total, amount)?;
+            if stats.active_count == 0 || amount > stats.largest_active {
+                stats.largest_active = amount;
+            }
+            if stats.active_count == 0 || amount < stats.smallest_active {
+                stats.smallest_active = amount;
+            }
+            stats.active_count += 1;
+        }
+        if stats.active_count > 0 {
+            stats.average_active = total / stats.active_count as i128;
+        }
+        Ok(stats)
+    }
+
+    fn aggregate_stats(
+        env: &Env,
+        index: Vec<u64>,
+        token: Option<Address>,
+        project_id: Option<u32>,
+    ) -> Result<AggregateStats, Error> {
+        let mut stats = AggregateStats {
+            total_locked: 0,
+            total_released: 0,
+            total_refunded: 0,
+            count_locked: 0,
+            count_released: 0,
+            count_refunded: 0,
+            total_cancelled: 0,
+            count_cancelled: 0,
+            count_settled: 0,
+            total_disputed: 0,
+            count_disputed: 0,
+        };
+
+        for i in 0..index.len() {
+            let bounty_id = index.get(i).unwrap();
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if let Some(token) = &token {
+                    if Self::escrow_token(env, bounty_id) != *token {
+                        continue;
+                    }
+                }
+                if project_id.is_some_and(|id| escrow.project_id != id) {
+                    continue;
+                }
+                match escrow.status {
+                    EscrowStatus::Locked
+                    | EscrowStatus::PartiallyReleased
+                    | EscrowStatus::PendingClaim
+                    | EscrowStatus::PendingRelease => stats.count_locked += 1,
+                    EscrowStatus::Released => stats.count_released += 1,
+                    EscrowStatus::Refunded | EscrowStatus::PartiallyRefunded => {
+                        stats.count_refunded += 1
+                    }
+                    EscrowStatus::Cancelled => {
+                        Self::add_total(&mut stats.total_cancelled, escrow.amount)?;
+                        stats.count_cancelled += 1;
+                        continue;
+                    }
+                    EscrowStatus::Settled => stats.count_settled += 1,
+                    EscrowStatus::Disputed => stats.count_disputed += 1,
+                }
+
+                // Milestone escrows can be part released and part refunded,
+                // so their totals come from the individual milestones.
+                if !escrow.milestones.is_empty() {
+                    for milestone in escrow.milestones.iter() {
+                        match milestone.status {
+                            MilestoneStatus::Pending if escrow.status == EscrowStatus::Disputed => {
+                                Self::add_total(&mut stats.total_disputed, milestone.amount)?
+                            }
+                            MilestoneStatus::Pending => {
+                                Self::add_total(&mut stats.total_locked, milestone.amount)?
+                            }
+                            MilestoneStatus::Released => {
+                                Self::add_total(&mut stats.total_released, milestone.amount)?
+                            }
+                            MilestoneStatus::Refunded => {
+                                Self::add_total(&mut stats.total_refunded, milestone.amount)?
+                            }
+                        }
+                    }
+                    continue;
+                }
+
+                // Anything already paid out of the escrow counts as released,
+                // even while the rest is still locked or was later refunded.
+                Self::add_total(
+                    &mut stats.total_released,
+                    escrow.amount - escrow.remaining_amount,
+                )?;
+                match escrow.status {
+                    EscrowStatus::Locked
+                    | EscrowStatus::PartiallyReleased
+                    | EscrowStatus::PendingClaim
+                    | EscrowStatus::PendingRelease => {
+                        Self::add_total(&mut stats.total_locked, escrow.remaining_amount)?
+                    }
+                    EscrowStatus::Refunded
+                    | EscrowStatus::PartiallyRefunded
+                    | EscrowStatus::Settled => {
+                        Self::add_total(&mut stats.total_refunded, escrow.remaining_amount)?
+                    }
+                    EscrowStatus::Disputed => {
+                        Self::add_total(&mut stats.total_disputed, escrow.remaining_amount)?
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(stats)
+    }
+
+    fn add_total(total: &mut i128, amount: i128) -> Result<(), Error> {
+        *total = total.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Get total count of escrows
+    pub fn get_escrow_count(env: Env) -> u32 {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        index.len()
+    }
+
+    /// Get the number of escrows currently in `status`, so clients paging
+    /// through a status know how many results to expect.
+    pub fn get_escrow_count_by_status(env: Env, status: EscrowStatus) -> u32 {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut count = 0u32;
+        for bounty_id in index.iter() {
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            {
+                if escrow.status == status {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Set the minimum and maximum allowed lock amount (admin only).
+    ///
+    /// Same as `set_amount_limits`, with the admin passed explicitly: `caller`
+    /// must be the admin or the call fails with Unauthorized. A limit of 0
+    /// disables that side, and min_amount above a non-zero max_amount fails
+    /// with InvalidAmount.
+    pub fn set_amount_policy(
+        env: Env,
+        caller: Address,
+        min_amount: i128,
+        max_amount: i128,
+    ) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != admin {
+            return Err(Error::Unauthorized);
+        }
+        admin.require_auth();
+
+        Self::store_amount_limits(&env, min_amount, max_amount)
+    }
+
+    /// Set the minimum and maximum amount a new escrow can lock (admin only).
+    ///
+    /// A limit of 0 disables that side, so `(0, 0)` removes both. Applies to
+    /// `lock_funds` and the batch lock paths. `top_up` and `contribute` must
+    /// add at least the minimum and keep the escrow's new total within the
+    /// maximum. Escrows that are already locked keep their amount.
+    pub fn set_amount_limits(env: Env, min_amount: i128, max_amount: i128) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        Self::store_amount_limits(&env, min_amount, max_amount)
+    }
+
+    /// Validate and save the lock limits for both setters.
+    fn store_amount_limits(env: &Env, min_amount: i128, max_amount: i128) -> Result<(), Error> {
+        if min_amount < 0 || max_amount < 0 || (max_amount > 0 && min_amount > max_amount) {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AmountPolicy, &(min_amount, max_amount));
+        Ok(())
+    }
+
+    /// view function to get the `(min_amount, max_amount)` lock limits; 0 means no limit
+    pub fn get_amount_limits(env: Env) -> (i128, i128) {
+        env.storage()
+            .instance()
+            .get(&DataKey::AmountPolicy)
+            .unwrap_or((0, 0))
+    }
+
+    /// Cap how much the contract can hold of any one token (admin only).
+    ///
+    /// Locks, batch locks, top-ups and contributions that would take what the
+    /// escrows in a token still hold past `max_total` fail with
+    /// AmountExceedsMax. Tokens sent to the contract directly do not count.
+    /// 0 removes the cap; the per-escrow maximum is set with
+    /// `set_amount_limits`.
+    pub fn set_max_total_locked(env: Env, max_total: i128) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if max_total < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::MaxTotalLocked, &max_total);
+        Ok(())
+    }
+
+    /// Turn the lock, release and refund events on or off (admin only). They
+    /// are on by default.
+    ///
+    /// Turning them off saves the cost of publishing an event on every lock,
+    /// release and refund, batch and split paths included. Escrow state,
+    /// transfers and stats change exactly as before, and every other event
+    /// is still published. Any indexer that follows those events will miss
+    /// everything that happens while they are off and cannot recover it from
+    /// events later, so only turn them off when no one consumes them.
+    pub fn set_emit_events(env: Env, enabled: bool) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
 
-        emit_funds_refunded(
+        if enabled {
+            env.storage()
+                .instance()
+                .remove(&DataKeyExt::TransferEventsMuted);
+        } else {
+            env.storage()
+                .instance()
+                .set(&DataKeyExt::TransferEventsMuted, &true);
+        }
+        events::emit_event_emission_toggled(
             &env,
-            FundsRefunded {
-                version: EVENT_VERSION_V2,
-                bounty_id,
-                amount: escrow.remaining_amount,
-                refund_to: escrow.depositor,
+            events::EventEmissionToggled {
+                enabled,
+                admin,
                 timestamp: env.ledger().timestamp(),
             },
         );
-
         Ok(())
     }
 
-    /// view function to get escrow info
-    pub fn get_escrow_info(env: Env, bounty_id: u64) -> Result<Escrow, Error> {
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
-        }
-        Ok(env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap())
+    /// view function to get whether the lock, release and refund events are published
+    pub fn get_emit_events(env: Env) -> bool {
+        !env.storage()
+            .instance()
+            .has(&DataKeyExt::TransferEventsMuted)
     }
 
-    /// view function to get contract balance of the token
-    pub fn get_balance(env: Env) -> Result<i128, Error> {
-        if !env.storage().instance().has(&DataKey::Token) {
-            return Err(Error::NotInitialized);
-        }
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
-        Ok(client.balance(&env.current_contract_address()))
+    /// view function to get the cap on what the escrows in a token hold; 0 means no cap
+    pub fn get_max_total_locked(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKeyExt::MaxTotalLocked)
+            .unwrap_or(0)
     }
 
-    /// Query escrows with filtering and pagination
-    /// Pass 0 for min values and i128::MAX/u64::MAX for max values to disable those filters
-    pub fn query_escrows_by_status(
+    /// Get escrow IDs by status
+    pub fn get_escrow_ids_by_status(
         env: Env,
         status: EscrowStatus,
         offset: u32,
         limit: u32,
-    ) -> Vec<EscrowWithId> {
+    ) -> Vec<u64> {
         let index: Vec<u64> = env
             .storage()
             .persistent()
@@ -1414,7 +7252,6 @@ impl BountyEscrowContract {
             if count >= limit {
                 break;
             }
-
             let bounty_id = index.get(i).unwrap();
             if let Some(escrow) = env
                 .storage()
@@ -1426,7 +7263,7 @@ impl BountyEscrowContract {
                         skipped += 1;
                         continue;
                     }
-                    results.push_back(EscrowWithId { bounty_id, escrow });
+                    results.push_back(bounty_id);
                     count += 1;
                 }
             }
@@ -1434,277 +7271,246 @@ impl BountyEscrowContract {
         results
     }
 
-    /// Query escrows with amount range filtering
-    pub fn query_escrows_by_amount(
+    /// Cursor-based variant of `get_escrow_ids_by_status`, paging the same
+    /// way as `query_escrows_by_status_paged`.
+    pub fn get_escrow_ids_by_status_paged(
         env: Env,
-        min_amount: i128,
-        max_amount: i128,
-        offset: u32,
+        status: EscrowStatus,
+        start_index: u32,
         limit: u32,
-    ) -> Vec<EscrowWithId> {
-        let index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        let mut results = Vec::new(&env);
-        let mut count = 0u32;
-        let mut skipped = 0u32;
+    ) -> EscrowIdPage {
+        let page = Self::query_escrows_by_status_paged(env.clone(), status, start_index, limit);
+        let mut ids = Vec::new(&env);
+        for item in page.items.iter() {
+            ids.push_back(item.bounty_id);
+        }
+        EscrowIdPage {
+            ids,
+            next_index: page.next_index,
+        }
+    }
 
-        for i in 0..index.len() {
-            if count >= limit {
-                break;
-            }
+    pub fn set_anti_abuse_admin(env: Env, admin: Address) -> Result<(), Error> {
+        let current: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        current.require_auth();
+        anti_abuse::set_admin(&env, admin);
+        Ok(())
+    }
 
-            let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                if escrow.amount >= min_amount && escrow.amount <= max_amount {
-                    if skipped < offset {
-                        skipped += 1;
-                        continue;
-                    }
-                    results.push_back(EscrowWithId { bounty_id, escrow });
-                    count += 1;
-                }
-            }
-        }
-        results
+    pub fn get_anti_abuse_admin(env: Env) -> Option<Address> {
+        anti_abuse::get_admin(&env)
     }
 
-    /// Query escrows with deadline range filtering
-    pub fn query_escrows_by_deadline(
+    /// Exempt an address from the lock rate limit (admin only). This does
+    /// not restrict who may lock funds; for a private deployment use the
+    /// depositor allowlist (`set_allowlist_enabled`, `add_allowed_depositor`).
+    pub fn set_whitelist(
         env: Env,
-        min_deadline: u64,
-        max_deadline: u64,
-        offset: u32,
-        limit: u32,
-    ) -> Vec<EscrowWithId> {
-        let index: Vec<u64> = env
+        whitelisted_address: Address,
+        whitelisted: bool,
+    ) -> Result<(), Error> {
+        let admin: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        let mut results = Vec::new(&env);
-        let mut count = 0u32;
-        let mut skipped = 0u32;
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        anti_abuse::set_whitelist(&env, whitelisted_address, whitelisted);
+        Ok(())
+    }
 
-        for i in 0..index.len() {
-            if count >= limit {
-                break;
-            }
+    /// Limit each depositor to `max_locks` lock calls per `window_seconds`
+    /// (admin only). Going over returns RateLimitExceeded. The cooldown
+    /// between consecutive locks is left as configured.
+    pub fn set_rate_limit(env: Env, max_locks: u32, window_seconds: u64) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        if max_locks == 0 || window_seconds == 0 {
+            return Err(Error::InvalidAmount);
+        }
 
-            let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                if escrow.deadline >= min_deadline && escrow.deadline <= max_deadline {
-                    if skipped < offset {
-                        skipped += 1;
-                        continue;
-                    }
-                    results.push_back(EscrowWithId { bounty_id, escrow });
-                    count += 1;
-                }
-            }
+        let mut config = anti_abuse::get_config(&env);
+        config.max_operations = max_locks;
+        config.window_size = window_seconds;
+        anti_abuse::set_config(&env, config);
+        Ok(())
+    }
+
+    /// When `exempt` is set, the admin and depositors on the allowlist skip the
+    /// rate limit (admin only). Addresses added with `set_whitelist` are
+    /// always exempt.
+    pub fn set_rate_limit_exempt_privileged(env: Env, exempt: bool) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
         }
-        results
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        anti_abuse::set_exempt_privileged(&env, exempt);
+        Ok(())
     }
 
-    /// Query escrows by depositor
-    pub fn query_escrows_by_depositor(
-        env: Env,
-        depositor: Address,
-        offset: u32,
-        limit: u32,
-    ) -> Vec<EscrowWithId> {
-        let index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::DepositorIndex(depositor))
-            .unwrap_or(Vec::new(&env));
-        let mut results = Vec::new(&env);
-        let start = offset.min(index.len());
-        let end = (offset + limit).min(index.len());
+    /// view function to get how many locks `depositor` has left in the current
+    /// window and when they can lock again
+    pub fn get_rate_limit_status(env: Env, depositor: Address) -> RateLimitStatus {
+        let config = anti_abuse::get_config(&env);
+        let mut status = RateLimitStatus {
+            exempt: Self::is_rate_limit_exempt(&env, &depositor),
+            max_locks: config.max_operations,
+            remaining: config.max_operations,
+            window_resets_at: 0,
+            next_allowed_at: 0,
+        };
+        if status.exempt {
+            return status;
+        }
+        let Some(state) = anti_abuse::get_state(&env, depositor) else {
+            return status;
+        };
 
-        for i in start..end {
-            let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                results.push_back(EscrowWithId { bounty_id, escrow });
-            }
+        let now = env.ledger().timestamp();
+        let window_end = state
+            .window_start_timestamp
+            .saturating_add(config.window_size);
+        if now < window_end {
+            status.remaining = config.max_operations.saturating_sub(state.operation_count);
+            status.window_resets_at = window_end;
         }
-        results
+        let cooldown_end = state
+            .last_operation_timestamp
+            .saturating_add(config.cooldown_period);
+        status.next_allowed_at = if status.remaining == 0 {
+            window_end.max(cooldown_end)
+        } else {
+            cooldown_end
+        };
+        status
     }
 
-    /// Get aggregate statistics
-    pub fn get_aggregate_stats(env: Env) -> AggregateStats {
-        let index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        let mut stats = AggregateStats {
-            total_locked: 0,
-            total_released: 0,
-            total_refunded: 0,
-            count_locked: 0,
-            count_released: 0,
-            count_refunded: 0,
-        };
+    fn is_rate_limit_exempt(env: &Env, depositor: &Address) -> bool {
+        if anti_abuse::is_whitelisted(env, depositor.clone()) {
+            return true;
+        }
+        if !anti_abuse::exempts_privileged(env) {
+            return false;
+        }
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        admin.as_ref() == Some(depositor) || Self::allowed_depositors(env).contains(depositor)
+    }
 
-        for i in 0..index.len() {
-            let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                match escrow.status {
-                    EscrowStatus::Locked => {
-                        stats.total_locked += escrow.amount;
-                        stats.count_locked += 1;
-                    }
-                    EscrowStatus::Released => {
-                        stats.total_released += escrow.amount;
-                        stats.count_released += 1;
-                    }
-                    EscrowStatus::Refunded | EscrowStatus::PartiallyRefunded => {
-                        stats.total_refunded += escrow.amount;
-                        stats.count_refunded += 1;
-                    }
-                }
-            }
+    fn check_lock_rate(env: &Env, depositor: &Address) -> Result<(), Error> {
+        if Self::is_rate_limit_exempt(env, depositor) {
+            return Ok(());
         }
-        stats
+        anti_abuse::check_rate_limit(env, depositor.clone())
     }
 
-    /// Get total count of escrows
-    pub fn get_escrow_count(env: Env) -> u32 {
-        let index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        index.len()
+    /// Turn allowlist mode on or off (admin only). While on, only depositors
+    /// added with `add_allowed_depositor` can lock or contribute funds.
+    /// Refunds and releases of existing escrows are never affected.
+    pub fn set_allowlist_enabled(env: Env, enabled: bool) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::AllowlistEnabled, &enabled);
+        Ok(())
     }
 
-    /// Set the minimum and maximum allowed lock amount (admin only).
-    ///
-    /// Once set, any call to lock_funds with an amount outside [min_amount, max_amount]
-    /// will be rejected with AmountBelowMinimum or AmountAboveMaximum respectively.
-    /// The policy can be updated at any time by the admin; new limits take effect
-    /// immediately for subsequent lock_funds calls.
-    ///
-    /// Passing min_amount == max_amount restricts locking to a single exact value.
-    /// min_amount must not exceed max_amount — the call panics if this invariant
-    /// is violated.
-    pub fn set_amount_policy(
-        env: Env,
-        caller: Address,
-        min_amount: i128,
-        max_amount: i128,
-    ) -> Result<(), Error> {
+    /// view function to check whether allowlist mode is on
+    pub fn is_allowlist_enabled(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::AllowlistEnabled)
+            .unwrap_or(false)
+    }
+
+    /// Add `depositor` to the allowlist (admin only). Adding twice is a no-op.
+    pub fn add_allowed_depositor(env: Env, depositor: Address) -> Result<(), Error> {
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::NotInitialized);
         }
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if caller != admin {
-            return Err(Error::Unauthorized);
+        admin.require_auth();
+
+        let mut allowed = Self::allowed_depositors(&env);
+        if !allowed.contains(&depositor) {
+            allowed.push_back(depositor);
+            env.storage()
+                .instance()
+                .set(&DataKey::AllowedDepositors, &allowed);
+        }
+        Ok(())
+    }
+
+    /// Remove `depositor` from the allowlist (admin only). Escrows they
+    /// already locked can still be released and refunded as usual.
+    pub fn remove_allowed_depositor(env: Env, depositor: Address) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
         }
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
 
-        if min_amount > max_amount {
-            panic!("invalid policy: min_amount cannot exceed max_amount");
+        let mut allowed = Self::allowed_depositors(&env);
+        if let Some(i) = allowed.first_index_of(&depositor) {
+            allowed.remove(i);
+            env.storage()
+                .instance()
+                .set(&DataKey::AllowedDepositors, &allowed);
         }
-
-        // Persist the policy so lock_funds can enforce it on every subsequent call.
-        env.storage()
-            .instance()
-            .set(&DataKey::AmountPolicy, &(min_amount, max_amount));
-
         Ok(())
     }
 
-    /// Get escrow IDs by status
-    pub fn get_escrow_ids_by_status(
-        env: Env,
-        status: EscrowStatus,
-        offset: u32,
-        limit: u32,
-    ) -> Vec<u64> {
-        let index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        let mut results = Vec::new(&env);
-        let mut count = 0u32;
-        let mut skipped = 0u32;
+    /// view function to check whether `depositor` is on the allowlist
+    pub fn is_allowed_depositor(env: Env, depositor: Address) -> bool {
+        Self::allowed_depositors(&env).contains(&depositor)
+    }
 
-        for i in 0..index.len() {
-            if count >= limit {
-                break;
-            }
-            let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                if escrow.status == status {
-                    if skipped < offset {
-                        skipped += 1;
-                        continue;
-                    }
-                    results.push_back(bounty_id);
-                    count += 1;
-                }
-            }
+    /// view function to list the allowlist, `limit` entries from `offset`,
+    /// capped at `MAX_PAGE_SIZE`
+    pub fn get_allowed_depositors(env: Env, offset: u32, limit: u32) -> Vec<Address> {
+        let allowed = Self::allowed_depositors(&env);
+        let end = offset
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(allowed.len());
+        if offset >= end {
+            return Vec::new(&env);
         }
-        results
+        allowed.slice(offset..end)
     }
 
-    pub fn set_anti_abuse_admin(env: Env, admin: Address) -> Result<(), Error> {
-        let current: Address = env
-            .storage()
+    fn allowed_depositors(env: &Env) -> Vec<Address> {
+        env.storage()
             .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        current.require_auth();
-        anti_abuse::set_admin(&env, admin);
-        Ok(())
-    }
-
-    pub fn get_anti_abuse_admin(env: Env) -> Option<Address> {
-        anti_abuse::get_admin(&env)
+            .get(&DataKey::AllowedDepositors)
+            .unwrap_or(Vec::new(env))
     }
 
-    pub fn set_whitelist(
-        env: Env,
-        whitelisted_address: Address,
-        whitelisted: bool,
-    ) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        admin.require_auth();
-        anti_abuse::set_whitelist(&env, whitelisted_address, whitelisted);
+    fn check_depositor_allowed(env: &Env, depositor: &Address) -> Result<(), Error> {
+        if Self::is_allowlist_enabled(env.clone())
+            && !Self::allowed_depositors(env).contains(depositor)
+        {
+            return Err(Error::DepositorNotAllowed);
+        }
         Ok(())
     }
 
     /// Retrieves the refund history for a specific bounty.
     ///
+    /// Returns the whole history in one vector. Across escrows, use
+    /// `get_refund_history_paged` or `get_refund_history_for_depositor`,
+    /// which page their results and tag each refund with its bounty_id.
+    ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `bounty_id` - The bounty to query
@@ -1724,6 +7530,105 @@ impl BountyEscrowContract {
         Ok(escrow.refund_history)
     }
 
+    /// Sort a refund record's `reason` symbol into a `RefundReason`.
+    fn refund_reason(reason: &Symbol) -> RefundReason {
+        let is = |name: Symbol| *reason == name;
+        if is(symbol_short!("refund"))
+            || is(symbol_short!("expired"))
+            || is(symbol_short!("milestone"))
+        {
+            RefundReason::DeadlinePassed
+        } else if is(symbol_short!("approved"))
+            || is(symbol_short!("settle"))
+            || is(symbol_short!("arbiter"))
+            || is(symbol_short!("dispute"))
+        {
+            RefundReason::Approved
+        } else if is(symbol_short!("emergency")) || is(symbol_short!("admin")) {
+            RefundReason::Emergency
+        } else if is(symbol_short!("cancel")) || is(symbol_short!("vesting")) {
+            RefundReason::Cancelled
+        } else {
+            RefundReason::Other
+        }
+    }
+
+    /// Walk the refunds of the escrows in `index`, skipping `offset` and
+    /// returning at most `limit` (capped at `MAX_PAGE_SIZE`). With
+    /// `address` set, only refunds of escrows it deposited or paid to it
+    /// are counted.
+    fn refund_entries(
+        env: &Env,
+        index: Vec<u64>,
+        address: Option<Address>,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<RefundHistoryEntry> {
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let mut entries = Vec::new(env);
+        let mut skipped = 0u32;
+        for bounty_id in index.iter() {
+            let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
+            else {
+                continue;
+            };
+            for record in escrow.refund_history.iter() {
+                if entries.len() >= limit {
+                    return entries;
+                }
+                if let Some(address) = &address {
+                    if record.depositor != *address && record.recipient != *address {
+                        continue;
+                    }
+                }
+                if skipped < offset {
+                    skipped += 1;
+                    continue;
+                }
+                entries.push_back(RefundHistoryEntry {
+                    bounty_id,
+                    reason: Self::refund_reason(&record.reason),
+                    record,
+                });
+            }
+        }
+        entries
+    }
+
+    /// view function to get a page of every refund the contract has made,
+    /// by escrow in lock order and then in the order each escrow refunded.
+    /// Returns at most `limit` entries, capped at `MAX_PAGE_SIZE`.
+    pub fn get_refund_history_paged(env: Env, offset: u32, limit: u32) -> Vec<RefundHistoryEntry> {
+        let index = Self::project_candidates(&env, 0);
+        Self::refund_entries(&env, index, None, offset, limit)
+    }
+
+    /// view function to get a bounty's refunds with their `RefundReason`;
+    /// empty for an unknown bounty
+    pub fn get_refund_history_for_bounty(env: Env, bounty_id: u64) -> Vec<RefundHistoryEntry> {
+        Self::refund_entries(&env, vec![&env, bounty_id], None, 0, MAX_PAGE_SIZE)
+    }
+
+    /// view function to get a page of the refunds of escrows `depositor`
+    /// locked, plus refunds paid to them on escrows they contributed to, in
+    /// the same order as `get_refund_history_paged`
+    pub fn get_refund_history_for_depositor(
+        env: Env,
+        depositor: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<RefundHistoryEntry> {
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DepositorIndex(depositor.clone()))
+            .unwrap_or(Vec::new(&env));
+        Self::refund_entries(&env, index, Some(depositor), offset, limit)
+    }
+
     /// Gets refund eligibility information for a bounty.
     ///
     /// # Arguments
@@ -1731,16 +7636,19 @@ impl BountyEscrowContract {
     /// * `bounty_id` - The bounty to query
     ///
     /// # Returns
-    /// * `Ok((bool, bool, i128, Option<RefundApproval>))` - Tuple containing:
-    ///   - can_refund: Whether refund is possible
-    ///   - deadline_passed: Whether the deadline has passed
+    /// * `Ok((RefundEligibility, bool, i128, Option<RefundApproval>))` - Tuple containing:
+    ///   - eligibility: Whether `refund` would succeed now, or why not. A
+    ///     refund approval does not make an escrow eligible; it is paid out
+    ///     by the admin, not through `refund`.
+    ///   - deadline_passed: Whether the deadline has passed, ignoring the
+    ///     refund grace period
     ///   - remaining: Remaining amount in escrow
     ///   - approval: Optional refund approval if exists
     /// * `Err(Error::BountyNotFound)` - Bounty doesn't exist
     pub fn get_refund_eligibility(
         env: Env,
         bounty_id: u64,
-    ) -> Result<(bool, bool, i128, Option<RefundApproval>), Error> {
+    ) -> Result<(RefundEligibility, bool, i128, Option<RefundApproval>), Error> {
         if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
             return Err(Error::BountyNotFound);
         }
@@ -1768,21 +7676,92 @@ impl BountyEscrowContract {
             None
         };
 
-        // can_refund is true if:
-        // 1. Status is Locked or PartiallyRefunded AND
-        // 2. (deadline has passed OR there's an approval)
-        let can_refund = (escrow.status == EscrowStatus::Locked
-            || escrow.status == EscrowStatus::PartiallyRefunded)
-            && (deadline_passed || approval.is_some());
+        let eligibility = match Self::check_refundable(&env, bounty_id, &escrow) {
+            Ok(()) => RefundEligibility::Eligible,
+            Err(Error::DeadlineNotPassed) => {
+                // Milestone escrows open for refund milestone by milestone
+                let next_deadline = if escrow.milestones.is_empty() {
+                    escrow.deadline
+                } else {
+                    escrow
+                        .milestones
+                        .iter()
+                        .filter(|milestone| milestone.status == MilestoneStatus::Pending)
+                        .map(|milestone| milestone.deadline)
+                        .min()
+                        .unwrap_or(escrow.deadline)
+                };
+                if env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::RefundRequest(bounty_id))
+                {
+                    RefundEligibility::PendingApproval
+                } else if now >= next_deadline {
+                    RefundEligibility::InGracePeriod
+                } else {
+                    RefundEligibility::DeadlineNotPassed
+                }
+            }
+            Err(_) => RefundEligibility::NotRefundable,
+        };
 
         Ok((
-            can_refund,
+            eligibility,
             deadline_passed,
             escrow.remaining_amount,
             approval,
         ))
     }
 
+    /// Batch view of `get_refund_eligibility` for keepers scanning many escrows.
+    ///
+    /// Returns each id with whether `refund` would succeed now and why, in
+    /// the same order as `bounty_ids`. Unknown ids are reported as
+    /// `NotFound`. At most `MAX_INFO_BATCH_SIZE` (50) ids per call.
+    pub fn get_refund_eligibility_batch(
+        env: Env,
+        bounty_ids: Vec<u64>,
+    ) -> Result<Vec<(u64, bool, RefundEligibility)>, Error> {
+        if bounty_ids.len() > MAX_INFO_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+
+        let mut results: Vec<(u64, bool, RefundEligibility)> = Vec::new(&env);
+        for bounty_id in bounty_ids.iter() {
+            let reason = Self::get_refund_eligibility(env.clone(), bounty_id)
+                .map(|(eligibility, ..)| eligibility)
+                .unwrap_or(RefundEligibility::NotFound);
+            results.push_back((bounty_id, reason == RefundEligibility::Eligible, reason));
+        }
+        Ok(results)
+    }
+
+    /// view function to get the ids of up to `limit` escrows (capped at
+    /// `MAX_PAGE_SIZE`) that `refund` would accept right now, in lock order.
+    /// Checks every escrow against the current ledger time, so keepers do
+    /// not need to know the ids up front.
+    pub fn get_all_refund_eligible_ids(env: Env, limit: u32) -> Vec<u64> {
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut ids = Vec::new(&env);
+        for bounty_id in index.iter() {
+            if ids.len() >= limit {
+                break;
+            }
+            if let Ok((RefundEligibility::Eligible, ..)) =
+                Self::get_refund_eligibility(env.clone(), bounty_id)
+            {
+                ids.push_back(bounty_id);
+            }
+        }
+        ids
+    }
+
     /// Batch lock funds for multiple bounties in a single transaction.
     /// This improves gas efficiency by reducing transaction overhead.
     ///
@@ -1794,15 +7773,21 @@ impl BountyEscrowContract {
     ///
     /// # Errors
     /// * InvalidBatchSize - if batch size exceeds MAX_BATCH_SIZE or is zero
-    /// * BountyExists - if any bounty_id already exists
+    /// * BountyAlreadyExists - if any bounty_id already exists
     /// * NotInitialized - if contract is not initialized
     ///
     /// # Note
     /// This operation is atomic - if any item fails, the entire transaction reverts.
     pub fn batch_lock_funds(env: Env, items: Vec<LockFundsItem>) -> Result<u32, Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
         if Self::check_paused(&env, symbol_short!("lock")) {
-            return Err(Error::FundsPaused);
+            return Err(Error::LockPaused);
         }
+
+        Self::enter_guard(&env)?;
+
         // Validate batch size
         let batch_size = items.len() as u32;
         if batch_size == 0 {
@@ -1822,6 +7807,7 @@ impl BountyEscrowContract {
         let timestamp = env.ledger().timestamp();
 
         // Validate all items before processing (all-or-nothing approach)
+        let mut batch_total: i128 = 0;
         for item in items.iter() {
             // Check if bounty already exists
             if env
@@ -1829,13 +7815,19 @@ impl BountyEscrowContract {
                 .persistent()
                 .has(&DataKey::Escrow(item.bounty_id))
             {
-                return Err(Error::BountyExists);
+                return Err(Error::BountyAlreadyExists);
             }
 
             // Validate amount
             if item.amount <= 0 {
                 return Err(Error::InvalidAmount);
             }
+            Self::check_depositor_allowed(&env, &item.depositor)?;
+            Self::check_amount_policy(&env, item.amount)?;
+            Self::check_deadline(&env, item.deadline)?;
+            batch_total = batch_total
+                .checked_add(item.amount)
+                .ok_or(Error::InvalidAmount)?;
 
             // Check for duplicate bounty_ids in the batch
             let mut count = 0u32;
@@ -1848,6 +7840,7 @@ impl BountyEscrowContract {
                 return Err(Error::DuplicateBountyId);
             }
         }
+        Self::check_total_locked(&env, 0, Some(token_addr.clone()), batch_total)?;
 
         // Collect unique depositors and require auth once for each
         // This prevents "frame is already authorized" errors when same depositor appears multiple times
@@ -1861,11 +7854,18 @@ impl BountyEscrowContract {
                 }
             }
             if !found {
+                Self::check_lock_rate(&env, &item.depositor)?;
                 seen_depositors.push_back(item.depositor.clone());
                 item.depositor.require_auth();
             }
         }
 
+        let mut index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+
         // Process all items (atomic - all succeed or all fail)
         let mut locked_count = 0u32;
         for item in items.iter() {
@@ -1880,18 +7880,51 @@ impl BountyEscrowContract {
                 deadline: item.deadline,
                 refund_history: vec![&env],
                 remaining_amount: item.amount,
+                milestones: vec![&env],
+                arbiter: None,
+                memo: None,
+                project_id: 0,
+                release_reason: None,
             };
 
             // Store escrow
             env.storage()
                 .persistent()
                 .set(&DataKey::Escrow(item.bounty_id), &escrow);
+            env.storage()
+                .persistent()
+                .set(&DataKey::LockedAt(item.bounty_id), &timestamp);
+
+            // Index like lock_funds so keepers, stats and queries see the escrow
+            index.push_back(item.bounty_id);
+            let depositor_key = DataKey::DepositorIndex(item.depositor.clone());
+            let mut depositor_index: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&depositor_key)
+                .unwrap_or(Vec::new(&env));
+            depositor_index.push_back(item.bounty_id);
+            env.storage()
+                .persistent()
+                .set(&depositor_key, &depositor_index);
+
+            Self::record_depositor_lock(&env, &item.depositor, item.amount);
+            Self::record_history(
+                &env,
+                item.bounty_id,
+                EscrowAction::Locked,
+                &item.depositor,
+                item.amount,
+            );
+            Self::record_daily_volume(&env, DailyMetric::Locked, item.amount);
 
             // Emit individual event for each locked bounty
             emit_funds_locked(
                 &env,
                 FundsLocked {
-                    version: EVENT_VERSION_V2,
+                    version: EVENT_VERSION_V3,
+                    token: token_addr.clone(),
+                    timestamp: env.ledger().timestamp(),
                     bounty_id: item.bounty_id,
                     amount: item.amount,
                     depositor: item.depositor.clone(),
@@ -1901,6 +7934,9 @@ impl BountyEscrowContract {
 
             locked_count += 1;
         }
+        env.storage()
+            .persistent()
+            .set(&DataKey::EscrowIndex, &index);
 
         // Emit batch event
         emit_batch_funds_locked(
@@ -1912,9 +7948,157 @@ impl BountyEscrowContract {
             },
         );
 
+        Self::exit_guard(&env);
         Ok(locked_count)
     }
 
+    /// Lock several bounties for one depositor in a single transaction.
+    ///
+    /// # Arguments
+    /// * `depositor` - Address funding every bounty in the batch
+    /// * `items` - (bounty_id, amount, deadline) for each bounty
+    ///
+    /// # Returns
+    /// Number of locked bounties
+    ///
+    /// # Errors
+    /// * InvalidBatchSize - if the batch is empty or larger than MAX_BATCH_SIZE
+    /// * BountyAlreadyExists / DuplicateBountyId - if any bounty_id is taken or repeated
+    /// * InvalidAmount, AmountBelowMinimum, AmountAboveMaximum - if any amount is rejected
+    /// * AmountExceedsMax - if the batch would take the escrows past `max_total_locked`
+    ///
+    /// # Note
+    /// The summed amount is transferred once, then each bounty gets its own
+    /// escrow and FundsLocked event. Every item costs one persistent write,
+    /// so MAX_BATCH_SIZE (20) is the supported limit; larger sets should be
+    /// split across calls. Any failure reverts the whole batch.
+    pub fn lock_funds_batch(
+        env: Env,
+        depositor: Address,
+        items: Vec<(u64, i128, u64)>,
+    ) -> Result<u32, Error> {
+        Self::check_lock_rate(&env, &depositor)?;
+
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        if Self::check_paused(&env, symbol_short!("lock")) {
+            return Err(Error::LockPaused);
+        }
+
+        Self::enter_guard(&env)?;
+
+        let batch_size = items.len();
+        if batch_size == 0 || batch_size > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+
+        depositor.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        Self::check_depositor_allowed(&env, &depositor)?;
+
+        // Validate all items before moving any tokens
+        let mut total_amount: i128 = 0;
+        for (i, (bounty_id, amount, deadline)) in items.iter().enumerate() {
+            if env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+                return Err(Error::BountyAlreadyExists);
+            }
+            if items
+                .iter()
+                .skip(i + 1)
+                .any(|(other, _, _)| other == bounty_id)
+            {
+                return Err(Error::DuplicateBountyId);
+            }
+            if amount <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            Self::check_amount_policy(&env, amount)?;
+            Self::check_deadline(&env, deadline)?;
+            total_amount = total_amount
+                .checked_add(amount)
+                .ok_or(Error::InvalidAmount)?;
+        }
+
+        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        Self::check_total_locked(&env, 0, Some(token_addr.clone()), total_amount)?;
+        let client = token::Client::new(&env, &token_addr);
+        client.transfer(&depositor, &env.current_contract_address(), &total_amount);
+
+        let mut index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowIndex)
+            .unwrap_or(Vec::new(&env));
+        let mut depositor_index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DepositorIndex(depositor.clone()))
+            .unwrap_or(Vec::new(&env));
+
+        for (bounty_id, amount, deadline) in items.iter() {
+            let escrow = Escrow {
+                depositor: depositor.clone(),
+                amount,
+                status: EscrowStatus::Locked,
+                deadline,
+                refund_history: vec![&env],
+                remaining_amount: amount,
+                milestones: vec![&env],
+                arbiter: None,
+                memo: None,
+                project_id: 0,
+                release_reason: None,
+            };
+            env.storage()
+                .persistent()
+                .set(&DataKey::Escrow(bounty_id), &escrow);
+            env.storage()
+                .persistent()
+                .set(&DataKey::LockedAt(bounty_id), &env.ledger().timestamp());
+            Self::record_depositor_lock(&env, &depositor, amount);
+            Self::record_daily_volume(&env, DailyMetric::Locked, amount);
+            Self::record_history(&env, bounty_id, EscrowAction::Locked, &depositor, amount);
+            index.push_back(bounty_id);
+            depositor_index.push_back(bounty_id);
+
+            emit_funds_locked(
+                &env,
+                FundsLocked {
+                    version: EVENT_VERSION_V3,
+                    token: token_addr.clone(),
+                    timestamp: env.ledger().timestamp(),
+                    bounty_id,
+                    amount,
+                    depositor: depositor.clone(),
+                    deadline,
+                },
+            );
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::EscrowIndex, &index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::DepositorIndex(depositor), &depositor_index);
+
+        emit_batch_funds_locked(
+            &env,
+            BatchFundsLocked {
+                count: batch_size,
+                total_amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Self::exit_guard(&env);
+        Ok(batch_size)
+    }
+
     /// Batch release funds to multiple contributors in a single transaction.
     /// This improves gas efficiency by reducing transaction overhead.
     ///
@@ -1928,14 +8112,23 @@ impl BountyEscrowContract {
     /// * InvalidBatchSize - if batch size exceeds MAX_BATCH_SIZE or is zero
     /// * BountyNotFound - if any bounty_id doesn't exist
     /// * FundsNotLocked - if any bounty is not in Locked status
+    /// * ReleaseTimelocked - if any bounty is above the release timelock threshold
     /// * Unauthorized - if caller is not admin
     ///
     /// # Note
     /// This operation is atomic - if any item fails, the entire transaction reverts.
+    /// The offending bounty_id is written to the diagnostic log so callers can see
+    /// which entry caused the revert when simulating the transaction.
     pub fn batch_release_funds(env: Env, items: Vec<ReleaseFundsItem>) -> Result<u32, Error> {
+        if Self::is_contract_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
         if Self::check_paused(&env, symbol_short!("release")) {
-            return Err(Error::FundsPaused);
+            return Err(Error::ReleasePaused);
         }
+
+        Self::enter_guard(&env)?;
+
         // Validate batch size
         let batch_size = items.len() as u32;
         if batch_size == 0 {
@@ -1952,9 +8145,6 @@ impl BountyEscrowContract {
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
 
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
-        let contract_address = env.current_contract_address();
         let timestamp = env.ledger().timestamp();
 
         // Validate all items before processing (all-or-nothing approach)
@@ -1966,6 +8156,11 @@ impl BountyEscrowContract {
                 .persistent()
                 .has(&DataKey::Escrow(item.bounty_id))
             {
+                log!(
+                    &env,
+                    "batch_release_funds: bounty not found",
+                    item.bounty_id
+                );
                 return Err(Error::BountyNotFound);
             }
 
@@ -1977,8 +8172,16 @@ impl BountyEscrowContract {
 
             // Check if funds are locked
             if escrow.status != EscrowStatus::Locked {
+                log!(
+                    &env,
+                    "batch_release_funds: bounty not locked",
+                    item.bounty_id
+                );
                 return Err(Error::FundsNotLocked);
             }
+            Self::check_assignee(&env, item.bounty_id, &item.contributor)?;
+            Self::check_not_blocked(&env, &item.contributor)?;
+            Self::check_release_timelock(&env, escrow.remaining_amount)?;
 
             // Check for duplicate bounty_ids in the batch
             let mut count = 0u32;
@@ -1988,11 +8191,16 @@ impl BountyEscrowContract {
                 }
             }
             if count > 1 {
+                log!(
+                    &env,
+                    "batch_release_funds: duplicate bounty",
+                    item.bounty_id
+                );
                 return Err(Error::DuplicateBountyId);
             }
 
             total_amount = total_amount
-                .checked_add(escrow.amount)
+                .checked_add(escrow.remaining_amount)
                 .ok_or(Error::InvalidAmount)?;
         }
 
@@ -2005,23 +8213,40 @@ impl BountyEscrowContract {
                 .get(&DataKey::Escrow(item.bounty_id))
                 .unwrap();
 
-            // Transfer funds to contributor
-            client.transfer(&contract_address, &item.contributor, &escrow.amount);
+            // Transfer funds to contributor, less the platform fee
+            let token_addr = Self::escrow_token(&env, item.bounty_id);
+            let client = token::Client::new(&env, &token_addr);
+            let (net, fee) = Self::pay_release(
+                &env,
+                item.bounty_id,
+                &client,
+                &item.contributor,
+                escrow.remaining_amount,
+            );
+            Self::index_contributor(&env, item.bounty_id, &item.contributor);
 
             // Update escrow status
+            escrow.remaining_amount = 0;
             escrow.status = EscrowStatus::Released;
+            escrow.release_reason = Some(symbol_short!("release"));
             env.storage()
                 .persistent()
                 .set(&DataKey::Escrow(item.bounty_id), &escrow);
+            Self::record_depositor_close(&env, &escrow.depositor, false);
 
             // Emit individual event for each released bounty
             emit_funds_released(
                 &env,
                 FundsReleased {
-                    version: EVENT_VERSION_V2,
+                    version: EVENT_VERSION_V3,
+                    token: token_addr.clone(),
                     bounty_id: item.bounty_id,
-                    amount: escrow.amount,
+                    depositor: escrow.depositor.clone(),
+                    gross: net + fee,
+                    amount: net,
+                    fee,
                     recipient: item.contributor.clone(),
+                    reason: symbol_short!("release"),
                     timestamp,
                 },
             );
@@ -2039,6 +8264,7 @@ impl BountyEscrowContract {
             },
         );
 
+        Self::exit_guard(&env);
         Ok(released_count)
     }
 }
@@ -2055,6 +8281,10 @@ mod test_expiration_and_dispute;
 #[cfg(test)]
 mod test_granular_pause;
 #[cfg(test)]
+mod test_milestones;
+#[cfg(test)]
 mod test_pause;
 #[cfg(test)]
 mod test_query_filters;
+#[cfg(test)]
+mod test_reentrancy;
@@ -2,8 +2,8 @@
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    token, Address, Env,
+    testutils::{Address as _, Events, Ledger},
+    token, Address, Env, Symbol, TryFromVal,
 };
 
 fn create_token_contract<'a>(
@@ -91,7 +91,7 @@ fn test_lock_funds_success() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #3)")] // BountyExists
+#[should_panic(expected = "Error(Contract, #3)")] // BountyAlreadyExists
 fn test_lock_funds_duplicate() {
     let setup = TestSetup::new();
     let bounty_id = 1;
@@ -108,6 +108,52 @@ fn test_lock_funds_duplicate() {
         .lock_funds(&setup.depositor, &bounty_id, &amount, &deadline);
 }
 
+#[test]
+fn test_lock_funds_duplicate_rejected_in_every_status() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    // 1 stays Locked, 2 is Released, 3 is Refunded
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1, &1000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2, &2000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &3, &3000, &deadline);
+    setup.escrow.release_funds(&2, &setup.contributor);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&3);
+
+    let count_before = setup.escrow.get_escrow_count();
+    let stats_before = setup.escrow.get_aggregate_stats();
+    let depositor_before = setup
+        .escrow
+        .query_escrows_by_depositor(&setup.depositor, &0, &10)
+        .len();
+
+    let new_deadline = deadline + 1000;
+    for bounty_id in [1u64, 2, 3] {
+        let res = setup
+            .escrow
+            .try_lock_funds(&setup.depositor, &bounty_id, &500, &new_deadline);
+        assert_eq!(res, Err(Ok(Error::BountyAlreadyExists)));
+    }
+
+    assert_eq!(setup.escrow.get_escrow_count(), count_before);
+    assert_eq!(setup.escrow.get_aggregate_stats(), stats_before);
+    assert_eq!(
+        setup
+            .escrow
+            .query_escrows_by_depositor(&setup.depositor, &0, &10)
+            .len(),
+        depositor_before
+    );
+    assert_eq!(setup.escrow.get_escrow_info(&1).amount, 1000);
+}
+
 #[test]
 #[should_panic] // Token transfer fail
 fn test_lock_funds_negative_amount() {
@@ -383,9 +429,9 @@ fn test_partial_release_full_amount_in_one_shot_marks_released() {
 
 /// Attempting to release more than remaining_amount must be rejected.
 /// After a partial release leaves 10 units, trying to release 11 must panic
-/// with InsufficientFunds — ensuring no overpayment or rounding exploit is possible.
+/// with InsufficientEscrowBalance — ensuring no overpayment or rounding exploit is possible.
 #[test]
-#[should_panic(expected = "Error(Contract, #16)")] // InsufficientFunds
+#[should_panic(expected = "Error(Contract, #16)")] // InsufficientEscrowBalance
 fn test_partial_release_overpayment_panics() {
     let setup = TestSetup::new();
     let bounty_id = 46;
@@ -599,9 +645,9 @@ fn test_claim_within_window_transfers_funds() {
 }
 
 /// Authorize a claim then advance time past the window. Calling claim() must
-/// fail — funds must NOT leave the contract and escrow must stay Locked.
+/// fail — funds must NOT leave the contract and escrow must stay PendingClaim.
 #[test]
-#[should_panic(expected = "Error(Contract, #6)")] // DeadlineNotPassed reused for ClaimExpired
+#[should_panic(expected = "Error(Contract, #27)")] // ClaimExpired
 fn test_claim_after_window_expires_panics() {
     let setup = TestSetup::new();
     let bounty_id = 101_u64;
@@ -781,7 +827,7 @@ fn test_claim_does_not_affect_other_bounties() {
 /// When no claim window is explicitly set (default 0) authorize_claim creates a
 /// claim that expires immediately (expires_at == now). Any claim() call must fail.
 #[test]
-#[should_panic(expected = "Error(Contract, #6)")] // DeadlineNotPassed / ClaimExpired
+#[should_panic(expected = "Error(Contract, #27)")] // ClaimExpired
 fn test_authorize_claim_zero_window_expires_immediately() {
     let setup = TestSetup::new();
     let bounty_id = 108_u64;
@@ -1073,7 +1119,7 @@ fn test_authorize_claim_creates_pending_claim() {
     // --- DUPLICATE BOUNTY ID TESTS ---
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #3)")] // BountyExists
+    #[should_panic(expected = "Error(Contract, #3)")] // BountyAlreadyExists
     fn test_batch_lock_funds_duplicate_bounty_id() {
         let setup = TestSetup::new();
         let deadline = setup.env.ledger().timestamp() + 1000;
@@ -1264,7 +1310,7 @@ fn test_authorize_claim_creates_pending_claim() {
     // --- MIXED VALIDITY TESTS ---
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #3)")] // BountyExists
+    #[should_panic(expected = "Error(Contract, #3)")] // BountyAlreadyExists
     fn test_batch_lock_funds_first_valid_second_exists() {
         // First item is valid, second already exists - entire batch should fail
         let setup = TestSetup::new();
@@ -1296,7 +1342,7 @@ fn test_authorize_claim_creates_pending_claim() {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #3)")] // BountyExists
+    #[should_panic(expected = "Error(Contract, #3)")] // BountyAlreadyExists
     fn test_batch_operations_atomicity() {
         let setup = TestSetup::new();
         let deadline = setup.env.ledger().timestamp() + 1000;
@@ -1780,3 +1826,3626 @@ fn test_authorize_claim_creates_pending_claim() {
         assert_eq!(setup.escrow.get_balance(), 0);
     }
 }
+
+// =============================================================================
+// Split release tests
+// =============================================================================
+
+#[test]
+fn test_release_funds_split_pays_every_recipient() {
+    let setup = TestSetup::new();
+    let bounty_id = 200_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    let second = Address::generate(&setup.env);
+    let third = Address::generate(&setup.env);
+    let payouts = vec![
+        &setup.env,
+        (setup.contributor.clone(), 500_i128),
+        (second.clone(), 300_i128),
+        (third.clone(), 200_i128),
+    ];
+    setup.escrow.release_funds_split(&bounty_id, &payouts);
+
+    assert_eq!(setup.token.balance(&setup.contributor), 500);
+    assert_eq!(setup.token.balance(&second), 300);
+    assert_eq!(setup.token.balance(&third), 200);
+    assert_eq!(setup.escrow.get_balance(), 0);
+
+    let escrow = setup.escrow.get_escrow_info(&bounty_id);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.remaining_amount, 0);
+
+    // The escrow is counted once, not once per recipient
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.count_released, 1);
+    assert_eq!(stats.total_released, 1_000);
+    assert_eq!(stats.count_locked, 0);
+}
+
+#[test]
+fn test_release_funds_split_mismatch_leaves_escrow_locked() {
+    let setup = TestSetup::new();
+    let bounty_id = 201_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    let other = Address::generate(&setup.env);
+    let under = vec![
+        &setup.env,
+        (setup.contributor.clone(), 500_i128),
+        (other.clone(), 400_i128),
+    ];
+    let res = setup.escrow.try_release_funds_split(&bounty_id, &under);
+    assert_eq!(res, Err(Ok(Error::SplitAmountMismatch)));
+
+    let over = vec![
+        &setup.env,
+        (setup.contributor.clone(), 600_i128),
+        (other.clone(), 500_i128),
+    ];
+    let res = setup.escrow.try_release_funds_split(&bounty_id, &over);
+    assert_eq!(res, Err(Ok(Error::SplitAmountMismatch)));
+
+    assert_eq!(setup.token.balance(&setup.contributor), 0);
+    assert_eq!(setup.escrow.get_balance(), 1_000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&bounty_id).status,
+        EscrowStatus::Locked
+    );
+}
+
+#[test]
+fn test_release_funds_split_rejects_empty_and_non_positive_legs() {
+    let setup = TestSetup::new();
+    let bounty_id = 202_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    let empty: Vec<(Address, i128)> = vec![&setup.env];
+    let res = setup.escrow.try_release_funds_split(&bounty_id, &empty);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    let other = Address::generate(&setup.env);
+    let with_zero = vec![
+        &setup.env,
+        (setup.contributor.clone(), 1_000_i128),
+        (other, 0_i128),
+    ];
+    let res = setup.escrow.try_release_funds_split(&bounty_id, &with_zero);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+}
+
+#[test]
+fn test_release_funds_split_on_released_bounty_fails() {
+    let setup = TestSetup::new();
+    let bounty_id = 203_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+    setup.escrow.release_funds(&bounty_id, &setup.contributor);
+
+    let payouts = vec![&setup.env, (setup.contributor.clone(), 1_000_i128)];
+    let res = setup.escrow.try_release_funds_split(&bounty_id, &payouts);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+#[test]
+fn test_release_split_by_shares_sends_dust_to_first_recipient() {
+    let setup = TestSetup::new();
+    let bounty_id = 203_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_001, &deadline);
+
+    let second = Address::generate(&setup.env);
+    let third = Address::generate(&setup.env);
+    let shares = vec![
+        &setup.env,
+        (setup.contributor.clone(), 3_334_u32),
+        (second.clone(), 3_333_u32),
+        (third.clone(), 3_333_u32),
+    ];
+    setup.escrow.release_split(&bounty_id, &shares);
+
+    // 333 + 333 + 333 = 999, the remaining 2 go to the first recipient
+    assert_eq!(setup.token.balance(&setup.contributor), 335);
+    assert_eq!(setup.token.balance(&second), 333);
+    assert_eq!(setup.token.balance(&third), 333);
+    assert_eq!(setup.escrow.get_balance(), 0);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&bounty_id).status,
+        EscrowStatus::Released
+    );
+
+    let breakdown = setup.escrow.get_release_breakdown(&bounty_id);
+    assert_eq!(
+        breakdown,
+        vec![
+            &setup.env,
+            (setup.contributor.clone(), 335_i128),
+            (second, 333_i128),
+            (third, 333_i128),
+        ]
+    );
+}
+
+#[test]
+fn test_release_split_rejects_invalid_shares() {
+    let setup = TestSetup::new();
+    let bounty_id = 204_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+    let other = Address::generate(&setup.env);
+
+    let empty: Vec<(Address, u32)> = vec![&setup.env];
+    let res = setup.escrow.try_release_split(&bounty_id, &empty);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    let zero_share = vec![
+        &setup.env,
+        (setup.contributor.clone(), 10_000_u32),
+        (other.clone(), 0_u32),
+    ];
+    let res = setup.escrow.try_release_split(&bounty_id, &zero_share);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    let short = vec![
+        &setup.env,
+        (setup.contributor.clone(), 5_000_u32),
+        (other.clone(), 4_999_u32),
+    ];
+    let res = setup.escrow.try_release_split(&bounty_id, &short);
+    assert_eq!(res, Err(Ok(Error::SplitAmountMismatch)));
+
+    let escrow = setup.escrow.get_escrow_info(&bounty_id);
+    assert_eq!(escrow.status, EscrowStatus::Locked);
+    assert_eq!(setup.escrow.get_balance(), 1_000);
+    assert_eq!(setup.escrow.get_release_breakdown(&bounty_id).len(), 0);
+}
+
+#[test]
+fn test_get_release_breakdown_unknown_bounty() {
+    let setup = TestSetup::new();
+    let res = setup.escrow.try_get_release_breakdown(&999);
+    assert_eq!(res, Err(Ok(Error::BountyNotFound)));
+}
+
+// =============================================================================
+// Top-up tests
+// =============================================================================
+
+#[test]
+fn test_top_up_increases_locked_amount() {
+    let setup = TestSetup::new();
+    let bounty_id = 300_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    setup.escrow.top_up(&setup.depositor, &bounty_id, &250);
+
+    let escrow = setup.escrow.get_escrow_info(&bounty_id);
+    assert_eq!(escrow.amount, 1_250);
+    assert_eq!(escrow.remaining_amount, 1_250);
+    assert_eq!(escrow.status, EscrowStatus::Locked);
+    assert_eq!(setup.escrow.get_balance(), 1_250);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 1_250);
+    assert_eq!(stats.count_locked, 1);
+
+    // The release pays out the topped-up total
+    setup.escrow.release_funds(&bounty_id, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_250);
+}
+
+#[test]
+fn test_top_up_emits_old_and_new_amounts() {
+    let setup = TestSetup::new();
+    let bounty_id = 301_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    setup.escrow.top_up(&setup.depositor, &bounty_id, &500);
+
+    let events = setup.env.events().all();
+    let (_, topics, data) = events.last().unwrap();
+    let topic: Symbol = Symbol::try_from_val(&setup.env, &topics.get(2).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("top_up"));
+    let event: events::FundsToppedUp =
+        events::FundsToppedUp::try_from_val(&setup.env, &data).unwrap();
+    assert_eq!(event.version, events::EVENT_VERSION_V3);
+    assert_eq!(event.bounty_id, bounty_id);
+    assert_eq!(event.token, setup.token.address);
+    assert_eq!(event.old_amount, 1_000);
+    assert_eq!(event.new_amount, 1_500);
+}
+
+#[test]
+fn test_top_up_by_non_depositor_rejected() {
+    let setup = TestSetup::new();
+    let bounty_id = 302_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    let stranger = Address::generate(&setup.env);
+    setup.token_admin.mint(&stranger, &1_000);
+    let res = setup.escrow.try_top_up(&stranger, &bounty_id, &100);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+    assert_eq!(setup.escrow.get_escrow_info(&bounty_id).amount, 1_000);
+}
+
+#[test]
+fn test_top_up_rejected_after_release_or_refund() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &303, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &304, &1_000, &deadline);
+    setup.escrow.release_funds(&303, &setup.contributor);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&304);
+
+    let res = setup.escrow.try_top_up(&setup.depositor, &303, &100);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+    let res = setup.escrow.try_top_up(&setup.depositor, &304, &100);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+#[test]
+fn test_top_up_rejects_non_positive_amount() {
+    let setup = TestSetup::new();
+    let bounty_id = 305_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    let res = setup.escrow.try_top_up(&setup.depositor, &bounty_id, &0);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+    let res = setup.escrow.try_top_up(&setup.depositor, &bounty_id, &-5);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+}
+
+// =============================================================================
+// Batch release tests
+// =============================================================================
+
+#[test]
+fn test_batch_release_counts_each_bounty_once() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let contributor2 = Address::generate(&setup.env);
+    for id in 400_u64..403 {
+        setup
+            .escrow
+            .lock_funds(&setup.depositor, &id, &1_000, &deadline);
+    }
+
+    let items = vec![
+        &setup.env,
+        ReleaseFundsItem {
+            bounty_id: 400,
+            contributor: setup.contributor.clone(),
+        },
+        ReleaseFundsItem {
+            bounty_id: 401,
+            contributor: contributor2.clone(),
+        },
+        ReleaseFundsItem {
+            bounty_id: 402,
+            contributor: setup.contributor.clone(),
+        },
+    ];
+    assert_eq!(setup.escrow.batch_release_funds(&items), 3);
+
+    // One release event per bounty
+    let released_events = setup
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics
+                .get(2)
+                .and_then(|t| Symbol::try_from_val(&setup.env, &t).ok())
+                == Some(symbol_short!("release"))
+        })
+        .count();
+    assert_eq!(released_events, 3);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.count_released, 3);
+    assert_eq!(stats.total_released, 3_000);
+    assert_eq!(stats.count_locked, 0);
+    assert_eq!(setup.token.balance(&setup.contributor), 2_000);
+    assert_eq!(setup.token.balance(&contributor2), 1_000);
+}
+
+#[test]
+fn test_batch_release_reverts_whole_batch_on_non_locked_entry() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    for id in 410_u64..413 {
+        setup
+            .escrow
+            .lock_funds(&setup.depositor, &id, &1_000, &deadline);
+    }
+    setup.escrow.release_funds(&411, &setup.contributor);
+    let stats_before = setup.escrow.get_aggregate_stats();
+
+    let items = vec![
+        &setup.env,
+        ReleaseFundsItem {
+            bounty_id: 410,
+            contributor: setup.contributor.clone(),
+        },
+        ReleaseFundsItem {
+            bounty_id: 411,
+            contributor: setup.contributor.clone(),
+        },
+        ReleaseFundsItem {
+            bounty_id: 412,
+            contributor: setup.contributor.clone(),
+        },
+    ];
+    let res = setup.escrow.try_batch_release_funds(&items);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+
+    assert_eq!(setup.escrow.get_aggregate_stats(), stats_before);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&410).status,
+        EscrowStatus::Locked
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&412).status,
+        EscrowStatus::Locked
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+// =============================================================================
+// Release fee tests
+// =============================================================================
+
+#[test]
+fn test_release_deducts_platform_fee() {
+    let setup = TestSetup::new();
+    let collector = Address::generate(&setup.env);
+    setup
+        .escrow
+        .update_fee_config(&None, &Some(250), &Some(collector.clone()), &Some(true));
+
+    let bounty_id = 500_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &10_000, &deadline);
+    setup.escrow.release_funds(&bounty_id, &setup.contributor);
+
+    // 2.5% of 10_000 = 250
+    assert_eq!(setup.token.balance(&collector), 250);
+    assert_eq!(setup.token.balance(&setup.contributor), 9_750);
+    assert_eq!(setup.escrow.get_balance(), 0);
+
+    let released = setup
+        .env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            topics
+                .get(2)
+                .and_then(|t| Symbol::try_from_val(&setup.env, &t).ok())
+                == Some(symbol_short!("release"))
+        })
+        .unwrap();
+    let event = events::FundsReleased::try_from_val(&setup.env, &released.2).unwrap();
+    assert_eq!(event.amount, 9_750);
+    assert_eq!(event.fee, 250);
+}
+
+#[test]
+fn test_release_without_fee_enabled_pays_full_amount() {
+    let setup = TestSetup::new();
+    let collector = Address::generate(&setup.env);
+    setup
+        .escrow
+        .update_fee_config(&None, &Some(250), &Some(collector.clone()), &Some(false));
+
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &501, &10_000, &deadline);
+    setup.escrow.release_funds(&501, &setup.contributor);
+
+    assert_eq!(setup.token.balance(&collector), 0);
+    assert_eq!(setup.token.balance(&setup.contributor), 10_000);
+}
+
+#[test]
+fn test_refund_is_never_charged_a_fee() {
+    let setup = TestSetup::new();
+    let collector = Address::generate(&setup.env);
+    setup
+        .escrow
+        .update_fee_config(&None, &Some(1_000), &Some(collector.clone()), &Some(true));
+
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let before = setup.token.balance(&setup.depositor);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &502, &10_000, &deadline);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&502);
+
+    assert_eq!(setup.token.balance(&collector), 0);
+    assert_eq!(setup.token.balance(&setup.depositor), before);
+}
+
+#[test]
+fn test_split_release_charges_fee_per_leg() {
+    let setup = TestSetup::new();
+    let collector = Address::generate(&setup.env);
+    setup
+        .escrow
+        .update_fee_config(&None, &Some(1_000), &Some(collector.clone()), &Some(true));
+
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &503, &10_000, &deadline);
+    let other = Address::generate(&setup.env);
+    let payouts = vec![
+        &setup.env,
+        (setup.contributor.clone(), 6_000_i128),
+        (other.clone(), 4_000_i128),
+    ];
+    setup.escrow.release_funds_split(&503, &payouts);
+
+    assert_eq!(setup.token.balance(&setup.contributor), 5_400);
+    assert_eq!(setup.token.balance(&other), 3_600);
+    assert_eq!(setup.token.balance(&collector), 1_000);
+}
+
+#[test]
+fn test_fee_rate_above_cap_rejected() {
+    let setup = TestSetup::new();
+    let res = setup
+        .escrow
+        .try_update_fee_config(&None, &Some(10_001), &None, &Some(true));
+    assert_eq!(res, Err(Ok(Error::InvalidFeeRate)));
+}
+
+#[test]
+fn test_set_fee_routes_fee_to_treasury() {
+    let setup = TestSetup::new();
+    let treasury = Address::generate(&setup.env);
+    setup.escrow.set_fee(&setup.admin, &500, &treasury);
+
+    let config = setup.escrow.get_fee_config();
+    assert_eq!(config.release_fee_rate, 500);
+    assert_eq!(config.fee_recipient, treasury);
+    assert!(config.fee_enabled);
+
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &510, &2_000, &deadline);
+    setup.escrow.release_funds(&510, &setup.contributor);
+
+    assert_eq!(setup.token.balance(&treasury), 100);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_900);
+
+    let released = setup
+        .env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            topics
+                .get(2)
+                .and_then(|t| Symbol::try_from_val(&setup.env, &t).ok())
+                == Some(symbol_short!("release"))
+        })
+        .unwrap();
+    let event = events::FundsReleased::try_from_val(&setup.env, &released.2).unwrap();
+    assert_eq!(event.gross, 2_000);
+    assert_eq!(event.fee, 100);
+    assert_eq!(event.amount, 1_900);
+}
+
+#[test]
+fn test_set_fee_rejects_rate_above_cap_and_non_admin() {
+    let setup = TestSetup::new();
+    let treasury = Address::generate(&setup.env);
+
+    let res = setup.escrow.try_set_fee(&setup.admin, &1_001, &treasury);
+    assert_eq!(res, Err(Ok(Error::InvalidFeeRate)));
+    let res = setup.escrow.try_set_fee(&setup.admin, &-1, &treasury);
+    assert_eq!(res, Err(Ok(Error::InvalidFeeRate)));
+
+    let res = setup.escrow.try_set_fee(&setup.depositor, &100, &treasury);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    // Default stays fee-free
+    assert!(!setup.escrow.get_fee_config().fee_enabled);
+}
+
+#[test]
+fn test_fee_stats_accumulate_across_releases() {
+    let setup = TestSetup::new();
+    let treasury = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    assert_eq!(setup.escrow.get_fee_stats().total_collected, 0);
+
+    setup.escrow.set_fee(&setup.admin, &1_000, &treasury);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &511, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &512, &3_000, &deadline);
+    setup.escrow.release_funds(&511, &setup.contributor);
+    setup.escrow.release_funds(&512, &setup.contributor);
+
+    let stats = setup.escrow.get_fee_stats();
+    assert_eq!(stats.total_collected, 400);
+    assert_eq!(stats.collection_count, 2);
+    assert_eq!(setup.token.balance(&treasury), 400);
+}
+
+// =============================================================================
+// Arbiter tests
+// =============================================================================
+
+#[test]
+fn test_lock_funds_with_arbiter_stores_arbiter() {
+    let setup = TestSetup::new();
+    let arbiter = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds_with_arbiter(&setup.depositor, &300, &1_000, &deadline, &arbiter);
+
+    let escrow = setup.escrow.get_escrow_info(&300);
+    assert_eq!(escrow.arbiter, Some(arbiter));
+    assert_eq!(escrow.status, EscrowStatus::Locked);
+
+    // Plain locks carry no arbiter
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &301, &1_000, &deadline);
+    assert_eq!(setup.escrow.get_escrow_info(&301).arbiter, None);
+}
+
+#[test]
+fn test_arbiter_release_requires_arbiter_auth() {
+    let setup = TestSetup::new();
+    let arbiter = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds_with_arbiter(&setup.depositor, &302, &1_000, &deadline, &arbiter);
+
+    setup.escrow.arbiter_release_funds(&302, &setup.contributor);
+
+    let auths = setup.env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, arbiter);
+
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&302).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+fn test_arbiter_refund_before_deadline() {
+    let setup = TestSetup::new();
+    let arbiter = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds_with_arbiter(&setup.depositor, &303, &1_000, &deadline, &arbiter);
+
+    // The depositor cannot refund yet, but the arbiter can
+    let res = setup.escrow.try_refund(&303);
+    assert_eq!(res, Err(Ok(Error::DeadlineNotPassed)));
+
+    let before = setup.token.balance(&setup.depositor);
+    setup.escrow.arbiter_refund(&303);
+
+    let auths = setup.env.auths();
+    assert_eq!(auths[0].0, arbiter);
+    assert_eq!(setup.token.balance(&setup.depositor), before + 1_000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&303).status,
+        EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_admin_release_still_works_with_arbiter() {
+    let setup = TestSetup::new();
+    let arbiter = Address::generate(&setup.env);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds_with_arbiter(&setup.depositor, &304, &1_000, &deadline, &arbiter);
+
+    setup.escrow.release_funds(&304, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+
+    // Once settled, the arbiter has nothing left to resolve
+    let res = setup.escrow.try_arbiter_refund(&304);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+#[test]
+fn test_arbiter_calls_rejected_without_arbiter() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &305, &1_000, &deadline);
+
+    let res = setup
+        .escrow
+        .try_arbiter_release_funds(&305, &setup.contributor);
+    assert_eq!(res, Err(Ok(Error::ArbiterNotSet)));
+    let res = setup.escrow.try_arbiter_refund(&305);
+    assert_eq!(res, Err(Ok(Error::ArbiterNotSet)));
+}
+
+// =============================================================================
+// Cancel tests
+// =============================================================================
+
+#[test]
+fn test_cancel_escrow_refunds_before_deadline() {
+    let setup = TestSetup::new();
+    let bounty_id = 400_u64;
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+    let before = setup.token.balance(&setup.depositor);
+
+    setup.escrow.cancel_escrow(&bounty_id);
+
+    assert_eq!(setup.token.balance(&setup.depositor), before + 1_000);
+    assert_eq!(setup.escrow.get_balance(), 0);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&bounty_id).status,
+        EscrowStatus::Cancelled
+    );
+
+    let history = setup.escrow.get_refund_history(&bounty_id);
+    assert_eq!(history.len(), 1);
+    let record = history.get(0).unwrap();
+    assert_eq!(record.amount, 1_000);
+    assert_eq!(record.recipient, setup.depositor);
+    assert_eq!(record.reason, symbol_short!("cancel"));
+
+    let (_, topics, _) = setup.env.events().all().last().unwrap();
+    let topic = Symbol::try_from_val(&setup.env, &topics.get(2).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("cancel"));
+}
+
+#[test]
+fn test_cancel_escrow_counted_separately_in_stats() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &401, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &402, &500, &deadline);
+
+    setup.escrow.cancel_escrow(&401);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&402);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.count_cancelled, 1);
+    assert_eq!(stats.total_cancelled, 1_000);
+    assert_eq!(stats.count_refunded, 1);
+    assert_eq!(stats.total_refunded, 500);
+    assert_eq!(stats.count_locked, 0);
+}
+
+#[test]
+fn test_cancel_escrow_rejected_with_pending_claim() {
+    let setup = TestSetup::new();
+    let bounty_id = 403_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+    setup.escrow.set_claim_window(&500);
+    setup.escrow.authorize_claim(&bounty_id, &setup.contributor);
+
+    let res = setup.escrow.try_cancel_escrow(&bounty_id);
+    assert_eq!(res, Err(Ok(Error::ContributorAssigned)));
+    assert_eq!(setup.escrow.get_balance(), 1_000);
+}
+
+#[test]
+fn test_cancel_escrow_requires_locked() {
+    let setup = TestSetup::new();
+    let bounty_id = 404_u64;
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+    setup.escrow.release_funds(&bounty_id, &setup.contributor);
+
+    let res = setup.escrow.try_cancel_escrow(&bounty_id);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+#[test]
+fn test_cancel_alias_requires_depositor_and_skips_deadline() {
+    let setup = TestSetup::new();
+    let bounty_id = 405_u64;
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+
+    setup.escrow.cancel(&bounty_id);
+    assert_eq!(setup.env.auths()[0].0, setup.depositor);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&bounty_id).status,
+        EscrowStatus::Cancelled
+    );
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.count_cancelled, 1);
+    assert_eq!(stats.total_cancelled, 1_000);
+    assert_eq!(stats.count_refunded, 0);
+    assert_eq!(stats.total_refunded, 0);
+}
+
+// =============================================================================
+// Single-depositor batch lock tests
+// =============================================================================
+
+#[test]
+fn test_lock_funds_batch_locks_every_bounty() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let items = vec![
+        &setup.env,
+        (600_u64, 1_000_i128, deadline),
+        (601_u64, 2_000_i128, deadline + 10),
+        (602_u64, 3_000_i128, deadline + 20),
+    ];
+
+    let count = setup.escrow.lock_funds_batch(&setup.depositor, &items);
+    assert_eq!(count, 3);
+
+    // One FundsLocked per bounty, plus the batch summary
+    let locked_events = setup
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics
+                .get(2)
+                .and_then(|t| Symbol::try_from_val(&setup.env, &t).ok())
+                == Some(symbol_short!("lock"))
+        })
+        .count();
+    assert_eq!(locked_events, 3);
+
+    assert_eq!(setup.escrow.get_balance(), 6_000);
+    assert_eq!(setup.token.balance(&setup.depositor), 1_000_000 - 6_000);
+    assert_eq!(setup.escrow.get_escrow_info(&601).deadline, deadline + 10);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.count_locked, 3);
+    assert_eq!(stats.total_locked, 6_000);
+    assert_eq!(
+        setup
+            .escrow
+            .query_escrows_by_depositor(&setup.depositor, &0, &10)
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn test_lock_funds_batch_is_atomic() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &610, &100, &deadline);
+
+    // The last item collides with an existing bounty
+    let items = vec![
+        &setup.env,
+        (611_u64, 1_000_i128, deadline),
+        (610_u64, 1_000_i128, deadline),
+    ];
+    let res = setup.escrow.try_lock_funds_batch(&setup.depositor, &items);
+    assert_eq!(res, Err(Ok(Error::BountyAlreadyExists)));
+
+    let repeated = vec![
+        &setup.env,
+        (612_u64, 1_000_i128, deadline),
+        (612_u64, 500_i128, deadline),
+    ];
+    let res = setup
+        .escrow
+        .try_lock_funds_batch(&setup.depositor, &repeated);
+    assert_eq!(res, Err(Ok(Error::DuplicateBountyId)));
+
+    let bad_amount = vec![
+        &setup.env,
+        (613_u64, 1_000_i128, deadline),
+        (614_u64, 0_i128, deadline),
+    ];
+    let res = setup
+        .escrow
+        .try_lock_funds_batch(&setup.depositor, &bad_amount);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    assert_eq!(setup.escrow.get_escrow_count(), 1);
+    assert_eq!(setup.escrow.get_balance(), 100);
+}
+
+#[test]
+fn test_lock_funds_batch_size_limits() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    let empty: Vec<(u64, i128, u64)> = vec![&setup.env];
+    let res = setup.escrow.try_lock_funds_batch(&setup.depositor, &empty);
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+
+    let mut too_many: Vec<(u64, i128, u64)> = vec![&setup.env];
+    for id in 0..(MAX_BATCH_SIZE as u64 + 1) {
+        too_many.push_back((700 + id, 10, deadline));
+    }
+    let res = setup
+        .escrow
+        .try_lock_funds_batch(&setup.depositor, &too_many);
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+}
+
+// =============================================================================
+// Pull claim tests
+// =============================================================================
+
+#[test]
+fn test_authorize_claim_then_claim() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &800, &1_000, &deadline);
+    setup.escrow.set_claim_window(&500);
+
+    setup.escrow.authorize_claim(&800, &setup.contributor);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&800).status,
+        EscrowStatus::PendingClaim
+    );
+    // Nothing has moved yet and the amount still counts as locked
+    assert_eq!(setup.token.balance(&setup.contributor), 0);
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 1_000);
+    assert_eq!(stats.total_released, 0);
+
+    setup.escrow.claim(&800);
+    assert_eq!(setup.env.auths()[0].0, setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&800).status,
+        EscrowStatus::Released
+    );
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 0);
+    assert_eq!(stats.total_released, 1_000);
+}
+
+#[test]
+fn test_pending_claim_blocks_refund_until_window_expires() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &801, &1_000, &deadline);
+    setup.escrow.set_claim_window(&500);
+    setup.escrow.authorize_claim(&801, &setup.contributor);
+
+    // Past the deadline but still inside the claim window
+    setup.env.ledger().set_timestamp(deadline + 1);
+    let (eligibility, _, _, _) = setup.escrow.get_refund_eligibility(&801);
+    assert_eq!(eligibility, RefundEligibility::NotRefundable);
+    let res = setup.escrow.try_refund(&801);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+
+    // Once the window lapses the contributor can no longer claim...
+    setup.env.ledger().set_timestamp(501);
+    let res = setup.escrow.try_claim(&801);
+    assert_eq!(res, Err(Ok(Error::ClaimExpired)));
+
+    // ...and the depositor can refund
+    let (eligibility, _, _, _) = setup.escrow.get_refund_eligibility(&801);
+    assert_eq!(eligibility, RefundEligibility::Eligible);
+    let before = setup.token.balance(&setup.depositor);
+    setup.escrow.refund(&801);
+    assert_eq!(setup.token.balance(&setup.depositor), before + 1_000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&801).status,
+        EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_claim_requires_authorization() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &802, &1_000, &deadline);
+
+    let res = setup.escrow.try_claim(&802);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+
+    setup.escrow.authorize_claim(&802, &setup.contributor);
+    let res = setup.escrow.try_authorize_claim(&802, &setup.contributor);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+    // The pending claim is the only way out of PendingClaim
+    let res = setup.escrow.try_release_funds(&802, &setup.contributor);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+    assert_eq!(setup.token.balance(&setup.escrow.address), 1_000);
+
+    // Cancelling the claim puts the escrow back to Locked
+    setup.escrow.cancel_pending_claim(&802);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&802).status,
+        EscrowStatus::Locked
+    );
+}
+
+#[test]
+fn test_submit_claim_then_depositor_approves() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &803, &1_000, &deadline);
+
+    setup.escrow.submit_claim(&803, &setup.contributor);
+    assert_eq!(setup.env.auths()[0].0, setup.contributor);
+    let submission = setup.escrow.get_claim_submission(&803).unwrap();
+    assert_eq!(submission.contributor, setup.contributor);
+
+    // A second contributor has to wait for the depositor to decide
+    let other = Address::generate(&setup.env);
+    let res = setup.escrow.try_submit_claim(&803, &other);
+    assert_eq!(res, Err(Ok(Error::ClaimAlreadySubmitted)));
+
+    setup.escrow.approve_claim(&803);
+    assert_eq!(setup.env.auths()[0].0, setup.depositor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&803).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(setup.escrow.get_claim_submission(&803), None);
+}
+
+#[test]
+fn test_approve_claim_requires_submission() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &804, &1_000, &deadline);
+
+    let res = setup.escrow.try_approve_claim(&804);
+    assert_eq!(res, Err(Ok(Error::ClaimNotSubmitted)));
+    let res = setup.escrow.try_reject_claim(&804);
+    assert_eq!(res, Err(Ok(Error::ClaimNotSubmitted)));
+
+    // A rejected claim frees the bounty for someone else
+    let other = Address::generate(&setup.env);
+    setup.escrow.submit_claim(&804, &other);
+    setup.escrow.reject_claim(&804);
+    setup.escrow.submit_claim(&804, &setup.contributor);
+    setup.escrow.approve_claim(&804);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(setup.token.balance(&other), 0);
+}
+
+// =============================================================================
+// Partial settlement tests
+// =============================================================================
+
+#[test]
+fn test_refund_partial_splits_between_depositor_and_contributor() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &900, &1_000, &deadline);
+    let depositor_before = setup.token.balance(&setup.depositor);
+
+    setup.escrow.refund_partial(&900, &400, &setup.contributor);
+
+    assert_eq!(
+        setup.token.balance(&setup.depositor),
+        depositor_before + 400
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 600);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 0);
+
+    let escrow = setup.escrow.get_escrow_info(&900);
+    assert_eq!(escrow.status, EscrowStatus::Settled);
+    let history = setup.escrow.get_refund_history(&900);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().amount, 400);
+    assert_eq!(history.get(0).unwrap().mode, RefundMode::Partial);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 0);
+    assert_eq!(stats.total_refunded, 400);
+    assert_eq!(stats.total_released, 600);
+    assert_eq!(stats.count_settled, 1);
+    assert_eq!(stats.count_locked, 0);
+}
+
+#[test]
+fn test_refund_partial_after_partial_release_credits_both_buckets() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &901, &1_000, &deadline);
+    setup.escrow.partial_release(&901, &setup.contributor, &300);
+
+    // 700 remain: 200 back to the depositor, 500 to the contributor
+    setup.escrow.refund_partial(&901, &200, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 800);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_refunded, 200);
+    assert_eq!(stats.total_released, 800);
+}
+
+#[test]
+fn test_refund_partial_rejects_amounts_outside_remaining() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &902, &1_000, &deadline);
+
+    for amount in [0_i128, -1, 1_000, 1_001] {
+        let res = setup
+            .escrow
+            .try_refund_partial(&902, &amount, &setup.contributor);
+        assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+    }
+
+    setup.escrow.release_funds(&902, &setup.contributor);
+    let res = setup
+        .escrow
+        .try_refund_partial(&902, &100, &setup.contributor);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+// =============================================================================
+// Per-bounty token tests
+// =============================================================================
+
+#[test]
+fn test_lock_funds_with_token_routes_release_and_refund() {
+    let setup = TestSetup::new();
+    let (usdc, usdc_admin) = create_token_contract(&setup.env, &setup.admin);
+    usdc_admin.mint(&setup.depositor, &5_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup.escrow.lock_funds_with_token(
+        &setup.depositor,
+        &1000,
+        &2_000,
+        &deadline,
+        &Some(usdc.address.clone()),
+    );
+    setup.escrow.lock_funds_with_token(
+        &setup.depositor,
+        &1001,
+        &1_000,
+        &deadline,
+        &Some(usdc.address.clone()),
+    );
+    assert_eq!(usdc.balance(&setup.escrow.address), 3_000);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 0);
+    assert_eq!(setup.escrow.get_escrow_token(&1000), usdc.address);
+
+    setup.escrow.release_funds(&1000, &setup.contributor);
+    assert_eq!(usdc.balance(&setup.contributor), 2_000);
+    assert_eq!(setup.token.balance(&setup.contributor), 0);
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&1001);
+    assert_eq!(usdc.balance(&setup.depositor), 3_000);
+    assert_eq!(usdc.balance(&setup.escrow.address), 0);
+}
+
+#[test]
+fn test_lock_funds_with_token_none_uses_init_token() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds_with_token(&setup.depositor, &1002, &500, &deadline, &None);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1003, &700, &deadline);
+
+    assert_eq!(setup.escrow.get_escrow_token(&1002), setup.token.address);
+    assert_eq!(setup.escrow.get_escrow_token(&1003), setup.token.address);
+    assert_eq!(setup.token.balance(&setup.escrow.address), 1_200);
+
+    let res = setup.escrow.try_get_escrow_token(&9999);
+    assert_eq!(res, Err(Ok(Error::BountyNotFound)));
+}
+
+#[test]
+fn test_aggregate_stats_for_token_keeps_tokens_apart() {
+    let setup = TestSetup::new();
+    let (usdc, usdc_admin) = create_token_contract(&setup.env, &setup.admin);
+    usdc_admin.mint(&setup.depositor, &5_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1004, &100, &deadline);
+    setup.escrow.lock_funds_with_token(
+        &setup.depositor,
+        &1005,
+        &4_000,
+        &deadline,
+        &Some(usdc.address.clone()),
+    );
+    setup.escrow.release_funds(&1005, &setup.contributor);
+
+    let native = setup
+        .escrow
+        .get_aggregate_stats_for_token(&setup.token.address);
+    assert_eq!(native.total_locked, 100);
+    assert_eq!(native.total_released, 0);
+    assert_eq!(native.count_locked, 1);
+
+    let usdc_stats = setup.escrow.get_aggregate_stats_for_token(&usdc.address);
+    assert_eq!(usdc_stats.total_locked, 0);
+    assert_eq!(usdc_stats.total_released, 4_000);
+    assert_eq!(usdc_stats.count_released, 1);
+
+    let all = setup.escrow.get_aggregate_stats();
+    assert_eq!(all.count_locked + all.count_released, 2);
+}
+
+#[test]
+fn test_top_up_and_cancel_use_escrow_token() {
+    let setup = TestSetup::new();
+    let (usdc, usdc_admin) = create_token_contract(&setup.env, &setup.admin);
+    usdc_admin.mint(&setup.depositor, &5_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let default_before = setup.token.balance(&setup.depositor);
+
+    setup.escrow.lock_funds_with_token(
+        &setup.depositor,
+        &1006,
+        &1_000,
+        &deadline,
+        &Some(usdc.address.clone()),
+    );
+    setup.escrow.top_up(&setup.depositor, &1006, &500);
+    assert_eq!(usdc.balance(&setup.escrow.address), 1_500);
+
+    setup.escrow.cancel_escrow(&1006);
+    assert_eq!(usdc.balance(&setup.depositor), 5_000);
+    assert_eq!(setup.token.balance(&setup.depositor), default_before);
+}
+
+#[test]
+fn test_batch_release_pays_each_escrow_in_its_own_token() {
+    let setup = TestSetup::new();
+    let (usdc, usdc_admin) = create_token_contract(&setup.env, &setup.admin);
+    usdc_admin.mint(&setup.depositor, &5_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1007, &300, &deadline);
+    setup.escrow.lock_funds_with_token(
+        &setup.depositor,
+        &1008,
+        &700,
+        &deadline,
+        &Some(usdc.address.clone()),
+    );
+
+    let items = vec![
+        &setup.env,
+        ReleaseFundsItem {
+            bounty_id: 1007,
+            contributor: setup.contributor.clone(),
+        },
+        ReleaseFundsItem {
+            bounty_id: 1008,
+            contributor: setup.contributor.clone(),
+        },
+    ];
+    setup.escrow.batch_release_funds(&items);
+
+    assert_eq!(setup.token.balance(&setup.contributor), 300);
+    assert_eq!(usdc.balance(&setup.contributor), 700);
+}
+
+// =============================================================================
+// Native XLM escrow tests
+// =============================================================================
+
+fn setup_native(setup: &TestSetup) -> token::Client<'static> {
+    let (xlm, xlm_admin) = create_token_contract(&setup.env, &setup.admin);
+    xlm_admin.mint(&setup.depositor, &10_000);
+    setup.escrow.set_native_token(&xlm.address);
+    xlm
+}
+
+#[test]
+fn test_lock_funds_native_lock_then_release() {
+    let setup = TestSetup::new();
+    let xlm = setup_native(&setup);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds_native(&setup.depositor, &1100, &3_000, &deadline);
+    assert_eq!(xlm.balance(&setup.escrow.address), 3_000);
+    assert_eq!(setup.escrow.get_escrow_asset(&1100), EscrowAsset::Native);
+
+    setup.escrow.release_funds(&1100, &setup.contributor);
+    assert_eq!(xlm.balance(&setup.contributor), 3_000);
+    assert_eq!(xlm.balance(&setup.escrow.address), 0);
+    assert_eq!(
+        setup
+            .escrow
+            .get_aggregate_stats_for_token(&xlm.address)
+            .total_released,
+        3_000
+    );
+}
+
+#[test]
+fn test_lock_funds_native_lock_then_refund() {
+    let setup = TestSetup::new();
+    let xlm = setup_native(&setup);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds_native(&setup.depositor, &1101, &4_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1102, &500, &deadline);
+    assert_eq!(
+        setup.escrow.get_escrow_asset(&1102),
+        EscrowAsset::Token(setup.token.address.clone())
+    );
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&1101);
+    assert_eq!(xlm.balance(&setup.depositor), 10_000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1101).status,
+        EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_lock_funds_native_requires_native_token() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let res = setup
+        .escrow
+        .try_lock_funds_native(&setup.depositor, &1103, &100, &deadline);
+    assert_eq!(res, Err(Ok(Error::NativeTokenNotSet)));
+}
+
+// =============================================================================
+// Co-funding tests
+// =============================================================================
+
+#[test]
+fn test_contribute_tracks_each_funder() {
+    let setup = TestSetup::new();
+    let backer = Address::generate(&setup.env);
+    setup.token_admin.mint(&backer, &5_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1200, &1_000, &deadline);
+    setup.escrow.contribute(&1200, &backer, &3_000);
+    setup.escrow.contribute(&1200, &backer, &1_000);
+    setup.escrow.top_up(&setup.depositor, &1200, &1_000);
+
+    let escrow = setup.escrow.get_escrow_info(&1200);
+    assert_eq!(escrow.amount, 6_000);
+    assert_eq!(escrow.remaining_amount, 6_000);
+
+    let contributions = setup.escrow.get_contributions(&1200);
+    assert_eq!(contributions.len(), 2);
+    assert_eq!(
+        contributions.get(0).unwrap(),
+        (setup.depositor.clone(), 2_000)
+    );
+    assert_eq!(contributions.get(1).unwrap(), (backer.clone(), 4_000));
+
+    // The bounty is listed once for each funder
+    let mine = setup
+        .escrow
+        .query_escrows_by_depositor(&setup.depositor, &0, &10);
+    assert_eq!(mine.len(), 1);
+    let theirs = setup.escrow.query_escrows_by_depositor(&backer, &0, &10);
+    assert_eq!(theirs.len(), 1);
+    assert_eq!(theirs.get(0).unwrap().bounty_id, 1200);
+}
+
+#[test]
+fn test_refund_pays_funders_pro_rata() {
+    let setup = TestSetup::new();
+    let backer = Address::generate(&setup.env);
+    setup.token_admin.mint(&backer, &5_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let depositor_before = setup.token.balance(&setup.depositor);
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1201, &1_000, &deadline);
+    setup.escrow.contribute(&1201, &backer, &2_000);
+    // 1_500 of the 3_000 goes out before the refund
+    setup
+        .escrow
+        .partial_release(&1201, &setup.contributor, &1_501);
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&1201);
+
+    // 1_499 remain: a third to the depositor (plus dust), two thirds to the backer
+    assert_eq!(setup.token.balance(&backer), 5_000 - 2_000 + 999);
+    assert_eq!(
+        setup.token.balance(&setup.depositor),
+        depositor_before - 1_000 + 500
+    );
+    assert_eq!(setup.token.balance(&setup.escrow.address), 0);
+
+    let history = setup.escrow.get_refund_history(&1201);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().recipient, setup.depositor);
+    assert_eq!(history.get(0).unwrap().amount, 500);
+    assert_eq!(history.get(1).unwrap().recipient, backer);
+    assert_eq!(history.get(1).unwrap().amount, 999);
+}
+
+#[test]
+fn test_cancel_returns_every_contribution() {
+    let setup = TestSetup::new();
+    let backer = Address::generate(&setup.env);
+    setup.token_admin.mint(&backer, &5_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1202, &1_000, &deadline);
+    setup.escrow.contribute(&1202, &backer, &4_000);
+    setup.escrow.cancel_escrow(&1202);
+
+    assert_eq!(setup.token.balance(&backer), 5_000);
+    assert_eq!(setup.escrow.get_refund_history(&1202).len(), 2);
+    assert_eq!(setup.escrow.get_aggregate_stats().total_cancelled, 5_000);
+}
+
+#[test]
+fn test_depositor_stats_credit_each_funder() {
+    let setup = TestSetup::new();
+    let backer = Address::generate(&setup.env);
+    setup.token_admin.mint(&backer, &5_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1204, &1_000, &deadline);
+    setup.escrow.contribute(&1204, &backer, &3_000);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1205, &1_000, &deadline);
+    setup.escrow.contribute(&1205, &backer, &1_000);
+
+    let mine = setup.escrow.get_depositor_stats(&setup.depositor);
+    let theirs = setup.escrow.get_depositor_stats(&backer);
+    assert_eq!(mine.total_locked, 2_000);
+    assert_eq!(theirs.total_locked, 4_000);
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&1204);
+    setup.escrow.cancel_escrow(&1205);
+
+    let mine = setup.escrow.get_depositor_stats(&setup.depositor);
+    let theirs = setup.escrow.get_depositor_stats(&backer);
+    assert_eq!(mine.total_refunded, 2_000);
+    assert_eq!(theirs.total_refunded, 4_000);
+}
+
+#[test]
+fn test_contribute_requires_locked_plain_escrow() {
+    let setup = TestSetup::new();
+    let backer = Address::generate(&setup.env);
+    setup.token_admin.mint(&backer, &5_000);
+    let deadline = setup.env.ledger().timestamp() + 1000;
+
+    let res = setup.escrow.try_contribute(&1203, &backer, &100);
+    assert_eq!(res, Err(Ok(Error::BountyNotFound)));
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1203, &1_000, &deadline);
+    let res = setup.escrow.try_contribute(&1203, &backer, &0);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    setup.escrow.release_funds(&1203, &setup.contributor);
+    let res = setup.escrow.try_contribute(&1203, &backer, &100);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+// =============================================================================
+// Contributor assignment tests
+// =============================================================================
+
+#[test]
+fn test_assign_contributor_by_depositor_or_admin() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let stranger = Address::generate(&setup.env);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1300, &1_000, &deadline);
+
+    let res = setup
+        .escrow
+        .try_assign_contributor(&stranger, &1300, &setup.contributor);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &1300, &setup.contributor);
+    assert_eq!(setup.env.auths()[0].0, setup.depositor);
+    assert_eq!(
+        setup.escrow.get_assigned_contributor(&1300),
+        Some(setup.contributor.clone())
+    );
+    let (_, topics, _) = setup.env.events().all().last().unwrap();
+    let topic = Symbol::try_from_val(&setup.env, &topics.get(2).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("assign"));
+
+    let admin = setup.escrow.get_admin();
+    setup.escrow.assign_contributor(&admin, &1300, &stranger);
+    assert_eq!(setup.escrow.get_assigned_contributor(&1300), Some(stranger));
+
+    let assigned = setup
+        .escrow
+        .query_escrows_by_contributor(&setup.contributor, &0, &10);
+    assert_eq!(assigned.len(), 1);
+    assert_eq!(assigned.get(0).unwrap().bounty_id, 1300);
+}
+
+#[test]
+fn test_require_assignment_blocks_release_to_others() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let other = Address::generate(&setup.env);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1301, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1302, &1_000, &deadline);
+    setup.escrow.set_require_assignment(&true);
+
+    // Nobody assigned yet
+    let res = setup.escrow.try_release_funds(&1301, &setup.contributor);
+    assert_eq!(res, Err(Ok(Error::NotAssignedContributor)));
+
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &1301, &setup.contributor);
+    let res = setup.escrow.try_release_funds(&1301, &other);
+    assert_eq!(res, Err(Ok(Error::NotAssignedContributor)));
+    let res = setup.escrow.try_partial_release(&1301, &other, &100);
+    assert_eq!(res, Err(Ok(Error::NotAssignedContributor)));
+
+    setup.escrow.release_funds(&1301, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+
+    // Without the flag the assignment is informational only
+    setup.escrow.set_require_assignment(&false);
+    setup.escrow.release_funds(&1302, &other);
+    assert_eq!(setup.token.balance(&other), 1_000);
+}
+
+#[test]
+fn test_require_assignment_covers_split_releases() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let other = Address::generate(&setup.env);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1305, &1_000, &deadline);
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &1305, &setup.contributor);
+    setup.escrow.set_require_assignment(&true);
+
+    // One leg to someone else is enough to reject the whole split
+    let payouts = vec![
+        &setup.env,
+        (setup.contributor.clone(), 600_i128),
+        (other.clone(), 400_i128),
+    ];
+    let res = setup.escrow.try_release_funds_split(&1305, &payouts);
+    assert_eq!(res, Err(Ok(Error::NotAssignedContributor)));
+    let shares = vec![
+        &setup.env,
+        (setup.contributor.clone(), 6_000_u32),
+        (other, 4_000_u32),
+    ];
+    let res = setup.escrow.try_release_split(&1305, &shares);
+    assert_eq!(res, Err(Ok(Error::NotAssignedContributor)));
+
+    let payouts = vec![&setup.env, (setup.contributor.clone(), 1_000_i128)];
+    setup.escrow.release_funds_split(&1305, &payouts);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+#[test]
+fn test_require_assignment_covers_arbiter_release() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let arbiter = Address::generate(&setup.env);
+    let other = Address::generate(&setup.env);
+    setup
+        .escrow
+        .lock_funds_with_arbiter(&setup.depositor, &1306, &1_000, &deadline, &arbiter);
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &1306, &setup.contributor);
+    setup.escrow.set_require_assignment(&true);
+
+    let res = setup.escrow.try_arbiter_release_funds(&1306, &other);
+    assert_eq!(res, Err(Ok(Error::NotAssignedContributor)));
+    setup
+        .escrow
+        .arbiter_release_funds(&1306, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+#[test]
+fn test_require_assignment_covers_authorize_claim() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let other = Address::generate(&setup.env);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1307, &1_000, &deadline);
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &1307, &setup.contributor);
+    setup.escrow.set_require_assignment(&true);
+
+    let res = setup.escrow.try_authorize_claim(&1307, &other);
+    assert_eq!(res, Err(Ok(Error::NotAssignedContributor)));
+    setup.escrow.authorize_claim(&1307, &setup.contributor);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1307).status,
+        EscrowStatus::PendingClaim
+    );
+}
+
+#[test]
+fn test_require_assignment_covers_release_milestone() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let other = Address::generate(&setup.env);
+    let milestones = vec![&setup.env, (600_i128, deadline), (400_i128, deadline)];
+    setup
+        .escrow
+        .lock_funds_with_milestones(&setup.depositor, &1308, &milestones);
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &1308, &setup.contributor);
+    setup.escrow.set_require_assignment(&true);
+
+    let res = setup.escrow.try_release_milestone(&1308, &0, &other);
+    assert_eq!(res, Err(Ok(Error::NotAssignedContributor)));
+    setup
+        .escrow
+        .release_milestone(&1308, &0, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 600);
+}
+
+#[test]
+fn test_cancel_escrow_rejected_once_assigned() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1303, &1_000, &deadline);
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &1303, &setup.contributor);
+
+    let res = setup.escrow.try_cancel_escrow(&1303);
+    assert_eq!(res, Err(Ok(Error::ContributorAssigned)));
+}
+
+#[test]
+fn test_reassign_and_unassign_contributor() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    let replacement = Address::generate(&setup.env);
+    let stranger = Address::generate(&setup.env);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1304, &1_000, &deadline);
+
+    // Nothing to reassign or unassign yet
+    let res = setup
+        .escrow
+        .try_reassign_contributor(&setup.depositor, &1304, &replacement);
+    assert_eq!(res, Err(Ok(Error::NotAssignedContributor)));
+    let res = setup
+        .escrow
+        .try_unassign_contributor(&setup.depositor, &1304);
+    assert_eq!(res, Err(Ok(Error::NotAssignedContributor)));
+
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &1304, &setup.contributor);
+    let res = setup
+        .escrow
+        .try_reassign_contributor(&stranger, &1304, &replacement);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    setup.env.ledger().set_timestamp(100);
+    setup
+        .escrow
+        .reassign_contributor(&setup.depositor, &1304, &replacement);
+    assert_eq!(
+        setup.escrow.get_assigned_contributor(&1304),
+        Some(replacement.clone())
+    );
+
+    let admin = setup.escrow.get_admin();
+    setup.env.ledger().set_timestamp(200);
+    setup.escrow.unassign_contributor(&admin, &1304);
+    assert_eq!(setup.escrow.get_assigned_contributor(&1304), None);
+    let (_, topics, _) = setup.env.events().all().last().unwrap();
+    let topic = Symbol::try_from_val(&setup.env, &topics.get(2).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("unassign"));
+
+    let history = setup.escrow.get_assignment_history(&1304);
+    assert_eq!(history.len(), 3);
+    assert_eq!(
+        history.get(0).unwrap().contributor,
+        Some(setup.contributor.clone())
+    );
+    let second = history.get(1).unwrap();
+    assert_eq!(second.contributor, Some(replacement));
+    assert_eq!(second.changed_by, setup.depositor);
+    assert_eq!(second.timestamp, 100);
+    let third = history.get(2).unwrap();
+    assert_eq!(third.contributor, None);
+    assert_eq!(third.changed_by, admin);
+    assert_eq!(third.timestamp, 200);
+
+    // Unassigned bounties can be cancelled again
+    setup.escrow.cancel_escrow(&1304);
+}
+
+#[test]
+fn test_unassign_blocks_release_under_require_assignment() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1305, &1_000, &deadline);
+    setup.escrow.set_require_assignment(&true);
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &1305, &setup.contributor);
+    setup.escrow.unassign_contributor(&setup.depositor, &1305);
+
+    let res = setup.escrow.try_release_funds(&1305, &setup.contributor);
+    assert_eq!(res, Err(Ok(Error::NotAssignedContributor)));
+
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &1305, &setup.contributor);
+    setup.escrow.release_funds(&1305, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+
+    // Assignment can no longer change once the escrow is settled
+    let res = setup
+        .escrow
+        .try_unassign_contributor(&setup.depositor, &1305);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+#[test]
+fn test_assignment_history_is_capped() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1306, &1_000, &deadline);
+    let first = Address::generate(&setup.env);
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &1306, &first);
+    for _ in 0..MAX_ASSIGNMENT_HISTORY {
+        let next = Address::generate(&setup.env);
+        setup
+            .escrow
+            .reassign_contributor(&setup.depositor, &1306, &next);
+    }
+
+    let history = setup.escrow.get_assignment_history(&1306);
+    assert_eq!(history.len(), MAX_ASSIGNMENT_HISTORY);
+    assert!(history.iter().all(|r| r.contributor != Some(first.clone())));
+}
+
+// =============================================================================
+// Refund request tests
+// =============================================================================
+
+#[test]
+fn test_request_refund_approved_by_admin() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1400, &1_000, &deadline);
+    let before = setup.token.balance(&setup.depositor);
+
+    setup.escrow.request_refund(&1400, &7);
+    assert_eq!(setup.env.auths()[0].0, setup.depositor);
+    assert_eq!(
+        setup.escrow.get_refund_request(&1400).unwrap().reason_code,
+        7
+    );
+    let (eligibility, _, _, _) = setup.escrow.get_refund_eligibility(&1400);
+    assert_eq!(eligibility, RefundEligibility::PendingApproval);
+
+    setup.escrow.approve_refund_request(&1400);
+    assert_eq!(setup.env.auths()[0].0, setup.escrow.get_admin());
+
+    assert_eq!(setup.token.balance(&setup.depositor), before + 1_000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1400).status,
+        EscrowStatus::Refunded
+    );
+    assert_eq!(setup.escrow.get_refund_request(&1400), None);
+    let history = setup.escrow.get_refund_history(&1400);
+    assert_eq!(history.len(), 1);
+    let record = history.get(0).unwrap();
+    assert_eq!(record.reason, symbol_short!("approved"));
+    assert_eq!(record.reason_code, 7);
+    assert_eq!(record.amount, 1_000);
+}
+
+#[test]
+fn test_reject_refund_request_keeps_escrow_locked() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1401, &1_000, &deadline);
+
+    setup.escrow.request_refund(&1401, &2);
+    let res = setup.escrow.try_request_refund(&1401, &3);
+    assert_eq!(res, Err(Ok(Error::RefundAlreadyRequested)));
+
+    setup.escrow.reject_refund_request(&1401);
+    let (_, topics, _) = setup.env.events().all().last().unwrap();
+    let topic = Symbol::try_from_val(&setup.env, &topics.get(2).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("ref_rej"));
+
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1401).status,
+        EscrowStatus::Locked
+    );
+    assert_eq!(setup.escrow.get_refund_request(&1401), None);
+    let (eligibility, _, _, _) = setup.escrow.get_refund_eligibility(&1401);
+    assert_eq!(eligibility, RefundEligibility::DeadlineNotPassed);
+
+    let res = setup.escrow.try_approve_refund_request(&1401);
+    assert_eq!(res, Err(Ok(Error::RefundNotRequested)));
+    let res = setup.escrow.try_reject_refund_request(&1401);
+    assert_eq!(res, Err(Ok(Error::RefundNotRequested)));
+
+    // The depositor may ask again
+    setup.escrow.request_refund(&1401, &3);
+    setup.escrow.release_funds(&1401, &setup.contributor);
+    let res = setup.escrow.try_approve_refund_request(&1401);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+#[test]
+fn test_request_refund_only_before_deadline() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1402, &1_000, &deadline);
+
+    setup.env.ledger().set_timestamp(deadline);
+    let res = setup.escrow.try_request_refund(&1402, &1);
+    assert_eq!(res, Err(Ok(Error::DeadlinePassed)));
+
+    let res = setup.escrow.try_request_refund(&9999, &1);
+    assert_eq!(res, Err(Ok(Error::BountyNotFound)));
+}
+
+// =============================================================================
+// Keeper refund tests
+// =============================================================================
+
+#[test]
+fn test_refund_expired_processes_up_to_limit() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    for bounty_id in 1500..1504u64 {
+        setup
+            .escrow
+            .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+    }
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1504, &1_000, &(deadline + 1_000));
+    setup.escrow.release_funds(&1503, &setup.contributor);
+
+    // Nothing has expired yet
+    assert_eq!(setup.escrow.refund_expired(&10), 0);
+
+    setup.env.ledger().set_timestamp(deadline);
+    let before = setup.token.balance(&setup.depositor);
+    assert_eq!(setup.escrow.refund_expired(&2), 2);
+    let refund_events = setup
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics
+                .get(2)
+                .and_then(|t| Symbol::try_from_val(&setup.env, &t).ok())
+                == Some(symbol_short!("refund"))
+        })
+        .count();
+    assert_eq!(refund_events, 2);
+
+    assert_eq!(setup.escrow.refund_expired(&10), 1);
+    assert_eq!(setup.escrow.refund_expired(&10), 0);
+    assert_eq!(setup.token.balance(&setup.depositor), before + 3_000);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.count_refunded, 3);
+    assert_eq!(stats.total_refunded, 3_000);
+    assert_eq!(stats.count_released, 1);
+    assert_eq!(stats.count_locked, 1);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1504).status,
+        EscrowStatus::Locked
+    );
+}
+
+#[test]
+fn test_refund_expired_resumes_from_cursor_and_sees_batch_locks() {
+    let setup = TestSetup::new();
+    setup.env.budget().reset_unlimited();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup.token_admin.mint(&setup.depositor, &100_000);
+    for batch in 0..3u64 {
+        let mut items = Vec::new(&setup.env);
+        for i in 0..20u64 {
+            items.push_back(LockFundsItem {
+                bounty_id: 1_600 + batch * 20 + i,
+                depositor: setup.depositor.clone(),
+                amount: 100,
+                deadline,
+            });
+        }
+        setup.escrow.batch_lock_funds(&items);
+    }
+
+    // Batch locks are indexed like single locks
+    assert_eq!(setup.escrow.get_escrow_count(), 60);
+    let page = setup
+        .escrow
+        .query_escrows_by_depositor_paged(&setup.depositor, &0, &100);
+    assert_eq!(page.items.len(), 60);
+
+    // Each call looks at 50 escrows at most, resuming where the last stopped
+    setup.env.ledger().set_timestamp(deadline);
+    assert_eq!(setup.escrow.refund_expired(&5), 5);
+    assert_eq!(setup.escrow.refund_expired(&100), 50);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1_654).status,
+        EscrowStatus::Refunded
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1_655).status,
+        EscrowStatus::Locked
+    );
+    assert_eq!(setup.escrow.refund_expired(&100), 5);
+    assert_eq!(setup.escrow.refund_expired(&100), 0);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.count_refunded, 60);
+    assert_eq!(stats.total_refunded, 6_000);
+    assert_eq!(setup.escrow.get_balance(), 0);
+}
+
+#[test]
+fn test_refund_expired_batch_returns_refunded_ids() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    for bounty_id in 1520..1523u64 {
+        setup
+            .escrow
+            .lock_funds(&setup.depositor, &bounty_id, &1_000, &deadline);
+    }
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1523, &1_000, &(deadline + 1_000));
+    setup.escrow.release_funds(&1521, &setup.contributor);
+
+    setup.env.ledger().set_timestamp(deadline);
+    let before = setup.token.balance(&setup.depositor);
+    let refunded = setup
+        .escrow
+        .refund_expired_batch(&vec![&setup.env, 1520, 1521, 1522, 1523, 9_999]);
+    assert_eq!(refunded, vec![&setup.env, 1520, 1522]);
+    assert_eq!(setup.token.balance(&setup.depositor), before + 2_000);
+
+    // Already refunded ids are skipped on the next call
+    let refunded = setup
+        .escrow
+        .refund_expired_batch(&vec![&setup.env, 1520, 1522]);
+    assert_eq!(refunded.len(), 0);
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.count_refunded, 2);
+    assert_eq!(stats.total_refunded, 2_000);
+    assert_eq!(stats.count_locked, 1);
+
+    let mut too_many = Vec::new(&setup.env);
+    for bounty_id in 0..21u64 {
+        too_many.push_back(bounty_id);
+    }
+    let res = setup.escrow.try_refund_expired_batch(&too_many);
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+}
+
+#[test]
+fn test_refund_expired_skips_disputed_and_respects_pause() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1510, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1511, &1_000, &deadline);
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &1510, &setup.contributor);
+    setup.escrow.open_dispute(&1510);
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.set_paused(&None, &None, &Some(true));
+    let res = setup.escrow.try_refund_expired(&10);
+    assert_eq!(res, Err(Ok(Error::RefundPaused)));
+
+    setup.escrow.set_paused(&None, &None, &Some(false));
+    assert_eq!(setup.escrow.refund_expired(&10), 1);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1510).status,
+        EscrowStatus::Disputed
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1511).status,
+        EscrowStatus::Refunded
+    );
+}
+
+// =============================================================================
+// Expired settlement tests
+// =============================================================================
+
+#[test]
+fn test_settle_expired_splits_by_bps() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1600, &1_000, &deadline);
+    let before = setup.token.balance(&setup.depositor);
+
+    let res = setup
+        .escrow
+        .try_settle_expired(&1600, &setup.contributor, &4_000);
+    assert_eq!(res, Err(Ok(Error::DeadlineNotPassed)));
+
+    setup.env.ledger().set_timestamp(deadline);
+    let res = setup
+        .escrow
+        .try_settle_expired(&1600, &setup.contributor, &10_001);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    setup
+        .escrow
+        .settle_expired(&1600, &setup.contributor, &4_000);
+    assert_eq!(setup.env.auths()[0].0, setup.admin);
+    assert_eq!(setup.token.balance(&setup.contributor), 400);
+    assert_eq!(setup.token.balance(&setup.depositor), before + 600);
+
+    let info = setup.escrow.get_escrow_info(&1600);
+    assert_eq!(info.status, EscrowStatus::Settled);
+    let history = setup.escrow.get_refund_history(&1600);
+    assert_eq!(history.len(), 1);
+    let record = history.get(0).unwrap();
+    assert_eq!(record.amount, 600);
+    assert_eq!(record.mode, RefundMode::Partial);
+    assert_eq!(record.reason, symbol_short!("expired"));
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_released, 400);
+    assert_eq!(stats.total_refunded, 600);
+    assert_eq!(stats.total_locked, 0);
+
+    let res = setup
+        .escrow
+        .try_settle_expired(&1600, &setup.contributor, &4_000);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+#[test]
+fn test_settle_expired_large_amount_does_not_overflow() {
+    let setup = TestSetup::new();
+    let amount = i128::MAX / 4;
+    setup.token_admin.mint(&setup.depositor, &amount);
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1601, &amount, &deadline);
+
+    setup.env.ledger().set_timestamp(deadline);
+    setup
+        .escrow
+        .settle_expired(&1601, &setup.contributor, &4_000);
+    let released = amount / 5 * 2 + amount % 5 * 2 / 5;
+    assert_eq!(setup.token.balance(&setup.contributor), released);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1601).status,
+        EscrowStatus::Settled
+    );
+}
+
+#[test]
+fn test_settle_expired_full_legs() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1601, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1602, &1_000, &deadline);
+    setup.env.ledger().set_timestamp(deadline + 1);
+
+    setup
+        .escrow
+        .settle_expired(&1601, &setup.contributor, &10_000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1601).status,
+        EscrowStatus::Released
+    );
+    setup.escrow.settle_expired(&1602, &setup.contributor, &0);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1602).status,
+        EscrowStatus::Refunded
+    );
+
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_released, 1_000);
+    assert_eq!(stats.total_refunded, 1_000);
+}
+
+#[test]
+fn test_settle_expired_full_release_after_partial_release() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1603, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1604, &500, &deadline);
+    setup
+        .escrow
+        .partial_release(&1603, &setup.contributor, &300);
+    setup.env.ledger().set_timestamp(deadline + 1);
+
+    setup
+        .escrow
+        .settle_expired(&1603, &setup.contributor, &10_000);
+
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    assert_eq!(setup.escrow.get_escrow_info(&1603).remaining_amount, 0);
+    assert_eq!(setup.escrow.get_balance(), 500);
+}
+
+// =============================================================================
+// Emergency refund tests
+// =============================================================================
+
+#[test]
+fn test_emergency_refund_skips_deadline() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1700, &1_000, &deadline);
+    let before = setup.token.balance(&setup.depositor);
+
+    setup.escrow.emergency_refund(&1700);
+    assert_eq!(setup.env.auths()[0].0, setup.admin);
+
+    assert_eq!(setup.token.balance(&setup.depositor), before + 1_000);
+    let info = setup.escrow.get_escrow_info(&1700);
+    assert_eq!(info.status, EscrowStatus::Refunded);
+    let history = setup.escrow.get_refund_history(&1700);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().reason, symbol_short!("emergency"));
+
+    let res = setup.escrow.try_emergency_refund(&1700);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+#[test]
+fn test_emergency_refund_disabled() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1701, &1_000, &deadline);
+
+    setup.escrow.set_emergency_refund_config(&false, &5);
+    let res = setup.escrow.try_emergency_refund(&1701);
+    assert_eq!(res, Err(Ok(Error::EmergencyRefundDisabled)));
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1701).status,
+        EscrowStatus::Locked
+    );
+}
+
+#[test]
+fn test_emergency_refund_daily_limit() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000_000;
+    for id in 1710..1714u64 {
+        setup
+            .escrow
+            .lock_funds(&setup.depositor, &id, &100, &deadline);
+    }
+    setup.escrow.set_emergency_refund_config(&true, &2);
+
+    setup.escrow.emergency_refund(&1710);
+    setup.escrow.emergency_refund(&1711);
+    let res = setup.escrow.try_emergency_refund(&1712);
+    assert_eq!(res, Err(Ok(Error::EmergencyRefundLimitReached)));
+
+    // The count resets with the next ledger-day window
+    let now = setup.env.ledger().timestamp();
+    setup.env.ledger().set_timestamp(now + 86_400);
+    setup.escrow.emergency_refund(&1712);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1712).status,
+        EscrowStatus::Refunded
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1713).status,
+        EscrowStatus::Locked
+    );
+}
+
+#[test]
+fn test_admin_refund_skips_deadline_and_counts_as_refunded() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1720, &1_000, &deadline);
+    let before = setup.token.balance(&setup.depositor);
+
+    setup.escrow.admin_refund(&1720);
+    assert_eq!(setup.env.auths()[0].0, setup.admin);
+    let (_, topics, _) = setup.env.events().all().last().unwrap();
+    let topic = Symbol::try_from_val(&setup.env, &topics.get(2).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("refund"));
+
+    assert_eq!(setup.token.balance(&setup.depositor), before + 1_000);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1720).status,
+        EscrowStatus::Refunded
+    );
+    let history = setup.escrow.get_refund_history(&1720);
+    assert_eq!(history.get(0).unwrap().reason, symbol_short!("admin"));
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_refunded, 1_000);
+    assert_eq!(stats.total_locked, 0);
+
+    let res = setup.escrow.try_admin_refund(&1720);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+// =============================================================================
+// Refund address tests
+// =============================================================================
+
+#[test]
+fn test_refund_goes_to_refund_address() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1800, &1_000, &deadline);
+    let before = setup.token.balance(&setup.depositor);
+    let cold_wallet = Address::generate(&setup.env);
+
+    assert_eq!(setup.escrow.get_refund_address(&1800), setup.depositor);
+    setup.escrow.set_refund_address(&1800, &cold_wallet);
+    assert_eq!(setup.env.auths()[0].0, setup.depositor);
+    assert_eq!(setup.escrow.get_refund_address(&1800), cold_wallet);
+
+    setup.env.ledger().set_timestamp(deadline);
+    setup.escrow.refund(&1800);
+    assert_eq!(setup.token.balance(&cold_wallet), 1_000);
+    assert_eq!(setup.token.balance(&setup.depositor), before);
+
+    let events = setup.env.events().all();
+    let (_, _, data) = events.last().unwrap();
+    let event: events::FundsRefunded =
+        events::FundsRefunded::try_from_val(&setup.env, &data).unwrap();
+    assert_eq!(event.depositor, setup.depositor);
+    assert_eq!(event.refund_to, cold_wallet);
+
+    let record = setup.escrow.get_refund_history(&1800).get(0).unwrap();
+    assert_eq!(record.depositor, setup.depositor);
+    assert_eq!(record.recipient, cold_wallet);
+
+    let res = setup.escrow.try_set_refund_address(&1800, &setup.depositor);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+#[test]
+fn test_refund_address_falls_back_to_depositor() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1801, &1_000, &deadline);
+    let before = setup.token.balance(&setup.depositor);
+
+    setup.env.ledger().set_timestamp(deadline);
+    setup.escrow.refund(&1801);
+    assert_eq!(setup.token.balance(&setup.depositor), before + 1_000);
+    let record = setup.escrow.get_refund_history(&1801).get(0).unwrap();
+    assert_eq!(record.recipient, setup.depositor);
+}
+
+#[test]
+fn test_set_refund_address_after_release_fails() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1802, &1_000, &deadline);
+    setup.escrow.release_funds(&1802, &setup.contributor);
+
+    let res = setup
+        .escrow
+        .try_set_refund_address(&1802, &Address::generate(&setup.env));
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+// =============================================================================
+// Deadline horizon tests
+// =============================================================================
+
+#[test]
+fn test_lock_funds_rejects_past_deadline() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(500);
+
+    let res = setup
+        .escrow
+        .try_lock_funds(&setup.depositor, &1900, &1_000, &400);
+    assert_eq!(res, Err(Ok(Error::DeadlineInPast)));
+    let res = setup
+        .escrow
+        .try_lock_funds(&setup.depositor, &1900, &1_000, &500);
+    assert_eq!(res, Err(Ok(Error::DeadlineInPast)));
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1900, &1_000, &501);
+    assert_eq!(setup.escrow.get_escrow_info(&1900).deadline, 501);
+}
+
+#[test]
+fn test_lock_funds_rejects_deadline_too_far() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(1_000);
+    assert_eq!(setup.escrow.get_deadline_limits(), (1_001, None));
+
+    setup.escrow.set_max_deadline_duration(&86_400);
+    assert_eq!(setup.escrow.get_max_deadline_duration(), 86_400);
+    assert_eq!(setup.escrow.get_deadline_limits(), (1_001, Some(87_400)));
+
+    let res = setup
+        .escrow
+        .try_lock_funds(&setup.depositor, &1901, &1_000, &87_401);
+    assert_eq!(res, Err(Ok(Error::DeadlineTooFar)));
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1901, &1_000, &87_400);
+
+    let items = vec![
+        &setup.env,
+        LockFundsItem {
+            bounty_id: 1902,
+            depositor: setup.depositor.clone(),
+            amount: 100,
+            deadline: 90_000,
+        },
+    ];
+    let res = setup.escrow.try_batch_lock_funds(&items);
+    assert_eq!(res, Err(Ok(Error::DeadlineTooFar)));
+
+    // Removing the limit allows far deadlines again
+    setup.escrow.set_max_deadline_duration(&0);
+    setup.env.ledger().set_timestamp(10_000);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1902, &1_000, &1_000_000_000);
+}
+
+#[test]
+fn test_extend_deadline_rejects_deadline_too_far() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(1_000);
+    setup.escrow.set_max_deadline_duration(&86_400);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1903, &1_000, &2_000);
+
+    let res = setup
+        .escrow
+        .try_extend_deadline(&setup.depositor, &1903, &87_401);
+    assert_eq!(res, Err(Ok(Error::DeadlineTooFar)));
+    assert_eq!(setup.escrow.get_escrow_info(&1903).deadline, 2_000);
+
+    setup
+        .escrow
+        .extend_deadline(&setup.depositor, &1903, &87_400);
+    assert_eq!(setup.escrow.get_escrow_info(&1903).deadline, 87_400);
+}
+
+#[test]
+fn test_refund_waits_for_grace_period() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(1_000);
+    assert_eq!(setup.escrow.get_refund_grace_period(), 0);
+    setup.escrow.set_refund_grace_period(&500);
+    assert_eq!(setup.escrow.get_refund_grace_period(), 500);
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1903, &1_000, &2_000);
+    setup.env.ledger().set_timestamp(1_060);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &1904, &1_000, &2_000);
+
+    // Deadline passed, grace period still running
+    setup.env.ledger().set_timestamp(2_100);
+    let (eligibility, deadline_passed, ..) = setup.escrow.get_refund_eligibility(&1903);
+    assert_eq!(eligibility, RefundEligibility::InGracePeriod);
+    assert!(deadline_passed);
+    assert_eq!(
+        setup.escrow.try_refund(&1903),
+        Err(Ok(Error::DeadlineNotPassed))
+    );
+
+    // The depositor can still pay a late contributor
+    setup.escrow.release_funds(&1904, &setup.contributor);
+
+    setup.env.ledger().set_timestamp(2_500);
+    let (eligibility, ..) = setup.escrow.get_refund_eligibility(&1903);
+    assert_eq!(eligibility, RefundEligibility::Eligible);
+    setup.escrow.refund(&1903);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&1903).status,
+        EscrowStatus::Refunded
+    );
+}
+
+// =============================================================================
+// Depositor allowlist tests
+// =============================================================================
+
+#[test]
+fn test_allowlist_blocks_unlisted_depositors() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    let outsider = Address::generate(&setup.env);
+    setup.token_admin.mint(&outsider, &1_000);
+
+    setup.escrow.set_allowlist_enabled(&true);
+    assert!(setup.escrow.is_allowlist_enabled());
+    let res = setup
+        .escrow
+        .try_lock_funds(&setup.depositor, &2000, &1_000, &deadline);
+    assert_eq!(res, Err(Ok(Error::DepositorNotAllowed)));
+
+    setup.escrow.add_allowed_depositor(&setup.depositor);
+    assert!(setup.escrow.is_allowed_depositor(&setup.depositor));
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2000, &1_000, &deadline);
+
+    let res = setup.escrow.try_contribute(&2000, &outsider, &500);
+    assert_eq!(res, Err(Ok(Error::DepositorNotAllowed)));
+
+    // Turning the mode off lets anyone lock again
+    setup.escrow.set_allowlist_enabled(&false);
+    setup.escrow.contribute(&2000, &outsider, &500);
+}
+
+#[test]
+fn test_allowlist_removal_keeps_existing_escrows_refundable() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup.escrow.set_allowlist_enabled(&true);
+    setup.escrow.add_allowed_depositor(&setup.depositor);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2001, &1_000, &deadline);
+
+    setup.escrow.remove_allowed_depositor(&setup.depositor);
+    assert!(!setup.escrow.is_allowed_depositor(&setup.depositor));
+
+    setup.env.ledger().set_timestamp(deadline);
+    setup.escrow.refund(&2001);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2001).status,
+        EscrowStatus::Refunded
+    );
+    let res = setup
+        .escrow
+        .try_lock_funds(&setup.depositor, &2002, &1_000, &(deadline + 100));
+    assert_eq!(res, Err(Ok(Error::DepositorNotAllowed)));
+}
+
+#[test]
+fn test_get_allowed_depositors_paginates() {
+    let setup = TestSetup::new();
+    let mut added = Vec::new(&setup.env);
+    for _ in 0..5 {
+        let depositor = Address::generate(&setup.env);
+        setup.escrow.add_allowed_depositor(&depositor);
+        added.push_back(depositor);
+    }
+    // Adding the same address again does not duplicate it
+    setup.escrow.add_allowed_depositor(&added.get(0).unwrap());
+
+    let page = setup.escrow.get_allowed_depositors(&0, &2);
+    assert_eq!(page, added.slice(0..2));
+    let page = setup.escrow.get_allowed_depositors(&4, &2);
+    assert_eq!(page, added.slice(4..5));
+    assert_eq!(setup.escrow.get_allowed_depositors(&5, &2).len(), 0);
+
+    setup
+        .escrow
+        .remove_allowed_depositor(&added.get(1).unwrap());
+    assert_eq!(setup.escrow.get_allowed_depositors(&0, &10).len(), 4);
+}
+
+// =============================================================================
+// Per-escrow fee override tests
+// =============================================================================
+
+#[test]
+fn test_lock_funds_with_fee_overrides_global_fee() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    let treasury = Address::generate(&setup.env);
+    setup.escrow.set_fee(&setup.admin, &1_000, &treasury);
+
+    setup
+        .escrow
+        .lock_funds_with_fee(&setup.depositor, &2100, &1_000, &deadline, &250);
+    let auths = setup.env.auths();
+    assert!(auths.iter().any(|(a, _)| *a == setup.admin));
+    assert!(auths.iter().any(|(a, _)| *a == setup.depositor));
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2101, &1_000, &deadline);
+    assert_eq!(setup.escrow.get_fee_override(&2100), Some(250));
+    assert_eq!(setup.escrow.get_fee_override(&2101), None);
+
+    setup.escrow.release_funds(&2100, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 975);
+    assert_eq!(setup.token.balance(&treasury), 25);
+
+    setup.escrow.release_funds(&2101, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 975 + 900);
+    assert_eq!(setup.token.balance(&treasury), 25 + 100);
+}
+
+#[test]
+fn test_fee_override_applies_with_global_fee_disabled() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+
+    let res =
+        setup
+            .escrow
+            .try_lock_funds_with_fee(&setup.depositor, &2102, &1_000, &deadline, &10_001);
+    assert_eq!(res, Err(Ok(Error::InvalidFeeRate)));
+
+    setup
+        .escrow
+        .lock_funds_with_fee(&setup.depositor, &2102, &1_000, &deadline, &0);
+    setup
+        .escrow
+        .lock_funds_with_fee(&setup.depositor, &2103, &1_000, &deadline, &500);
+    setup.escrow.release_funds(&2102, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+
+    // With no fee config the fee goes to the admin
+    setup.escrow.release_funds(&2103, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_950);
+    assert_eq!(setup.token.balance(&setup.admin), 50);
+}
+
+// =============================================================================
+// Contributor blocklist tests
+// =============================================================================
+
+#[test]
+fn test_blocked_contributor_cannot_be_paid() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2200, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2201, &1_000, &deadline);
+    setup.escrow.release_funds(&2201, &setup.contributor);
+
+    setup.escrow.block_contributor(&setup.contributor);
+    assert!(setup.escrow.is_blocked(&setup.contributor));
+    let (_, topics, _) = setup.env.events().all().last().unwrap();
+    let topic: Symbol = Symbol::try_from_val(&setup.env, &topics.get(0).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("c_block"));
+
+    let res = setup.escrow.try_release_funds(&2200, &setup.contributor);
+    assert_eq!(res, Err(Ok(Error::ContributorBlocked)));
+    let res = setup
+        .escrow
+        .try_partial_release(&2200, &setup.contributor, &100);
+    assert_eq!(res, Err(Ok(Error::ContributorBlocked)));
+    let other = Address::generate(&setup.env);
+    let res = setup.escrow.try_release_funds_split(
+        &2200,
+        &vec![
+            &setup.env,
+            (other.clone(), 500_i128),
+            (setup.contributor.clone(), 500_i128),
+        ],
+    );
+    assert_eq!(res, Err(Ok(Error::ContributorBlocked)));
+
+    // The earlier release stands
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2201).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+
+    setup.escrow.unblock_contributor(&setup.contributor);
+    assert!(!setup.escrow.is_blocked(&setup.contributor));
+    let (_, topics, _) = setup.env.events().all().last().unwrap();
+    let topic: Symbol = Symbol::try_from_val(&setup.env, &topics.get(0).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("c_unblock"));
+    setup.escrow.release_funds(&2200, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 2_000);
+}
+
+#[test]
+fn test_blocked_contributor_cannot_claim() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2202, &1_000, &deadline);
+    setup.escrow.set_claim_window(&500);
+    setup.escrow.authorize_claim(&2202, &setup.contributor);
+
+    setup.escrow.block_contributor(&setup.contributor);
+    let res = setup.escrow.try_claim(&2202);
+    assert_eq!(res, Err(Ok(Error::ContributorBlocked)));
+    assert_eq!(setup.token.balance(&setup.contributor), 0);
+}
+
+// ============================================================================
+// Lock rate limit tests
+// ============================================================================
+
+#[test]
+fn test_rate_limit_caps_locks_per_window() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(1_000);
+    let deadline = 100_000;
+    setup.escrow.set_rate_limit(&2, &3_600);
+
+    let status = setup.escrow.get_rate_limit_status(&setup.depositor);
+    assert!(!status.exempt);
+    assert_eq!(status.max_locks, 2);
+    assert_eq!(status.remaining, 2);
+    assert_eq!(status.window_resets_at, 0);
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2300, &1_000, &deadline);
+    let status = setup.escrow.get_rate_limit_status(&setup.depositor);
+    assert_eq!(status.remaining, 1);
+    assert_eq!(status.window_resets_at, 4_600);
+    assert_eq!(status.next_allowed_at, 1_060);
+
+    setup.env.ledger().set_timestamp(1_060);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2301, &1_000, &deadline);
+
+    setup.env.ledger().set_timestamp(1_120);
+    let res = setup
+        .escrow
+        .try_lock_funds(&setup.depositor, &2302, &1_000, &deadline);
+    assert_eq!(res, Err(Ok(Error::RateLimitExceeded)));
+    let status = setup.escrow.get_rate_limit_status(&setup.depositor);
+    assert_eq!(status.remaining, 0);
+    assert_eq!(status.next_allowed_at, 4_600);
+
+    // Quota comes back once the window has passed
+    setup.env.ledger().set_timestamp(4_600);
+    assert_eq!(
+        setup
+            .escrow
+            .get_rate_limit_status(&setup.depositor)
+            .remaining,
+        2
+    );
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2302, &1_000, &deadline);
+}
+
+#[test]
+fn test_rate_limit_exempts_allowlisted_depositors_when_enabled() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(1_000);
+    let deadline = 100_000;
+    setup.escrow.set_rate_limit(&1, &3_600);
+    setup.escrow.add_allowed_depositor(&setup.depositor);
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2310, &1_000, &deadline);
+    setup.env.ledger().set_timestamp(1_100);
+    let res = setup
+        .escrow
+        .try_lock_funds(&setup.depositor, &2311, &1_000, &deadline);
+    assert_eq!(res, Err(Ok(Error::RateLimitExceeded)));
+
+    setup.escrow.set_rate_limit_exempt_privileged(&true);
+    assert!(setup.escrow.get_rate_limit_status(&setup.depositor).exempt);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2311, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2312, &1_000, &deadline);
+
+    let res = setup.escrow.try_set_rate_limit(&0, &3_600);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+}
+
+#[test]
+fn test_rate_limit_applies_to_batch_lock_funds() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(1_000);
+    let deadline = 100_000;
+    setup.escrow.set_rate_limit(&1, &3_600);
+
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2320, &1_000, &deadline);
+
+    setup.env.ledger().set_timestamp(1_100);
+    let items = vec![
+        &setup.env,
+        LockFundsItem {
+            bounty_id: 2321,
+            depositor: setup.depositor.clone(),
+            amount: 1_000,
+            deadline,
+        },
+        LockFundsItem {
+            bounty_id: 2322,
+            depositor: setup.depositor.clone(),
+            amount: 1_000,
+            deadline,
+        },
+    ];
+    let res = setup.escrow.try_batch_lock_funds(&items);
+    assert_eq!(res, Err(Ok(Error::RateLimitExceeded)));
+    assert!(!setup.escrow.escrow_exists(&2321));
+}
+
+// ============================================================================
+// Release timelock tests
+// ============================================================================
+
+#[test]
+fn test_release_above_threshold_waits_for_timelock() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup.escrow.set_release_timelock(&1_000, &3_600);
+    assert_eq!(setup.escrow.get_release_timelock(), (1_000, 3_600));
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2400, &5_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2401, &500, &deadline);
+
+    // At or below the threshold nothing changes
+    setup.escrow.release_funds(&2401, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 500);
+
+    setup.escrow.release_funds(&2400, &setup.contributor);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2400).status,
+        EscrowStatus::PendingRelease
+    );
+    let pending = setup.escrow.get_pending_release(&2400).unwrap();
+    assert_eq!(pending.recipient, setup.contributor);
+    assert_eq!(pending.amount, 5_000);
+    assert_eq!(pending.executable_at, 3_600);
+    assert_eq!(setup.token.balance(&setup.contributor), 500);
+    assert_eq!(setup.escrow.get_aggregate_stats().total_released, 500);
+
+    let res = setup.escrow.try_execute_release(&2400);
+    assert_eq!(res, Err(Ok(Error::ReleaseTimelocked)));
+
+    setup.env.ledger().set_timestamp(3_600);
+    setup.escrow.execute_release(&2400);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2400).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(setup.token.balance(&setup.contributor), 5_500);
+    assert_eq!(setup.escrow.get_aggregate_stats().total_released, 5_500);
+    assert!(setup.escrow.get_pending_release(&2400).is_none());
+}
+
+#[test]
+fn test_depositor_cancels_pending_release_within_window() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup.escrow.set_release_timelock(&1_000, &3_600);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2410, &5_000, &deadline);
+
+    setup.escrow.release_funds(&2410, &setup.contributor);
+    setup.escrow.cancel_pending_release(&2410);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2410).status,
+        EscrowStatus::Locked
+    );
+    assert!(setup.escrow.get_pending_release(&2410).is_none());
+    let res = setup.escrow.try_execute_release(&2410);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+
+    // Too late once the release is executable
+    setup.escrow.release_funds(&2410, &setup.contributor);
+    setup.env.ledger().set_timestamp(3_600);
+    let res = setup.escrow.try_cancel_pending_release(&2410);
+    assert_eq!(res, Err(Ok(Error::ReleaseWindowClosed)));
+    assert_eq!(setup.token.balance(&setup.contributor), 0);
+}
+
+#[test]
+fn test_partial_release_above_threshold_is_timelocked() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup.escrow.set_release_timelock(&1_000, &3_600);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2420, &5_000, &deadline);
+
+    let res = setup
+        .escrow
+        .try_partial_release(&2420, &setup.contributor, &5_000);
+    assert_eq!(res, Err(Ok(Error::ReleaseTimelocked)));
+
+    // Cancelling a queued release does not open a way around the delay
+    setup.escrow.release_funds(&2420, &setup.contributor);
+    setup.escrow.cancel_pending_release(&2420);
+    let res = setup
+        .escrow
+        .try_partial_release(&2420, &setup.contributor, &5_000);
+    assert_eq!(res, Err(Ok(Error::ReleaseTimelocked)));
+
+    setup
+        .escrow
+        .partial_release(&2420, &setup.contributor, &1_000);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+}
+
+#[test]
+fn test_split_releases_above_threshold_are_timelocked() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    let other = Address::generate(&setup.env);
+    setup.escrow.set_release_timelock(&1_000, &3_600);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2421, &5_000, &deadline);
+
+    let payouts = vec![
+        &setup.env,
+        (setup.contributor.clone(), 4_000_i128),
+        (other.clone(), 1_000_i128),
+    ];
+    let res = setup.escrow.try_release_funds_split(&2421, &payouts);
+    assert_eq!(res, Err(Ok(Error::ReleaseTimelocked)));
+    let shares = vec![
+        &setup.env,
+        (setup.contributor.clone(), 8_000_u32),
+        (other, 2_000_u32),
+    ];
+    let res = setup.escrow.try_release_split(&2421, &shares);
+    assert_eq!(res, Err(Ok(Error::ReleaseTimelocked)));
+    assert_eq!(setup.token.balance(&setup.contributor), 0);
+}
+
+#[test]
+fn test_release_milestone_above_threshold_is_timelocked() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup.escrow.set_release_timelock(&1_000, &3_600);
+    let milestones = vec![&setup.env, (4_000_i128, deadline), (500_i128, deadline)];
+    setup
+        .escrow
+        .lock_funds_with_milestones(&setup.depositor, &2422, &milestones);
+
+    let res = setup
+        .escrow
+        .try_release_milestone(&2422, &0, &setup.contributor);
+    assert_eq!(res, Err(Ok(Error::ReleaseTimelocked)));
+    setup
+        .escrow
+        .release_milestone(&2422, &1, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 500);
+}
+
+#[test]
+fn test_authorize_claim_above_threshold_is_timelocked() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup.escrow.set_release_timelock(&1_000, &3_600);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2423, &5_000, &deadline);
+
+    let res = setup.escrow.try_authorize_claim(&2423, &setup.contributor);
+    assert_eq!(res, Err(Ok(Error::ReleaseTimelocked)));
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2423).status,
+        EscrowStatus::Locked
+    );
+}
+
+#[test]
+fn test_batch_release_above_threshold_is_timelocked() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup.escrow.set_release_timelock(&1_000, &3_600);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2424, &500, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2425, &5_000, &deadline);
+
+    let items = vec![
+        &setup.env,
+        ReleaseFundsItem {
+            bounty_id: 2424,
+            contributor: setup.contributor.clone(),
+        },
+        ReleaseFundsItem {
+            bounty_id: 2425,
+            contributor: setup.contributor.clone(),
+        },
+    ];
+    let res = setup.escrow.try_batch_release_funds(&items);
+    assert_eq!(res, Err(Ok(Error::ReleaseTimelocked)));
+    assert_eq!(setup.token.balance(&setup.contributor), 0);
+}
+
+// ============================================================================
+// Vesting tests
+// ============================================================================
+
+#[test]
+fn test_vesting_claims_stream_and_cancel_splits_remainder() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(1_000);
+    setup
+        .escrow
+        .lock_funds_vesting(&setup.depositor, &2500, &1_000, &1_000, &2_000);
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &2500, &setup.contributor);
+
+    setup.env.ledger().set_timestamp(1_250);
+    assert_eq!(setup.escrow.claim_vested(&2500), 250);
+    assert_eq!(setup.token.balance(&setup.contributor), 250);
+    let info = setup.escrow.get_vesting_info(&2500).unwrap();
+    assert_eq!((info.vested, info.claimed, info.claimable), (250, 250, 0));
+    assert_eq!(setup.escrow.get_escrow_info(&2500).remaining_amount, 750);
+    let res = setup.escrow.try_claim_vested(&2500);
+    assert_eq!(res, Err(Ok(Error::NothingToClaim)));
+
+    let stats = setup.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_released, 250);
+    assert_eq!(stats.total_locked, 750);
+
+    let res = setup.escrow.try_release_funds(&2500, &setup.contributor);
+    assert_eq!(res, Err(Ok(Error::VestingEscrow)));
+
+    // Half vested: the unclaimed quarter goes to the contributor, the rest back
+    let before = setup.token.balance(&setup.depositor);
+    setup.env.ledger().set_timestamp(1_500);
+    setup.escrow.cancel_vesting(&2500);
+    assert_eq!(setup.token.balance(&setup.contributor), 500);
+    assert_eq!(setup.token.balance(&setup.depositor), before + 500);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2500).status,
+        EscrowStatus::Settled
+    );
+    assert_eq!(setup.escrow.get_vesting_info(&2500).unwrap().claimable, 0);
+    let res = setup.escrow.try_claim_vested(&2500);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+#[test]
+fn test_vesting_fully_claimed_after_end() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(1_000);
+    setup
+        .escrow
+        .lock_funds_vesting(&setup.depositor, &2510, &900, &1_100, &2_000);
+
+    // Nobody to pay yet
+    setup.env.ledger().set_timestamp(1_500);
+    let res = setup.escrow.try_claim_vested(&2510);
+    assert_eq!(res, Err(Ok(Error::NotAssignedContributor)));
+
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &2510, &setup.contributor);
+    setup.env.ledger().set_timestamp(5_000);
+    assert_eq!(setup.escrow.claim_vested(&2510), 900);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2510).status,
+        EscrowStatus::Released
+    );
+
+    let res = setup.escrow.try_claim_vested(&1);
+    assert_eq!(res, Err(Ok(Error::BountyNotFound)));
+}
+
+#[test]
+fn test_cancel_vesting_refunds_everything_when_assignee_blocked() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(1_000);
+    let before = setup.token.balance(&setup.depositor);
+    setup
+        .escrow
+        .lock_funds_vesting(&setup.depositor, &2512, &1_000, &1_000, &2_000);
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &2512, &setup.contributor);
+    setup.escrow.block_contributor(&setup.contributor);
+
+    // Half has vested, but nothing can be paid to a blocked contributor
+    setup.env.ledger().set_timestamp(1_500);
+    setup.escrow.cancel_vesting(&2512);
+    assert_eq!(setup.token.balance(&setup.contributor), 0);
+    assert_eq!(setup.token.balance(&setup.depositor), before);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2512).status,
+        EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_admin_refunds_reject_vesting_escrows() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(1_000);
+    setup
+        .escrow
+        .lock_funds_vesting(&setup.depositor, &2513, &1_000, &1_000, &2_000);
+
+    setup.env.ledger().set_timestamp(1_500);
+    let res = setup.escrow.try_admin_refund(&2513);
+    assert_eq!(res, Err(Ok(Error::VestingEscrow)));
+    let res = setup.escrow.try_emergency_refund(&2513);
+    assert_eq!(res, Err(Ok(Error::VestingEscrow)));
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2513).status,
+        EscrowStatus::Locked
+    );
+}
+
+#[test]
+fn test_vesting_large_amount_does_not_overflow() {
+    let setup = TestSetup::new();
+    let amount = i128::MAX / 4;
+    setup.token_admin.mint(&setup.depositor, &amount);
+    setup.env.ledger().set_timestamp(1_000);
+    setup
+        .escrow
+        .lock_funds_vesting(&setup.depositor, &2515, &amount, &1_000, &1_001_000);
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &2515, &setup.contributor);
+
+    // amount * elapsed is far beyond i128 here
+    setup.env.ledger().set_timestamp(251_000);
+    assert_eq!(setup.escrow.claim_vested(&2515), amount / 4);
+    let info = setup.escrow.get_vesting_info(&2515).unwrap();
+    assert_eq!(info.vested, amount / 4);
+
+    setup.env.ledger().set_timestamp(751_000);
+    setup.escrow.cancel_vesting(&2515);
+    let vested = amount / 4 * 3 + amount % 4 * 3 / 4;
+    assert_eq!(setup.token.balance(&setup.contributor), vested);
+    assert_eq!(
+        setup.token.balance(&setup.depositor),
+        1_000_000 + amount - vested
+    );
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2515).status,
+        EscrowStatus::Settled
+    );
+}
+
+#[test]
+fn test_cancel_vesting_without_assignee_refunds_everything() {
+    let setup = TestSetup::new();
+    setup.env.ledger().set_timestamp(1_000);
+    let before = setup.token.balance(&setup.depositor);
+    setup
+        .escrow
+        .lock_funds_vesting(&setup.depositor, &2520, &1_000, &1_000, &2_000);
+    let res = setup
+        .escrow
+        .try_lock_funds_vesting(&setup.depositor, &2521, &1_000, &2_000, &2_000);
+    assert_eq!(res, Err(Ok(Error::InvalidDeadline)));
+
+    setup.env.ledger().set_timestamp(1_500);
+    setup.escrow.cancel_vesting(&2520);
+    assert_eq!(setup.token.balance(&setup.depositor), before);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2520).status,
+        EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_lock_funds_with_memo_is_returned_by_escrow_info() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    let memo = Symbol::new(&setup.env, "grainlify_issue_4217");
+    setup.escrow.lock_funds_with_memo(
+        &setup.depositor,
+        &2600,
+        &1_000,
+        &deadline,
+        &Some(memo.clone()),
+    );
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2601, &1_000, &deadline);
+
+    assert_eq!(setup.escrow.get_escrow_info(&2600).memo, Some(memo.clone()));
+    assert_eq!(setup.escrow.get_escrow_info(&2601).memo, None);
+
+    // Unchanged by later state transitions
+    setup
+        .escrow
+        .partial_release(&2600, &setup.contributor, &400);
+    assert_eq!(setup.escrow.get_escrow_info(&2600).memo, Some(memo));
+}
+
+// =============================================================================
+// Contributor earnings tests
+// =============================================================================
+
+#[test]
+fn test_contributor_earnings_credit_each_split_share() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2000, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2001, &1_000, &deadline);
+
+    setup.env.ledger().set_timestamp(50);
+    setup
+        .escrow
+        .partial_release(&2000, &setup.contributor, &200);
+    let second = Address::generate(&setup.env);
+    let payouts = vec![
+        &setup.env,
+        (setup.contributor.clone(), 500_i128),
+        (second.clone(), 300_i128),
+    ];
+    setup.escrow.release_funds_split(&2000, &payouts);
+    setup.env.ledger().set_timestamp(80);
+    setup.escrow.release_funds(&2001, &setup.contributor);
+
+    // Two payouts from 2000 still count as one bounty
+    let earnings = setup.escrow.get_contributor_earnings(&setup.contributor);
+    assert_eq!(earnings.total_earned, 1_700);
+    assert_eq!(earnings.bounty_count, 2);
+    assert_eq!(earnings.last_payout_at, 80);
+
+    let earnings = setup.escrow.get_contributor_earnings(&second);
+    assert_eq!(earnings.total_earned, 300);
+    assert_eq!(earnings.bounty_count, 1);
+    assert_eq!(earnings.last_payout_at, 50);
+
+    let top = setup.escrow.get_top_contributors(&10);
+    assert_eq!(
+        top,
+        vec![
+            &setup.env,
+            (setup.contributor.clone(), 1_700_i128),
+            (second, 300_i128)
+        ]
+    );
+    assert_eq!(setup.escrow.get_top_contributors(&1).len(), 1);
+}
+
+#[test]
+fn test_top_contributors_is_bounded() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2002, &100_000, &deadline);
+
+    // Contributor i earns 100 * (i + 1), so the last ones paid are the top
+    let count = MAX_TOP_CONTRIBUTORS + 2;
+    let mut contributors = Vec::new(&setup.env);
+    for i in 0..count {
+        let contributor = Address::generate(&setup.env);
+        setup
+            .escrow
+            .partial_release(&2002, &contributor, &(100 * (i as i128 + 1)));
+        contributors.push_back(contributor);
+    }
+
+    let top = setup.escrow.get_top_contributors(&u32::MAX);
+    assert_eq!(top.len(), MAX_TOP_CONTRIBUTORS);
+    assert_eq!(
+        top.get(0).unwrap(),
+        (contributors.get(count - 1).unwrap(), 100 * count as i128)
+    );
+    assert_eq!(top.get(MAX_TOP_CONTRIBUTORS - 1).unwrap().1, 300);
+
+    // A low earner climbs back onto the board once they pass the last entry
+    let first = contributors.get(0).unwrap();
+    setup.escrow.partial_release(&2002, &first, &5_000);
+    let top = setup.escrow.get_top_contributors(&1);
+    assert_eq!(top.get(0).unwrap(), (first, 5_100_i128));
+    assert_eq!(
+        setup.escrow.get_top_contributors(&u32::MAX).len(),
+        MAX_TOP_CONTRIBUTORS
+    );
+}
+
+// =============================================================================
+// Escrow history tests
+// =============================================================================
+
+#[test]
+fn test_escrow_history_records_lifecycle_in_order() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2100, &1_000, &deadline);
+    setup.env.ledger().set_timestamp(50);
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &2100, &setup.contributor);
+    setup.env.ledger().set_timestamp(80);
+    setup.escrow.release_funds(&2100, &setup.contributor);
+
+    let history = setup.escrow.get_escrow_history(&2100, &0, &10);
+    assert_eq!(history.len(), 3);
+    assert_eq!(
+        history.get(0).unwrap(),
+        (0, EscrowAction::Locked, setup.depositor.clone(), 1_000)
+    );
+    assert_eq!(
+        history.get(1).unwrap(),
+        (50, EscrowAction::Assigned, setup.depositor.clone(), 0)
+    );
+    assert_eq!(
+        history.get(2).unwrap(),
+        (80, EscrowAction::Released, setup.contributor.clone(), 1_000)
+    );
+
+    let page = setup.escrow.get_escrow_history(&2100, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().1, EscrowAction::Assigned);
+    assert_eq!(setup.escrow.get_escrow_history(&9999, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_escrow_history_cap_only_blocks_non_financial_actions() {
+    let setup = TestSetup::new();
+    setup.env.budget().reset_unlimited();
+    let deadline = setup.env.ledger().timestamp() + 1000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2101, &1_000, &deadline);
+    // Locked + 199 assignment changes, ending assigned
+    for i in 1..MAX_ESCROW_HISTORY {
+        if i % 2 == 1 {
+            setup
+                .escrow
+                .assign_contributor(&setup.depositor, &2101, &setup.contributor);
+        } else {
+            setup.escrow.unassign_contributor(&setup.depositor, &2101);
+        }
+    }
+    let last = setup
+        .escrow
+        .get_escrow_history(&2101, &(MAX_ESCROW_HISTORY - 1), &10);
+    assert_eq!(last.len(), 1);
+
+    let res = setup
+        .escrow
+        .try_unassign_contributor(&setup.depositor, &2101);
+    assert_eq!(res, Err(Ok(Error::LimitExceeded)));
+    let res = setup
+        .escrow
+        .try_extend_deadline(&setup.depositor, &2101, &(deadline + 1));
+    assert_eq!(res, Err(Ok(Error::LimitExceeded)));
+
+    // Funds still move once the history is full
+    setup
+        .escrow
+        .partial_release(&2101, &setup.contributor, &100);
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&2101);
+    assert_eq!(setup.token.balance(&setup.contributor), 100);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2101).status,
+        EscrowStatus::Refunded
+    );
+    let history = setup
+        .escrow
+        .get_escrow_history(&2101, &MAX_ESCROW_HISTORY, &10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().1, EscrowAction::Released);
+    assert_eq!(history.get(1).unwrap().1, EscrowAction::Refunded);
+}
+
+// =============================================================================
+// Pre-flight lock check tests
+// =============================================================================
+
+#[test]
+fn test_can_lock_checks_depositor_balance() {
+    let setup = TestSetup::new();
+    let balance = setup.token.balance(&setup.depositor);
+
+    assert!(setup.escrow.can_lock(&setup.depositor, &balance));
+    assert!(!setup.escrow.can_lock(&setup.depositor, &(balance + 1)));
+    assert!(!setup.escrow.can_lock(&setup.depositor, &0));
+    assert!(!setup.escrow.can_lock(&Address::generate(&setup.env), &1));
+
+    // A read only: nothing moved and nothing was authorized
+    assert_eq!(setup.token.balance(&setup.depositor), balance);
+    assert_eq!(setup.env.auths().len(), 0);
+    assert_eq!(setup.escrow.get_escrow_count(), 0);
+}
+
+// =============================================================================
+// Release reason tests
+// =============================================================================
+
+#[test]
+fn test_release_reason_is_stored_and_emitted() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2700, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2701, &1_000, &deadline);
+    assert_eq!(setup.escrow.get_escrow_info(&2700).release_reason, None);
+
+    setup.escrow.release_funds_with_reason(
+        &2700,
+        &setup.contributor,
+        &Some(symbol_short!("early")),
+    );
+    let info = setup.escrow.get_escrow_info(&2700);
+    assert_eq!(info.status, EscrowStatus::Released);
+    assert_eq!(info.release_reason, Some(symbol_short!("early")));
+
+    let (_, topics, data) = setup.env.events().all().last().unwrap();
+    let action = Symbol::try_from_val(&setup.env, &topics.get(2).unwrap()).unwrap();
+    assert_eq!(action, symbol_short!("release"));
+    let event = events::FundsReleased::try_from_val(&setup.env, &data).unwrap();
+    assert_eq!(event.reason, symbol_short!("early"));
+
+    // Without a reason the generic code is recorded
+    setup.escrow.release_funds(&2701, &setup.contributor);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2701).release_reason,
+        Some(symbol_short!("release"))
+    );
+}
+
+#[test]
+fn test_release_reason_survives_timelock() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 10_000;
+    setup.escrow.set_release_timelock(&1_000, &3_600);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2702, &5_000, &deadline);
+
+    setup.escrow.release_funds_with_reason(
+        &2702,
+        &setup.contributor,
+        &Some(symbol_short!("completed")),
+    );
+    let pending = setup.escrow.get_pending_release(&2702).unwrap();
+    assert_eq!(pending.reason, symbol_short!("completed"));
+    assert_eq!(setup.escrow.get_escrow_info(&2702).release_reason, None);
+
+    setup.env.ledger().set_timestamp(3_600);
+    setup.escrow.execute_release(&2702);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&2702).release_reason,
+        Some(symbol_short!("completed"))
+    );
+}
+
+// =============================================================================
+// Full release after partial release tests
+// =============================================================================
+
+#[test]
+fn test_claim_after_partial_release_pays_only_remaining() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2800, &1_000, &deadline);
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &2801, &500, &deadline);
+
+    setup
+        .escrow
+        .partial_release(&2800, &setup.contributor, &300);
+    setup.escrow.authorize_claim(&2800, &setup.contributor);
+    setup.escrow.claim(&2800);
+
+    assert_eq!(setup.token.balance(&setup.contributor), 1_000);
+    let info = setup.escrow.get_escrow_info(&2800);
+    assert_eq!(info.status, EscrowStatus::Released);
+    assert_eq!(info.remaining_amount, 0);
+
+    // The other bounty's funds are still fully backed
+    assert_eq!(setup.escrow.get_balance(), 500);
+    assert_eq!(setup.escrow.get_escrow_info(&2801).remaining_amount, 500);
+}
+
+#[test]
+fn test_release_and_batch_release_after_partial_release() {
+    let setup = TestSetup::new();
+    let deadline = setup.env.ledger().timestamp() + 1_000;
+    for id in [2802_u64, 2803, 2804] {
+        setup
+            .escrow
+            .lock_funds(&setup.depositor, &id, &1_000, &deadline);
+        setup.escrow.partial_release(&id, &setup.contributor, &400);
+    }
+
+    setup.escrow.release_funds(&2802, &setup.contributor);
+    assert_eq!(setup.token.balance(&setup.contributor), 1_800);
+
+    let items = vec![
+        &setup.env,
+        ReleaseFundsItem {
+            bounty_id: 2803,
+            contributor: setup.contributor.clone(),
+        },
+    ];
+    setup.escrow.batch_release_funds(&items);
+    assert_eq!(setup.token.balance(&setup.contributor), 2_400);
+    assert_eq!(setup.escrow.get_balance(), 600);
+}
+
+// =============================================================================
+// Vesting guard on release paths
+// =============================================================================
+
+/// Locks a vesting escrow of 1000 over [1000, 2000], assigns the contributor
+/// and claims the first half at the midpoint.
+fn setup_half_claimed_vesting(setup: &TestSetup, bounty_id: u64) {
+    setup.env.ledger().set_timestamp(1_000);
+    setup
+        .escrow
+        .lock_funds_vesting(&setup.depositor, &bounty_id, &1_000, &1_000, &2_000);
+    setup
+        .escrow
+        .assign_contributor(&setup.depositor, &bounty_id, &setup.contributor);
+    setup.env.ledger().set_timestamp(1_500);
+    assert_eq!(setup.escrow.claim_vested(&bounty_id), 500);
+}
+
+#[test]
+fn test_vesting_rejects_authorize_claim() {
+    let setup = TestSetup::new();
+    setup_half_claimed_vesting(&setup, 2900);
+
+    let res = setup.escrow.try_authorize_claim(&2900, &setup.contributor);
+    assert_eq!(res, Err(Ok(Error::VestingEscrow)));
+
+    let info = setup.escrow.get_escrow_info(&2900);
+    assert_eq!(info.status, EscrowStatus::Locked);
+    assert_eq!(info.remaining_amount, 500);
+    assert_eq!(setup.token.balance(&setup.contributor), 500);
+}
+
+#[test]
+fn test_vesting_rejects_split_releases() {
+    let setup = TestSetup::new();
+    setup_half_claimed_vesting(&setup, 2901);
+
+    let payouts = vec![&setup.env, (setup.contributor.clone(), 500_i128)];
+    let res = setup.escrow.try_release_funds_split(&2901, &payouts);
+    assert_eq!(res, Err(Ok(Error::VestingEscrow)));
+
+    let shares = vec![&setup.env, (setup.contributor.clone(), 10_000_u32)];
+    let res = setup.escrow.try_release_split(&2901, &shares);
+    assert_eq!(res, Err(Ok(Error::VestingEscrow)));
+    assert_eq!(setup.token.balance(&setup.contributor), 500);
+}
+
+#[test]
+fn test_vesting_rejects_batch_release() {
+    let setup = TestSetup::new();
+    setup_half_claimed_vesting(&setup, 2902);
+
+    let items = vec![
+        &setup.env,
+        ReleaseFundsItem {
+            bounty_id: 2902,
+            contributor: setup.contributor.clone(),
+        },
+    ];
+    let res = setup.escrow.try_batch_release_funds(&items);
+    assert_eq!(res, Err(Ok(Error::VestingEscrow)));
+    assert_eq!(setup.token.balance(&setup.contributor), 500);
+}
+
+#[test]
+fn test_vesting_rejects_dispute_release_ruling() {
+    let setup = TestSetup::new();
+    setup_half_claimed_vesting(&setup, 2903);
+    setup.escrow.set_arbiter(&Address::generate(&setup.env));
+
+    setup.escrow.open_dispute(&2903);
+    let res = setup
+        .escrow
+        .try_resolve_dispute(&2903, &DisputeRuling::Release);
+    assert_eq!(res, Err(Ok(Error::VestingEscrow)));
+    assert_eq!(setup.token.balance(&setup.contributor), 500);
+
+    // Refunding the unvested remainder is still possible
+    let before = setup.token.balance(&setup.depositor);
+    setup.escrow.resolve_dispute(&2903, &DisputeRuling::Refund);
+    assert_eq!(setup.token.balance(&setup.depositor), before + 500);
+}
@@ -17,12 +17,28 @@
 /// * `get_escrow_ids_by_status` – ID-only view mirrors full-object equivalent
 /// * `get_refund_eligibility` – eligibility flags flip correctly across lifecycle
 /// * `get_refund_history`    – history vector is populated by approved-refund path
+/// * `get_refund_history_paged` – refunds page across escrows and carry their reason
 /// * Monitoring event emission – lock/release/refund each emit ≥ 1 event
 /// * Error flows             – failed attempts do not corrupt metrics
-use crate::{BountyEscrowContract, BountyEscrowContractClient, EscrowStatus, RefundMode};
+/// * Granular pause         – a paused path leaves the other paths' metrics intact
+/// * `get_stats_in_window`   – activity is bucketed by its own timestamp
+/// * Overflow              – totals past i128::MAX return ArithmeticOverflow
+/// * Projects              – per-project stats and queries stay separate
+/// * `get_depositor_stats`  – running totals stay scoped to one depositor
+/// * `get_daily_stats`      – activity lands in the bucket of its own day
+/// * `get_balance_reconciliation` – expected holdings match each token's balance
+/// * `get_health_check`     – one solvency entry per token in use
+/// * `get_extended_stats`   – extremes follow releases; unique addresses count once
+/// * `get_error_stats`      – skipped keeper refunds are counted per category
+/// * Running stats          – stored totals and counters at their max fail with ArithmeticOverflow
+use crate::{
+    BountyEscrowContract, BountyEscrowContractClient, DataKey, Error, EscrowStatus,
+    RefundEligibility, RefundMode, RefundReason,
+};
 use soroban_sdk::{
+    symbol_short,
     testutils::{Address as _, Events, Ledger},
-    token, Address, Env,
+    token, vec, Address, Env, Symbol, TryFromVal, Vec,
 };
 
 // ---------------------------------------------------------------------------
@@ -685,9 +701,13 @@ fn test_refund_eligibility_false_before_deadline() {
     let deadline = env.ledger().timestamp() + 2000;
     escrow.lock_funds(&depositor, &180, &1_000, &deadline);
 
-    let (can_refund, deadline_passed, remaining, approval) = escrow.get_refund_eligibility(&180);
+    let (eligibility, deadline_passed, remaining, approval) = escrow.get_refund_eligibility(&180);
 
-    assert!(!can_refund, "should not be eligible before deadline");
+    assert_eq!(
+        eligibility,
+        RefundEligibility::DeadlineNotPassed,
+        "should not be eligible before deadline"
+    );
     assert!(!deadline_passed);
     assert_eq!(remaining, 1_000);
     assert!(approval.is_none());
@@ -708,9 +728,13 @@ fn test_refund_eligibility_true_after_deadline_passes() {
     escrow.lock_funds(&depositor, &181, &1_000, &deadline);
     env.ledger().set_timestamp(deadline + 1);
 
-    let (can_refund, deadline_passed, remaining, approval) = escrow.get_refund_eligibility(&181);
+    let (eligibility, deadline_passed, remaining, approval) = escrow.get_refund_eligibility(&181);
 
-    assert!(can_refund, "should be eligible after deadline");
+    assert_eq!(
+        eligibility,
+        RefundEligibility::Eligible,
+        "should be eligible after deadline"
+    );
     assert!(deadline_passed);
     assert_eq!(remaining, 1_000);
     assert!(approval.is_none());
@@ -733,13 +757,18 @@ fn test_refund_eligibility_false_after_release() {
     escrow.release_funds(&182, &contributor);
 
     // After release the status is Released, so can_refund must be false
-    let (can_refund, _deadline_passed, _remaining, _approval) = escrow.get_refund_eligibility(&182);
+    let (eligibility, _deadline_passed, _remaining, _approval) =
+        escrow.get_refund_eligibility(&182);
 
-    assert!(!can_refund, "released escrow should not be refund-eligible");
+    assert_eq!(
+        eligibility,
+        RefundEligibility::NotRefundable,
+        "released escrow should not be refund-eligible"
+    );
 }
 
 #[test]
-fn test_refund_eligibility_true_with_admin_approval_before_deadline() {
+fn test_refund_eligibility_ignores_admin_approval_before_deadline() {
     let env = Env::default();
     env.mock_all_auths();
     let admin = Address::generate(&env);
@@ -755,15 +784,93 @@ fn test_refund_eligibility_true_with_admin_approval_before_deadline() {
     // Admin approves a partial refund before the deadline
     escrow.approve_refund(&183, &500, &depositor, &RefundMode::Partial);
 
-    let (can_refund, deadline_passed, remaining, approval) = escrow.get_refund_eligibility(&183);
+    let (eligibility, deadline_passed, remaining, approval) = escrow.get_refund_eligibility(&183);
 
-    // Approval present → eligible even before deadline
-    assert!(can_refund, "should be eligible with admin approval");
+    // `refund` does not act on approvals, so the escrow is not eligible yet
+    assert_eq!(eligibility, RefundEligibility::DeadlineNotPassed);
+    assert_eq!(escrow.try_refund(&183), Err(Ok(Error::DeadlineNotPassed)));
+    assert!(escrow.get_all_refund_eligible_ids(&10).is_empty());
     assert!(!deadline_passed, "deadline hasn't passed yet");
     assert_eq!(remaining, 1_000);
     assert!(approval.is_some());
 }
 
+#[test]
+fn test_refund_eligibility_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    let now = env.ledger().timestamp();
+    escrow.lock_funds(&depositor, &184, &1_000, &(now + 100));
+    escrow.lock_funds(&depositor, &185, &1_000, &(now + 5_000));
+    env.ledger().set_timestamp(now + 100);
+
+    let results = escrow.get_refund_eligibility_batch(&vec![&env, 184, 185, 999]);
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        results.get(0).unwrap(),
+        (184, true, RefundEligibility::Eligible)
+    );
+    assert_eq!(
+        results.get(1).unwrap(),
+        (185, false, RefundEligibility::DeadlineNotPassed)
+    );
+    // Unknown ids are reported apart from ineligible ones
+    assert_eq!(
+        results.get(2).unwrap(),
+        (999, false, RefundEligibility::NotFound)
+    );
+
+    let mut too_many = Vec::new(&env);
+    for id in 0..51u64 {
+        too_many.push_back(id);
+    }
+    assert_eq!(
+        escrow.try_get_refund_eligibility_batch(&too_many),
+        Err(Ok(Error::InvalidBatchSize))
+    );
+}
+
+#[test]
+fn test_all_refund_eligible_ids_scans_every_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    let now = env.ledger().timestamp();
+    escrow.lock_funds(&depositor, &186, &1_000, &(now + 100));
+    escrow.lock_funds(&depositor, &187, &1_000, &(now + 5_000));
+    escrow.lock_funds(&depositor, &188, &1_000, &(now + 50));
+    escrow.lock_funds(&depositor, &189, &1_000, &(now + 50));
+    escrow.release_funds(&189, &contributor);
+    assert_eq!(escrow.get_all_refund_eligible_ids(&10).len(), 0);
+
+    env.ledger().set_timestamp(now + 100);
+    assert_eq!(
+        escrow.get_all_refund_eligible_ids(&10),
+        vec![&env, 186, 188]
+    );
+    assert_eq!(escrow.get_all_refund_eligible_ids(&1), vec![&env, 186]);
+
+    let results = escrow.get_refund_eligibility_batch(&vec![&env, 189]);
+    assert_eq!(
+        results.get(0).unwrap(),
+        (189, false, RefundEligibility::NotRefundable)
+    );
+}
+
 // ===========================================================================
 // 11. Refund history analytics view
 // ===========================================================================
@@ -803,6 +910,67 @@ fn test_refund_history_panics_for_nonexistent_bounty() {
     escrow.get_refund_history(&999_u64);
 }
 
+#[test]
+fn test_refund_history_pages_with_reasons() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&alice, &1_000_000);
+    token_admin.mint(&bob, &1_000_000);
+
+    let deadline = env.ledger().timestamp() + 500;
+    escrow.lock_funds(&alice, &191, &100, &deadline);
+    escrow.lock_funds(&alice, &192, &200, &deadline);
+    escrow.lock_funds(&bob, &193, &300, &deadline);
+    escrow.lock_funds(&alice, &194, &400, &deadline);
+    escrow.lock_funds(&bob, &195, &500, &deadline);
+
+    escrow.emergency_refund(&192);
+    escrow.cancel_escrow(&193);
+    escrow.admin_refund(&195);
+    env.ledger().set_timestamp(deadline);
+    escrow.refund(&191);
+
+    let all = escrow.get_refund_history_paged(&0, &10);
+    assert_eq!(all.len(), 4);
+    let reasons = [
+        RefundReason::DeadlinePassed,
+        RefundReason::Emergency,
+        RefundReason::Cancelled,
+        RefundReason::Emergency,
+    ];
+    for (i, reason) in reasons.iter().enumerate() {
+        assert_eq!(all.get(i as u32).unwrap().reason, *reason);
+    }
+    assert_eq!(all.get(0).unwrap().bounty_id, 191);
+    assert_eq!(all.get(3).unwrap().record.amount, 500);
+
+    let page = escrow.get_refund_history_paged(&1, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().bounty_id, 192);
+    assert_eq!(page.get(1).unwrap().bounty_id, 193);
+
+    let bobs = escrow.get_refund_history_for_depositor(&bob, &0, &10);
+    assert_eq!(bobs.len(), 2);
+    assert_eq!(bobs.get(1).unwrap().bounty_id, 195);
+    assert_eq!(
+        escrow
+            .get_refund_history_for_depositor(&alice, &2, &10)
+            .len(),
+        0
+    );
+
+    let one = escrow.get_refund_history_for_bounty(&191);
+    assert_eq!(one.len(), 1);
+    assert_eq!(one.get(0).unwrap().record.depositor, alice);
+    assert_eq!(escrow.get_refund_history_for_bounty(&194).len(), 0);
+}
+
 // ===========================================================================
 // 12. Event emission monitoring – operations produce events
 // ===========================================================================
@@ -1149,3 +1317,933 @@ fn test_get_balance_zero_after_all_escrows_settled() {
         "contract balance must be zero when all escrows are settled"
     );
 }
+
+// ===========================================================================
+// 16. Partial releases – only the released portion leaves the locked bucket
+// ===========================================================================
+
+#[test]
+fn test_aggregate_stats_partial_release_moves_only_released_portion() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    escrow.lock_funds(&depositor, &300, &1_000, &deadline);
+    escrow.partial_release(&300, &contributor, &600);
+
+    let stats = escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 400);
+    assert_eq!(stats.total_released, 600);
+    assert_eq!(stats.count_locked, 1);
+    assert_eq!(stats.count_released, 0);
+    assert_eq!(
+        escrow.get_escrow_info(&300).status,
+        EscrowStatus::Locked,
+        "escrow stays Locked while a remainder exists"
+    );
+
+    escrow.partial_release(&300, &contributor, &400);
+    let stats = escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 0);
+    assert_eq!(stats.total_released, 1_000);
+    assert_eq!(stats.count_released, 1);
+    assert_eq!(escrow.get_escrow_info(&300).status, EscrowStatus::Released);
+}
+
+#[test]
+fn test_aggregate_stats_refund_after_partial_release_splits_buckets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    escrow.lock_funds(&depositor, &301, &1_000, &deadline);
+    escrow.partial_release(&301, &contributor, &250);
+    env.ledger().set_timestamp(deadline + 1);
+    escrow.refund(&301);
+
+    let stats = escrow.get_aggregate_stats();
+    assert_eq!(stats.total_released, 250);
+    assert_eq!(stats.total_refunded, 750);
+    assert_eq!(stats.total_locked, 0);
+}
+
+#[test]
+fn test_query_by_amount_sees_reduced_locked_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    escrow.lock_funds(&depositor, &302, &1_000, &deadline);
+    escrow.partial_release(&302, &contributor, &700);
+
+    assert_eq!(
+        escrow.query_escrows_by_amount(&900, &1_000, &0, &10).len(),
+        0
+    );
+    let hits = escrow.query_escrows_by_amount(&300, &300, &0, &10);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits.get(0).unwrap().bounty_id, 302);
+}
+
+#[test]
+fn test_partial_release_over_remaining_returns_insufficient_escrow_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    escrow.lock_funds(&depositor, &303, &1_000, &deadline);
+    escrow.partial_release(&303, &contributor, &600);
+
+    let res = escrow.try_partial_release(&303, &contributor, &401);
+    assert_eq!(res, Err(Ok(crate::Error::InsufficientEscrowBalance)));
+    assert_eq!(escrow.get_aggregate_stats().total_locked, 400);
+}
+
+// ===========================================================================
+// 17. Granular pause – one paused path leaves the other metrics untouched
+// ===========================================================================
+
+#[test]
+fn test_aggregate_stats_with_only_lock_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    escrow.lock_funds(&depositor, &400, &1_000, &deadline);
+    escrow.lock_funds(&depositor, &401, &2_000, &deadline);
+
+    escrow.set_pause_flags(&true, &false, &false);
+
+    let res = escrow.try_lock_funds(&depositor, &402, &3_000, &deadline);
+    assert_eq!(res, Err(Ok(crate::Error::LockPaused)));
+    assert_eq!(escrow.get_escrow_count(), 2);
+
+    // Releases and refunds keep draining existing escrows
+    escrow.release_funds(&400, &contributor);
+    env.ledger().set_timestamp(deadline + 1);
+    escrow.refund(&401);
+
+    let stats = escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 0);
+    assert_eq!(stats.total_released, 1_000);
+    assert_eq!(stats.total_refunded, 2_000);
+    assert_eq!(stats.count_released, 1);
+    assert_eq!(stats.count_refunded, 1);
+}
+
+#[test]
+fn test_aggregate_stats_with_only_release_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    escrow.lock_funds(&depositor, &410, &1_000, &deadline);
+
+    escrow.set_pause_flags(&false, &true, &false);
+
+    let res = escrow.try_release_funds(&410, &contributor);
+    assert_eq!(res, Err(Ok(crate::Error::ReleasePaused)));
+    let res = escrow.try_partial_release(&410, &contributor, &100);
+    assert_eq!(res, Err(Ok(crate::Error::ReleasePaused)));
+
+    escrow.lock_funds(&depositor, &411, &500, &deadline);
+    env.ledger().set_timestamp(deadline + 1);
+    escrow.refund(&410);
+
+    let stats = escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 500);
+    assert_eq!(stats.total_released, 0);
+    assert_eq!(stats.total_refunded, 1_000);
+    assert_eq!(stats.count_locked, 1);
+    assert_eq!(stats.count_refunded, 1);
+}
+
+#[test]
+fn test_aggregate_stats_with_only_refund_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    escrow.lock_funds(&depositor, &420, &1_000, &deadline);
+    escrow.lock_funds(&depositor, &421, &2_000, &deadline);
+
+    escrow.set_pause_flags(&false, &false, &true);
+
+    escrow.release_funds(&420, &contributor);
+    env.ledger().set_timestamp(deadline + 1);
+    let res = escrow.try_refund(&421);
+    assert_eq!(res, Err(Ok(crate::Error::RefundPaused)));
+
+    let stats = escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 2_000);
+    assert_eq!(stats.total_released, 1_000);
+    assert_eq!(stats.total_refunded, 0);
+    assert_eq!(stats.count_locked, 1);
+    assert_eq!(stats.count_released, 1);
+}
+
+// ===========================================================================
+// 18. Amount distribution across locked escrows
+// ===========================================================================
+
+#[test]
+fn test_amount_percentiles_empty() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (token, _) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+
+    let p = escrow.get_amount_percentiles();
+    assert_eq!(p.count, 0);
+    assert_eq!((p.min, p.median, p.max, p.average), (0, 0, 0, 0));
+}
+
+#[test]
+fn test_amount_percentiles_only_counts_locked() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    let deadline = env.ledger().timestamp() + 1_000;
+    escrow.lock_funds(&depositor, &190, &500, &deadline);
+    escrow.lock_funds(&depositor, &191, &100, &deadline);
+    escrow.lock_funds(&depositor, &192, &900, &deadline);
+    escrow.lock_funds(&depositor, &193, &50_000, &deadline);
+    escrow.release_funds(&193, &contributor);
+
+    // Odd count: the middle amount
+    let p = escrow.get_amount_percentiles();
+    assert_eq!(p.count, 3);
+    assert_eq!(p.min, 100);
+    assert_eq!(p.median, 500);
+    assert_eq!(p.max, 900);
+    assert_eq!(p.average, 500);
+
+    // Even count: mean of the two middle amounts
+    escrow.lock_funds(&depositor, &194, &601, &deadline);
+    let p = escrow.get_amount_percentiles();
+    assert_eq!(p.count, 4);
+    assert_eq!(p.median, 550);
+    assert_eq!(p.average, 525);
+}
+
+// ===========================================================================
+// 19. Activity inside a time window
+// ===========================================================================
+
+#[test]
+fn test_stats_in_window_uses_each_activity_timestamp() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    env.ledger().set_timestamp(1_000);
+    escrow.lock_funds(&depositor, &200, &500, &10_000);
+    env.ledger().set_timestamp(1_060);
+    escrow.lock_funds(&depositor, &201, &300, &10_000);
+
+    // Locked before the window, released inside it
+    env.ledger().set_timestamp(5_000);
+    escrow.lock_funds(&depositor, &202, &700, &10_000);
+    escrow.partial_release(&200, &contributor, &100);
+    escrow.release_funds(&201, &contributor);
+
+    env.ledger().set_timestamp(20_000);
+    escrow.refund(&202);
+
+    let stats = escrow.get_stats_in_window(&0, &2_000);
+    assert_eq!(stats.count_locked, 2);
+    assert_eq!(stats.total_locked, 800);
+    assert_eq!(stats.count_released, 0);
+    assert_eq!(stats.count_refunded, 0);
+
+    let stats = escrow.get_stats_in_window(&4_000, &6_000);
+    assert_eq!(stats.count_locked, 1);
+    assert_eq!(stats.total_locked, 700);
+    assert_eq!(stats.count_released, 2);
+    assert_eq!(stats.total_released, 400);
+    assert_eq!(stats.count_refunded, 0);
+
+    let stats = escrow.get_stats_in_window(&20_000, &20_000);
+    assert_eq!(stats.count_locked, 0);
+    assert_eq!(stats.count_released, 0);
+    assert_eq!(stats.count_refunded, 1);
+    assert_eq!(stats.total_refunded, 700);
+
+    let stats = escrow.get_stats_in_window(&6_000, &4_000);
+    assert_eq!(stats.count_locked, 0);
+    assert_eq!(stats.total_released, 0);
+}
+
+// ===========================================================================
+// 20. Aggregate totals near i128::MAX
+// ===========================================================================
+
+#[test]
+fn test_aggregate_stats_reports_overflow_instead_of_wrapping() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let whale_a = Address::generate(&env);
+    let whale_b = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    // A single token balance cannot exceed i128::MAX, so use two tokens
+    let (other_token, other_token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+
+    let half = i128::MAX / 2 + 1;
+    token_admin.mint(&whale_a, &half);
+    other_token_admin.mint(&whale_b, &half);
+    let deadline = env.ledger().timestamp() + 1_000;
+
+    escrow.lock_funds(&whale_a, &200, &half, &deadline);
+    assert_eq!(escrow.get_aggregate_stats().total_locked, half);
+
+    escrow.lock_funds_with_token(
+        &whale_b,
+        &201,
+        &half,
+        &deadline,
+        &Some(other_token.address.clone()),
+    );
+    let res = escrow.try_get_aggregate_stats();
+    assert_eq!(res, Err(Ok(Error::ArithmeticOverflow)));
+
+    // Each token on its own still fits
+    assert_eq!(
+        escrow
+            .get_aggregate_stats_for_token(&other_token.address)
+            .total_locked,
+        half
+    );
+}
+
+// ===========================================================================
+// 21. Per-project stats and queries
+// ===========================================================================
+
+#[test]
+fn test_project_stats_follow_interleaved_lifecycles() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    let deadline = env.ledger().timestamp() + 1_000;
+    escrow.lock_funds_with_project(&depositor, &210, &300, &deadline, &1);
+    escrow.lock_funds_with_project(&depositor, &211, &500, &deadline, &2);
+    escrow.release_funds(&210, &contributor);
+    escrow.lock_funds_with_project(&depositor, &212, &200, &deadline, &1);
+    escrow.lock_funds(&depositor, &213, &100, &deadline);
+    escrow.partial_release(&212, &contributor, &50);
+    env.ledger().set_timestamp(deadline + 1);
+    escrow.refund(&211);
+
+    assert_eq!(escrow.get_escrow_info(&212).project_id, 1);
+    assert_eq!(escrow.get_escrow_info(&213).project_id, 0);
+
+    let p1 = escrow.get_aggregate_stats_for_project(&1);
+    assert_eq!(p1.count_locked, 1);
+    assert_eq!(p1.count_released, 1);
+    assert_eq!(p1.total_locked, 150);
+    assert_eq!(p1.total_released, 350);
+    assert_eq!(p1.total_refunded, 0);
+
+    let p2 = escrow.get_aggregate_stats_for_project(&2);
+    assert_eq!(p2.count_refunded, 1);
+    assert_eq!(p2.total_refunded, 500);
+    assert_eq!(p2.total_locked, 0);
+
+    let p0 = escrow.get_aggregate_stats_for_project(&0);
+    assert_eq!(p0.count_locked, 1);
+    assert_eq!(p0.total_locked, 100);
+
+    // Global totals are the sum of the projects
+    let all = escrow.get_aggregate_stats();
+    assert_eq!(all.total_locked, 250);
+    assert_eq!(all.total_released, 350);
+    assert_eq!(all.total_refunded, 500);
+
+    let page = escrow.query_escrows_by_project(&1, &0, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().bounty_id, 210);
+    assert_eq!(page.get(1).unwrap().bounty_id, 212);
+    let page = escrow.query_escrows_by_project(&1, &1, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().bounty_id, 212);
+    let page = escrow.query_escrows_by_project(&0, &0, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().bounty_id, 213);
+    assert_eq!(escrow.query_escrows_by_project(&9, &0, &10).len(), 0);
+}
+
+// ===========================================================================
+// 22. Per-depositor stats
+// ===========================================================================
+
+#[test]
+fn test_depositor_stats_are_scoped_to_each_depositor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&alice, &1_000_000);
+    token_admin.mint(&bob, &1_000_000);
+
+    let deadline = 1_000;
+    env.ledger().set_timestamp(100);
+    escrow.lock_funds(&alice, &220, &300, &deadline);
+    env.ledger().set_timestamp(200);
+    escrow.lock_funds(&alice, &221, &500, &deadline);
+    escrow.partial_release(&221, &contributor, &50);
+    escrow.release_funds(&220, &contributor);
+
+    let before = escrow.get_depositor_stats(&alice);
+    assert_eq!(before.total_locked, 800);
+    assert_eq!(before.count_locked, 2);
+    assert_eq!(before.total_released, 350);
+    assert_eq!(before.count_released, 1);
+    assert_eq!(before.total_refunded, 0);
+    assert_eq!(before.active_bounty_ids_count, 1);
+    assert_eq!(before.last_lock_at, 200);
+
+    // Bob's whole lifecycle leaves Alice's numbers alone
+    env.ledger().set_timestamp(300);
+    escrow.lock_funds(&bob, &222, &1_000, &deadline);
+    env.ledger().set_timestamp(deadline + 1);
+    escrow.refund(&222);
+    assert_eq!(escrow.get_depositor_stats(&alice), before);
+
+    let bob_stats = escrow.get_depositor_stats(&bob);
+    assert_eq!(bob_stats.total_locked, 1_000);
+    assert_eq!(bob_stats.total_refunded, 1_000);
+    assert_eq!(bob_stats.count_refunded, 1);
+    assert_eq!(bob_stats.count_released, 0);
+    assert_eq!(bob_stats.active_bounty_ids_count, 0);
+    assert_eq!(bob_stats.last_lock_at, 300);
+
+    // Refunding the rest of Alice's partly released escrow closes it
+    escrow.refund(&221);
+    let after = escrow.get_depositor_stats(&alice);
+    assert_eq!(after.total_refunded, 450);
+    assert_eq!(after.count_refunded, 1);
+    assert_eq!(after.active_bounty_ids_count, 0);
+    assert_eq!(
+        after.total_locked - after.total_released - after.total_refunded,
+        0
+    );
+
+    let stranger = escrow.get_depositor_stats(&Address::generate(&env));
+    assert_eq!(stranger.count_locked, 0);
+    assert_eq!(stranger.last_lock_at, 0);
+}
+
+// ===========================================================================
+// 23. Daily volume buckets
+// ===========================================================================
+
+#[test]
+fn test_daily_stats_split_activity_at_day_boundary() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    let deadline = 200_000;
+    env.ledger().set_timestamp(86_300);
+    escrow.lock_funds(&depositor, &230, &400, &deadline);
+    env.ledger().set_timestamp(86_399);
+    escrow.partial_release(&230, &contributor, &100);
+
+    // One second later is the next day
+    env.ledger().set_timestamp(86_400);
+    escrow.lock_funds(&depositor, &231, &700, &deadline);
+    escrow.release_funds(&231, &contributor);
+    env.ledger().set_timestamp(deadline + 1);
+    escrow.refund(&230);
+
+    let day0 = escrow.get_daily_stats(&0);
+    assert_eq!(day0.total_locked, 400);
+    assert_eq!(day0.count_locked, 1);
+    assert_eq!(day0.total_released, 100);
+    assert_eq!(day0.count_released, 1);
+    assert_eq!(day0.total_refunded, 0);
+
+    let day1 = escrow.get_daily_stats(&1);
+    assert_eq!(day1.total_locked, 700);
+    assert_eq!(day1.count_locked, 1);
+    assert_eq!(day1.total_released, 700);
+    assert_eq!(day1.total_refunded, 0);
+
+    let refund_day = (deadline + 1) / 86_400;
+    let series = escrow.get_daily_stats_range(&0, &refund_day);
+    assert_eq!(series.len() as u64, refund_day + 1);
+    assert_eq!(series.get(0).unwrap(), day0);
+    assert_eq!(series.get(1).unwrap(), day1);
+    let last = series.get(refund_day as u32).unwrap();
+    assert_eq!(last.total_refunded, 300);
+    assert_eq!(last.count_refunded, 1);
+
+    // Ranges are capped and empty days are zeros
+    assert_eq!(escrow.get_daily_stats_range(&0, &1_000).len(), 90);
+    assert_eq!(escrow.get_daily_stats_range(&5, &4).len(), 0);
+    assert_eq!(escrow.get_daily_stats(&50).count_locked, 0);
+}
+
+// ===========================================================================
+// 24. Balance reconciliation
+// ===========================================================================
+
+#[test]
+fn test_balance_reconciliation_per_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let (other_token, other_token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+    other_token_admin.mint(&depositor, &1_000_000);
+
+    let deadline = env.ledger().timestamp() + 1_000;
+    escrow.lock_funds(&depositor, &240, &1_000, &deadline);
+    escrow.lock_funds(&depositor, &241, &500, &deadline);
+    escrow.partial_release(&240, &contributor, &300);
+    escrow.release_funds(&241, &contributor);
+    escrow.lock_funds_with_token(
+        &depositor,
+        &242,
+        &800,
+        &deadline,
+        &Some(other_token.address.clone()),
+    );
+
+    assert_eq!(escrow.get_expected_locked_total(), 700);
+    let rec = escrow.get_balance_reconciliation(&token.address);
+    assert_eq!(rec.expected_locked, 700);
+    assert_eq!(rec.actual_balance, 700);
+    assert_eq!(rec.surplus, 0);
+
+    let rec = escrow.get_balance_reconciliation(&other_token.address);
+    assert_eq!(rec.expected_locked, 800);
+    assert_eq!(rec.surplus, 0);
+
+    // Tokens sent straight to the contract show up as a surplus
+    token_admin.mint(&escrow.address, &25);
+    let rec = escrow.get_balance_reconciliation(&token.address);
+    assert_eq!(rec.actual_balance, 725);
+    assert_eq!(rec.surplus, 25);
+    assert_eq!(
+        escrow
+            .get_balance_reconciliation(&other_token.address)
+            .surplus,
+        0
+    );
+}
+
+#[test]
+fn test_health_check_reports_each_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let (other_token, other_token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+    other_token_admin.mint(&depositor, &1_000_000);
+
+    // Nothing locked yet: only the init token, trivially solvent
+    let report = escrow.get_health_check();
+    assert_eq!(report.len(), 1);
+    assert!(report.get(0).unwrap().is_solvent);
+
+    let deadline = env.ledger().timestamp() + 1_000;
+    escrow.lock_funds(&depositor, &243, &1_000, &deadline);
+    escrow.authorize_claim(&243, &contributor);
+    escrow.lock_funds_with_token(
+        &depositor,
+        &244,
+        &800,
+        &deadline,
+        &Some(other_token.address.clone()),
+    );
+    escrow.lock_funds_with_token(
+        &depositor,
+        &245,
+        &200,
+        &deadline,
+        &Some(other_token.address.clone()),
+    );
+
+    let report = escrow.get_health_check();
+    assert_eq!(report.len(), 2);
+    let main = report.get(0).unwrap();
+    assert_eq!(main.token, token.address);
+    // The pending claim still counts as owed
+    assert_eq!(main.expected_locked, 1_000);
+    assert_eq!(main.actual_balance, 1_000);
+    assert!(main.is_solvent);
+    let other = report.get(1).unwrap();
+    assert_eq!(other.token, other_token.address);
+    assert_eq!(other.expected_locked, 1_000);
+    assert!(other.is_solvent);
+}
+
+// ===========================================================================
+// 25. Extended stats
+// ===========================================================================
+
+#[test]
+fn test_extended_stats_track_extremes_and_unique_addresses() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&alice, &1_000_000);
+    token_admin.mint(&bob, &1_000_000);
+
+    let empty = escrow.get_extended_stats();
+    assert_eq!(empty.active_count, 0);
+    assert_eq!(empty.average_active, 0);
+    assert_eq!(empty.unique_depositors, 0);
+
+    let deadline = env.ledger().timestamp() + 1_000;
+    escrow.lock_funds(&alice, &250, &100, &deadline);
+    escrow.lock_funds(&alice, &251, &500, &deadline);
+    escrow.lock_funds(&bob, &252, &300, &deadline);
+
+    let stats = escrow.get_extended_stats();
+    assert_eq!(stats.active_count, 3);
+    assert_eq!(stats.average_active, 300);
+    assert_eq!(stats.largest_active, 500);
+    assert_eq!(stats.smallest_active, 100);
+    assert_eq!(stats.unique_depositors, 2);
+    assert_eq!(stats.unique_contributors, 0);
+
+    // Releasing the largest and part of the smallest moves both extremes
+    escrow.release_funds(&251, &contributor);
+    escrow.partial_release(&250, &contributor, &40);
+    let stats = escrow.get_extended_stats();
+    assert_eq!(stats.active_count, 2);
+    assert_eq!(stats.largest_active, 300);
+    assert_eq!(stats.smallest_active, 60);
+    assert_eq!(stats.average_active, 180);
+    assert_eq!(stats.unique_contributors, 1);
+
+    // Repeat addresses are only counted once
+    escrow.lock_funds(&bob, &253, &50, &deadline);
+    escrow.release_funds(&252, &contributor);
+    let stats = escrow.get_extended_stats();
+    assert_eq!(stats.smallest_active, 50);
+    assert_eq!(stats.largest_active, 60);
+    assert_eq!(stats.unique_depositors, 2);
+    assert_eq!(stats.unique_contributors, 1);
+}
+
+#[test]
+fn test_unique_depositor_count_never_decreases() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&alice, &1_000_000);
+    token_admin.mint(&bob, &1_000_000);
+    assert_eq!(escrow.get_unique_depositor_count(), 0);
+
+    let deadline = env.ledger().timestamp() + 1_000;
+    escrow.lock_funds(&alice, &254, &100, &deadline);
+    escrow.lock_funds(&alice, &255, &100, &deadline);
+    assert_eq!(escrow.get_unique_depositor_count(), 1);
+
+    // Contributing to someone else's escrow is not a lock of one's own
+    escrow.contribute(&254, &bob, &50);
+    assert_eq!(escrow.get_unique_depositor_count(), 1);
+    escrow.lock_funds(&bob, &256, &100, &deadline);
+    assert_eq!(escrow.get_unique_depositor_count(), 2);
+
+    escrow.release_funds(&254, &contributor);
+    escrow.release_funds(&255, &contributor);
+    env.ledger().set_timestamp(deadline);
+    escrow.refund(&256);
+    assert_eq!(escrow.get_unique_depositor_count(), 2);
+    assert_eq!(escrow.get_extended_stats().unique_depositors, 2);
+}
+
+// ===========================================================================
+// 26. Error stats
+// ===========================================================================
+
+#[test]
+fn test_error_stats_count_skipped_keeper_refunds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000_000);
+
+    let deadline = env.ledger().timestamp() + 1_000;
+    escrow.lock_funds(&depositor, &300, &100, &deadline);
+    escrow.lock_funds(&depositor, &301, &100, &(deadline + 1_000));
+    escrow.lock_funds(&depositor, &302, &100, &deadline);
+    escrow.release_funds(&302, &contributor);
+    assert_eq!(escrow.get_error_stats().total, 0);
+
+    env.ledger().set_timestamp(deadline);
+    let refunded = escrow.refund_expired_batch(&vec![&env, 300, 301, 302, 999]);
+    assert_eq!(refunded, vec![&env, 300]);
+
+    let stats = escrow.get_error_stats();
+    assert_eq!(stats.refund_before_deadline, 1);
+    assert_eq!(stats.not_locked, 1);
+    assert_eq!(stats.disputed, 0);
+    assert_eq!(stats.other, 1);
+    assert_eq!(stats.total, 3);
+
+    // A failure that aborts the call is rolled back with it
+    assert_eq!(escrow.try_refund(&301), Err(Ok(Error::DeadlineNotPassed)));
+    assert_eq!(escrow.get_error_stats().total, 3);
+
+    escrow.reset_error_stats();
+    let stats = escrow.get_error_stats();
+    assert_eq!(stats.refund_before_deadline, 0);
+    assert_eq!(stats.not_locked, 0);
+    assert_eq!(stats.other, 0);
+    assert_eq!(stats.total, 0);
+}
+
+// ===========================================================================
+// 27. Running stats near overflow
+// ===========================================================================
+
+#[test]
+fn test_depositor_stats_overflow_fails_the_lock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000);
+    let deadline = env.ledger().timestamp() + 1_000;
+    escrow.lock_funds(&depositor, &270, &100, &deadline);
+
+    let mut stats = escrow.get_depositor_stats(&depositor);
+    stats.total_locked = i128::MAX - 50;
+    env.as_contract(&escrow.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::DepositorStats(depositor.clone()), &stats);
+    });
+
+    let res = escrow.try_lock_funds(&depositor, &271, &100, &deadline);
+    assert_eq!(res, Err(Ok(Error::ArithmeticOverflow)));
+    assert_eq!(escrow.get_escrow_count(), 1);
+    assert_eq!(token.balance(&depositor), 900);
+    assert_eq!(
+        escrow.get_depositor_stats(&depositor).total_locked,
+        i128::MAX - 50
+    );
+
+    // Up to the limit still fits
+    escrow.lock_funds(&depositor, &271, &50, &deadline);
+    assert_eq!(
+        escrow.get_depositor_stats(&depositor).total_locked,
+        i128::MAX
+    );
+}
+
+#[test]
+fn test_contributor_earnings_overflow_fails_the_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000);
+    let deadline = env.ledger().timestamp() + 1_000;
+    escrow.lock_funds(&depositor, &272, &500, &deadline);
+
+    let mut earnings = escrow.get_contributor_earnings(&contributor);
+    earnings.total_earned = i128::MAX;
+    env.as_contract(&escrow.address, || {
+        env.storage().persistent().set(
+            &DataKey::ContributorEarnings(contributor.clone()),
+            &earnings,
+        );
+    });
+
+    let res = escrow.try_release_funds(&272, &contributor);
+    assert_eq!(res, Err(Ok(Error::ArithmeticOverflow)));
+    assert_eq!(escrow.get_escrow_info(&272).status, EscrowStatus::Locked);
+    assert_eq!(token.balance(&contributor), 0);
+    assert_eq!(escrow.get_aggregate_stats().total_released, 0);
+}
+
+#[test]
+fn test_depositor_lock_count_overflow_fails_the_lock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000);
+    let deadline = env.ledger().timestamp() + 1_000;
+    escrow.lock_funds(&depositor, &274, &100, &deadline);
+
+    let mut stats = escrow.get_depositor_stats(&depositor);
+    stats.count_locked = u32::MAX;
+    env.as_contract(&escrow.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::DepositorStats(depositor.clone()), &stats);
+    });
+
+    let res = escrow.try_lock_funds(&depositor, &275, &100, &deadline);
+    assert_eq!(res, Err(Ok(Error::ArithmeticOverflow)));
+    assert_eq!(escrow.get_escrow_count(), 1);
+    assert_eq!(
+        escrow.get_depositor_stats(&depositor).count_locked,
+        u32::MAX
+    );
+}
+
+#[test]
+fn test_active_count_underflow_saturates_with_anomaly_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token, token_admin) = create_token_contract(&env, &admin);
+    let escrow = create_escrow_contract(&env);
+    escrow.init(&admin, &token.address);
+    token_admin.mint(&depositor, &1_000);
+    let deadline = env.ledger().timestamp() + 1_000;
+    escrow.lock_funds(&depositor, &273, &500, &deadline);
+
+    let mut stats = escrow.get_depositor_stats(&depositor);
+    stats.active_bounty_ids_count = 0;
+    env.as_contract(&escrow.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::DepositorStats(depositor.clone()), &stats);
+    });
+
+    escrow.release_funds(&273, &contributor);
+    let stats = escrow.get_depositor_stats(&depositor);
+    assert_eq!(stats.active_bounty_ids_count, 0);
+    assert_eq!(stats.count_released, 1);
+    assert_eq!(stats.total_released, 500);
+
+    let anomaly = env.events().all().iter().any(|(contract, topics, _)| {
+        contract == escrow.address
+            && Symbol::try_from_val(&env, &topics.get(0).unwrap()) == Ok(symbol_short!("anomaly"))
+            && Symbol::try_from_val(&env, &topics.get(1).unwrap()) == Ok(symbol_short!("active"))
+    });
+    assert!(anomaly);
+}
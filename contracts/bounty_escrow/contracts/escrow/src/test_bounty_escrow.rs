@@ -2,8 +2,9 @@
 use crate::{BountyEscrowContract, BountyEscrowContractClient, Error as ContractError};
 use soroban_sdk::testutils::Events;
 use soroban_sdk::{
+    symbol_short,
     testutils::{Address as _, Ledger},
-    token, Address, Env, Map, Symbol, TryFromVal, Val,
+    token, Address, Env, Map, Symbol, TryFromVal, Val, Vec,
 };
 
 fn create_test_env() -> (Env, BountyEscrowContractClient<'static>, Address) {
@@ -25,27 +26,86 @@ fn create_token_contract<'a>(
     (token, token_client, token_admin_client)
 }
 
-fn assert_event_data_has_v2_tag(env: &Env, data: &Val) {
+fn assert_event_data_has_version_tag(env: &Env, data: &Val, expected: u32) {
     let data_map: Map<Symbol, Val> =
         Map::try_from_val(env, data).unwrap_or_else(|_| panic!("event payload should be a map"));
     let version_val = data_map
         .get(Symbol::new(env, "version"))
         .unwrap_or_else(|| panic!("event payload must contain version field"));
     let version = u32::try_from_val(env, &version_val).expect("version should decode as u32");
-    assert_eq!(version, 2);
+    assert_eq!(version, expected);
 }
 
-fn assert_current_call_has_versioned_contract_event(env: &Env, contract_id: &Address) {
+/// Returns the payload of the last `("escrow", "v3", action, bounty_id)`
+/// event, failing if the contract did not publish one.
+fn versioned_event_payload(
+    env: &Env,
+    contract_id: &Address,
+    action: Symbol,
+    bounty_id: u64,
+) -> Map<Symbol, Val> {
+    let mut payload = None;
+    for (contract, topics, data) in env.events().all().iter() {
+        if contract != *contract_id || topics.len() != 4 {
+            continue;
+        }
+        let prefix = Symbol::try_from_val(env, &topics.get(0).unwrap());
+        let version = Symbol::try_from_val(env, &topics.get(1).unwrap());
+        let kind = Symbol::try_from_val(env, &topics.get(2).unwrap());
+        let id = u64::try_from_val(env, &topics.get(3).unwrap());
+        if prefix == Ok(symbol_short!("escrow"))
+            && version == Ok(symbol_short!("v3"))
+            && kind == Ok(action.clone())
+            && id == Ok(bounty_id)
+        {
+            payload = Some(Map::try_from_val(env, &data).expect("payload should be a map"));
+        }
+    }
+    payload.unwrap_or_else(|| panic!("versioned event not emitted"))
+}
+
+fn event_field<T: TryFromVal<Env, Val>>(env: &Env, payload: &Map<Symbol, Val>, name: &str) -> T {
+    let val = payload
+        .get(Symbol::new(env, name))
+        .unwrap_or_else(|| panic!("event payload missing field"));
+    T::try_from_val(env, &val).unwrap_or_else(|_| panic!("event field has the wrong type"))
+}
+
+fn assert_payload_fields(env: &Env, payload: &Map<Symbol, Val>, fields: &[&str]) {
+    let mut expected: Vec<Symbol> = Vec::new(env);
+    for name in fields {
+        expected.push_back(Symbol::new(env, name));
+    }
+    assert_eq!(payload.len(), expected.len());
+    for name in expected.iter() {
+        assert!(payload.contains_key(name));
+    }
+}
+
+/// Checks every event `contract_id` published after the first `seen` ones
+/// and moves `seen` past them.
+fn assert_current_call_has_versioned_contract_event(
+    env: &Env,
+    contract_id: &Address,
+    seen: &mut u32,
+    expected: u32,
+) {
     let events = env.events().all();
+    let mut index = 0;
     let mut found = false;
     for (contract, _topics, data) in events.iter() {
         if contract != *contract_id {
             continue;
         }
-        assert_event_data_has_v2_tag(env, &data);
+        index += 1;
+        if index <= *seen {
+            continue;
+        }
+        assert_event_data_has_version_tag(env, &data, expected);
         found = true;
     }
     assert!(found);
+    *seen = index;
 }
 
 #[test]
@@ -71,7 +131,7 @@ fn test_init_event() {
 }
 
 #[test]
-fn test_events_emit_v2_version_tags_for_all_bounty_emitters() {
+fn test_events_emit_expected_version_tags_for_all_bounty_emitters() {
     let (env, client, contract_id) = create_test_env();
     env.mock_all_auths();
 
@@ -81,15 +141,17 @@ fn test_events_emit_v2_version_tags_for_all_bounty_emitters() {
     let token_admin = Address::generate(&env);
     let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
 
+    // `init` is contract-level and stays on v2; escrow events are v3
+    let mut seen = 0;
     client.init(&admin, &token);
-    assert_current_call_has_versioned_contract_event(&env, &contract_id);
+    assert_current_call_has_versioned_contract_event(&env, &contract_id, &mut seen, 2);
 
     token_admin_client.mint(&depositor, &10_000);
     client.lock_funds(&depositor, &1, &10_000, &(env.ledger().timestamp() + 10));
-    assert_current_call_has_versioned_contract_event(&env, &contract_id);
+    assert_current_call_has_versioned_contract_event(&env, &contract_id, &mut seen, 3);
 
     client.release_funds(&1, &contributor);
-    assert_current_call_has_versioned_contract_event(&env, &contract_id);
+    assert_current_call_has_versioned_contract_event(&env, &contract_id, &mut seen, 3);
 }
 
 #[test]
@@ -185,11 +247,72 @@ fn test_lock_funds_zero_amount_edge_case() {
     client.init(&admin, &token);
     token_admin_client.mint(&depositor, &1_000);
 
-    client.lock_funds(&depositor, &bounty_id, &amount, &deadline);
+    let res = client.try_lock_funds(&depositor, &bounty_id, &amount, &deadline);
+    assert_eq!(res, Err(Ok(ContractError::InvalidAmount)));
 
-    let escrow = client.get_escrow_info(&bounty_id);
-    assert_eq!(escrow.amount, 0);
-    assert_eq!(escrow.status, crate::EscrowStatus::Locked);
+    // Nothing was recorded for the rejected lock
+    assert_eq!(
+        client.try_get_escrow_info(&bounty_id),
+        Err(Ok(ContractError::BountyNotFound))
+    );
+    assert_eq!(client.get_escrow_count(), 0);
+    assert_eq!(client.get_aggregate_stats().count_locked, 0);
+}
+
+#[test]
+fn test_lock_funds_negative_amount_rejected() {
+    let (env, client, _contract_id) = create_test_env();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 100;
+
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token, token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &1_000);
+
+    let res = client.try_lock_funds(&depositor, &102, &-500, &deadline);
+    assert_eq!(res, Err(Ok(ContractError::InvalidAmount)));
+
+    let stats = client.get_aggregate_stats();
+    assert_eq!(stats.count_locked, 0);
+    assert_eq!(stats.total_locked, 0);
+    assert_eq!(token_client.balance(&depositor), 1_000);
+}
+
+#[test]
+fn test_top_up_and_milestones_reject_non_positive_amounts() {
+    let (env, client, _contract_id) = create_test_env();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 100;
+
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &1_000);
+
+    client.lock_funds(&depositor, &103, &100, &deadline);
+    assert_eq!(
+        client.try_top_up(&depositor, &103, &0),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_top_up(&depositor, &103, &-10),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(client.get_escrow_info(&103).amount, 100);
+
+    let milestones = soroban_sdk::vec![&env, (50_i128, deadline), (-1_i128, deadline)];
+    assert_eq!(
+        client.try_lock_funds_with_milestones(&depositor, &104, &milestones),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(client.get_aggregate_stats().count_locked, 1);
 }
 
 #[test]
@@ -503,17 +626,17 @@ fn test_update_fee_config_with_max_release_fee() {
 
     client.init(&admin, &token);
 
-    // Test: Set release_fee_rate to MAX_FEE_RATE (5000 = 50%) (should succeed)
+    // Test: Set release_fee_rate to MAX_PROTOCOL_FEE_BPS (1000 = 10%) (should succeed)
     let result = client.try_update_fee_config(
         &None,       // lock_fee_rate: unchanged
-        &Some(5000), // release_fee_rate: 50% (MAX_FEE_RATE)
+        &Some(1000), // release_fee_rate: 10% (MAX_PROTOCOL_FEE_BPS)
         &Some(fee_recipient.clone()),
         &None, // fee_enabled: unchanged
     );
     assert!(result.is_ok());
 
     let config = client.get_fee_config();
-    assert_eq!(config.release_fee_rate, 5000);
+    assert_eq!(config.release_fee_rate, 1000);
     assert_eq!(config.fee_recipient, fee_recipient);
 }
 
@@ -605,8 +728,11 @@ fn test_update_fee_config_rejects_over_max_release_fee() {
 
     let original_config = client.get_fee_config();
 
+    // The release fee shares set_fee's cap, so the lock fee cap does not apply
     let result =
-        client.try_update_fee_config(&None, &Some(5001), &Some(fee_recipient.clone()), &None);
+        client.try_update_fee_config(&None, &Some(1001), &Some(fee_recipient.clone()), &None);
+    assert_eq!(result, Err(Ok(ContractError::InvalidFeeRate)));
+    let result = client.try_set_fee(&admin, &1001, &fee_recipient);
     assert_eq!(result, Err(Ok(ContractError::InvalidFeeRate)));
 
     let current_config = client.get_fee_config();
@@ -703,10 +829,10 @@ fn test_update_fee_config_both_rates_at_max() {
 
     client.init(&admin, &token);
 
-    // Test: Set both lock and release fees to MAX_FEE_RATE (should succeed)
+    // Test: Set both lock and release fees to their caps (should succeed)
     let result = client.try_update_fee_config(
         &Some(5000), // lock_fee_rate: 50% (MAX_FEE_RATE)
-        &Some(5000), // release_fee_rate: 50% (MAX_FEE_RATE)
+        &Some(1000), // release_fee_rate: 10% (MAX_PROTOCOL_FEE_BPS)
         &Some(fee_recipient.clone()),
         &None,
     );
@@ -714,7 +840,7 @@ fn test_update_fee_config_both_rates_at_max() {
 
     let config = client.get_fee_config();
     assert_eq!(config.lock_fee_rate, 5000);
-    assert_eq!(config.release_fee_rate, 5000);
+    assert_eq!(config.release_fee_rate, 1000);
 }
 
 #[test]
@@ -1066,7 +1192,7 @@ fn test_no_policy_set_allows_any_positive_amount() {
 
 /// Supplying min > max is a logically invalid policy and must be rejected.
 #[test]
-#[should_panic] // InvalidPolicy / contract-defined panic for malformed config
+#[should_panic(expected = "Error(Contract, #13)")] // InvalidAmount
 fn test_set_amount_policy_min_greater_than_max_rejected() {
     let (env, client, _) = create_test_env();
     let admin = Address::generate(&env);
@@ -1150,3 +1276,651 @@ fn test_one_above_maximum_boundary_rejected() {
     // 10_001 == max(10_000) + 1 → must be rejected.
     client.lock_funds(&depositor, &10, &10_001_i128, &deadline);
 }
+
+/// `set_amount_limits` accepts amounts exactly at min and max and rejects
+/// anything outside them.
+#[test]
+fn test_amount_limits_boundaries() {
+    let (env, client, _) = create_test_env();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 100;
+
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &100_000);
+
+    assert_eq!(client.get_amount_limits(), (0, 0));
+    client.set_amount_limits(&100_i128, &10_000_i128);
+    assert_eq!(client.get_amount_limits(), (100, 10_000));
+
+    client.lock_funds(&depositor, &11, &100_i128, &deadline);
+    client.lock_funds(&depositor, &12, &10_000_i128, &deadline);
+    assert_eq!(
+        client.try_lock_funds(&depositor, &13, &99_i128, &deadline),
+        Err(Ok(ContractError::AmountBelowMinimum))
+    );
+    assert_eq!(
+        client.try_lock_funds(&depositor, &13, &10_001_i128, &deadline),
+        Err(Ok(ContractError::AmountAboveMaximum))
+    );
+
+    // Added funds must meet the minimum, and the new total the maximum
+    assert_eq!(
+        client.try_contribute(&11, &depositor, &99_i128),
+        Err(Ok(ContractError::AmountBelowMinimum))
+    );
+    assert_eq!(
+        client.try_top_up(&depositor, &11, &99_i128),
+        Err(Ok(ContractError::AmountBelowMinimum))
+    );
+    assert_eq!(
+        client.try_contribute(&12, &depositor, &100_i128),
+        Err(Ok(ContractError::AmountAboveMaximum))
+    );
+    assert_eq!(
+        client.try_top_up(&depositor, &12, &100_i128),
+        Err(Ok(ContractError::AmountAboveMaximum))
+    );
+
+    client.contribute(&11, &depositor, &100_i128);
+    client.top_up(&depositor, &11, &9_700_i128);
+    assert_eq!(client.get_escrow_info(&11).amount, 9_900);
+    assert_eq!(
+        client.try_contribute(&11, &depositor, &101_i128),
+        Err(Ok(ContractError::AmountAboveMaximum))
+    );
+    client.contribute(&11, &depositor, &100_i128);
+    assert_eq!(client.get_escrow_info(&11).amount, 10_000);
+}
+
+/// A limit of zero means no limit on that side, and tightening the limits
+/// leaves escrows that are already locked alone.
+#[test]
+fn test_amount_limits_zero_means_unlimited() {
+    let (env, client, _) = create_test_env();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 100;
+
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &1_000_000);
+
+    client.lock_funds(&depositor, &14, &5_i128, &deadline);
+
+    client.set_amount_limits(&100_i128, &0_i128);
+    client.lock_funds(&depositor, &15, &900_000_i128, &deadline);
+    client.release_funds(&14, &contributor);
+    assert_eq!(
+        client.get_escrow_info(&14).status,
+        crate::EscrowStatus::Released
+    );
+
+    client.set_amount_limits(&0_i128, &0_i128);
+    client.lock_funds(&depositor, &16, &1_i128, &deadline);
+
+    assert_eq!(
+        client.try_set_amount_limits(&500_i128, &100_i128),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_set_amount_limits(&-1_i128, &0_i128),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+}
+
+/// `max_total_locked` caps what the escrows hold, releases free up room under
+/// it, and tokens sent to the contract directly do not count against it.
+#[test]
+fn test_max_total_locked_caps_exposure() {
+    let (env, client, _) = create_test_env();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 100;
+
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &1_000_000);
+
+    assert_eq!(client.get_max_total_locked(), 0);
+    client.set_max_total_locked(&1_000_i128);
+    assert_eq!(client.get_max_total_locked(), 1_000);
+
+    client.lock_funds(&depositor, &17, &600_i128, &deadline);
+    client.lock_funds(&depositor, &18, &400_i128, &deadline);
+    assert_eq!(
+        client.try_lock_funds(&depositor, &19, &1_i128, &deadline),
+        Err(Ok(ContractError::AmountExceedsMax))
+    );
+    assert_eq!(
+        client.try_top_up(&depositor, &17, &1_i128),
+        Err(Ok(ContractError::AmountExceedsMax))
+    );
+
+    client.release_funds(&18, &contributor);
+    token_admin_client.mint(&client.address, &10_000);
+    client.lock_funds(&depositor, &19, &400_i128, &deadline);
+
+    client.set_max_total_locked(&0_i128);
+    client.lock_funds(&depositor, &20, &50_000_i128, &deadline);
+    assert_eq!(
+        client.try_set_max_total_locked(&-1_i128),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_versioned_event_schema_lock_release_refund() {
+    let (env, client, contract_id) = create_test_env();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &10_000);
+
+    client.lock_funds(&depositor, &1, &4_000, &100);
+    let locked = versioned_event_payload(&env, &contract_id, symbol_short!("lock"), 1);
+    assert_payload_fields(
+        &env,
+        &locked,
+        &[
+            "version",
+            "bounty_id",
+            "amount",
+            "depositor",
+            "deadline",
+            "token",
+            "timestamp",
+        ],
+    );
+    assert_eq!(event_field::<u32>(&env, &locked, "version"), 3);
+    assert_eq!(event_field::<u64>(&env, &locked, "bounty_id"), 1);
+    assert_eq!(event_field::<i128>(&env, &locked, "amount"), 4_000);
+    assert_eq!(
+        event_field::<Address>(&env, &locked, "depositor"),
+        depositor
+    );
+    assert_eq!(event_field::<u64>(&env, &locked, "deadline"), 100);
+    assert_eq!(event_field::<Address>(&env, &locked, "token"), token);
+    assert_eq!(event_field::<u64>(&env, &locked, "timestamp"), 0);
+
+    client.release_funds(&1, &contributor);
+    let released = versioned_event_payload(&env, &contract_id, symbol_short!("release"), 1);
+    assert_payload_fields(
+        &env,
+        &released,
+        &[
+            "version",
+            "bounty_id",
+            "depositor",
+            "gross",
+            "amount",
+            "fee",
+            "recipient",
+            "reason",
+            "token",
+            "timestamp",
+        ],
+    );
+    assert_eq!(event_field::<u32>(&env, &released, "version"), 3);
+    assert_eq!(event_field::<i128>(&env, &released, "gross"), 4_000);
+    assert_eq!(event_field::<i128>(&env, &released, "amount"), 4_000);
+    assert_eq!(event_field::<i128>(&env, &released, "fee"), 0);
+    assert_eq!(
+        event_field::<Address>(&env, &released, "recipient"),
+        contributor
+    );
+    assert_eq!(event_field::<Address>(&env, &released, "token"), token);
+    assert_eq!(
+        event_field::<Symbol>(&env, &released, "reason"),
+        symbol_short!("release")
+    );
+
+    client.lock_funds(&depositor, &2, &2_500, &100);
+    env.ledger().set_timestamp(101);
+    client.refund(&2);
+    let refunded = versioned_event_payload(&env, &contract_id, symbol_short!("refund"), 2);
+    assert_payload_fields(
+        &env,
+        &refunded,
+        &[
+            "version",
+            "bounty_id",
+            "amount",
+            "depositor",
+            "refund_to",
+            "token",
+            "timestamp",
+        ],
+    );
+    assert_eq!(event_field::<u32>(&env, &refunded, "version"), 3);
+    assert_eq!(event_field::<i128>(&env, &refunded, "amount"), 2_500);
+    assert_eq!(
+        event_field::<Address>(&env, &refunded, "refund_to"),
+        depositor
+    );
+    assert_eq!(event_field::<Address>(&env, &refunded, "token"), token);
+    assert_eq!(event_field::<u64>(&env, &refunded, "timestamp"), 101);
+}
+
+#[test]
+fn test_versioned_event_schema_cancel_and_dispute() {
+    let (env, client, contract_id) = create_test_env();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &10_000);
+
+    client.lock_funds(&depositor, &1, &1_000, &100);
+    client.cancel_escrow(&1);
+    let cancelled = versioned_event_payload(&env, &contract_id, symbol_short!("cancel"), 1);
+    assert_payload_fields(
+        &env,
+        &cancelled,
+        &[
+            "version",
+            "bounty_id",
+            "amount",
+            "depositor",
+            "token",
+            "timestamp",
+        ],
+    );
+    assert_eq!(event_field::<u32>(&env, &cancelled, "version"), 3);
+    assert_eq!(event_field::<i128>(&env, &cancelled, "amount"), 1_000);
+    assert_eq!(
+        event_field::<Address>(&env, &cancelled, "depositor"),
+        depositor
+    );
+    assert_eq!(event_field::<Address>(&env, &cancelled, "token"), token);
+
+    client.lock_funds(&depositor, &2, &3_000, &100);
+    client.assign_contributor(&admin, &2, &contributor);
+    client.open_dispute(&2);
+    let disputed = versioned_event_payload(&env, &contract_id, symbol_short!("dispute"), 2);
+    assert_payload_fields(
+        &env,
+        &disputed,
+        &[
+            "version",
+            "bounty_id",
+            "contributor",
+            "depositor",
+            "amount",
+            "token",
+            "timestamp",
+        ],
+    );
+    assert_eq!(event_field::<u32>(&env, &disputed, "version"), 3);
+    assert_eq!(
+        event_field::<Address>(&env, &disputed, "contributor"),
+        contributor
+    );
+    assert_eq!(
+        event_field::<Address>(&env, &disputed, "depositor"),
+        depositor
+    );
+    assert_eq!(event_field::<i128>(&env, &disputed, "amount"), 3_000);
+    assert_eq!(event_field::<Address>(&env, &disputed, "token"), token);
+}
+
+/// Number of events `contract_id` has published so far in this test.
+fn contract_event_count(env: &Env, contract_id: &Address) -> u32 {
+    env.events()
+        .all()
+        .iter()
+        .filter(|(contract, _, _)| contract == contract_id)
+        .count() as u32
+}
+
+/// Asserts that exactly one event was published since `seen` and that the
+/// latest `action` event for `bounty_id` is a v3 payload, which is returned.
+fn assert_one_event(
+    env: &Env,
+    contract_id: &Address,
+    seen: &mut u32,
+    action: Symbol,
+    bounty_id: u64,
+) -> Map<Symbol, Val> {
+    let now = contract_event_count(env, contract_id);
+    assert_eq!(now, *seen + 1);
+    *seen = now;
+    let payload = versioned_event_payload(env, contract_id, action, bounty_id);
+    assert_eq!(event_field::<u32>(env, &payload, "version"), 3);
+    payload
+}
+
+#[test]
+fn test_versioned_event_schema_claims_and_refund_requests() {
+    let (env, client, contract_id) = create_test_env();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let refund_to = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &10_000);
+    for id in 1..=4_u64 {
+        client.lock_funds(&depositor, &id, &1_000, &100);
+    }
+
+    // Each call publishes exactly one event, under the versioned topics
+    let mut seen = contract_event_count(&env, &contract_id);
+
+    client.set_refund_address(&1, &refund_to);
+    let set = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("ref_addr"), 1);
+    assert_payload_fields(
+        &env,
+        &set,
+        &[
+            "version",
+            "bounty_id",
+            "depositor",
+            "refund_to",
+            "timestamp",
+        ],
+    );
+    assert_eq!(event_field::<Address>(&env, &set, "refund_to"), refund_to);
+
+    client.request_refund(&1, &7);
+    let requested = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("ref_req"), 1);
+    assert_eq!(event_field::<u32>(&env, &requested, "reason_code"), 7);
+    client.approve_refund(&1, &400, &refund_to, &crate::RefundMode::Partial);
+    let approved = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("ref_appr"), 1);
+    assert_payload_fields(
+        &env,
+        &approved,
+        &[
+            "version",
+            "bounty_id",
+            "amount",
+            "recipient",
+            "mode",
+            "approved_by",
+            "timestamp",
+        ],
+    );
+    assert_eq!(event_field::<i128>(&env, &approved, "amount"), 400);
+
+    // Pending claims: created, cancelled, created again and claimed
+    client.authorize_claim(&2, &contributor);
+    let created = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("clm_new"), 2);
+    assert_payload_fields(
+        &env,
+        &created,
+        &[
+            "version",
+            "bounty_id",
+            "recipient",
+            "amount",
+            "expires_at",
+            "timestamp",
+        ],
+    );
+    assert_eq!(event_field::<i128>(&env, &created, "amount"), 1_000);
+    client.cancel_pending_claim(&2);
+    let cancelled = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("clm_cncl"), 2);
+    assert_eq!(
+        event_field::<Address>(&env, &cancelled, "cancelled_by"),
+        admin
+    );
+    client.authorize_claim(&2, &contributor);
+    assert_one_event(&env, &contract_id, &mut seen, symbol_short!("clm_new"), 2);
+    client.claim(&2);
+    let released = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("release"), 2);
+    assert_eq!(
+        event_field::<Symbol>(&env, &released, "reason"),
+        symbol_short!("claim")
+    );
+
+    client.authorize_claim(&3, &contributor);
+    assert_one_event(&env, &contract_id, &mut seen, symbol_short!("clm_new"), 3);
+    client.claim(&3);
+    let released = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("release"), 3);
+    assert_eq!(event_field::<i128>(&env, &released, "gross"), 1_000);
+    assert_eq!(
+        event_field::<Symbol>(&env, &released, "reason"),
+        symbol_short!("claim")
+    );
+
+    // Submitted claims: rejected, then approved
+    client.assign_contributor(&admin, &4, &contributor);
+    seen = contract_event_count(&env, &contract_id);
+    client.submit_claim(&4, &contributor);
+    assert_one_event(&env, &contract_id, &mut seen, symbol_short!("clm_sub"), 4);
+    client.reject_claim(&4);
+    let rejected = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("clm_rej"), 4);
+    assert_payload_fields(
+        &env,
+        &rejected,
+        &[
+            "version",
+            "bounty_id",
+            "contributor",
+            "rejected_by",
+            "timestamp",
+        ],
+    );
+    assert_eq!(
+        event_field::<Address>(&env, &rejected, "rejected_by"),
+        depositor
+    );
+    client.submit_claim(&4, &contributor);
+    assert_one_event(&env, &contract_id, &mut seen, symbol_short!("clm_sub"), 4);
+    client.approve_claim(&4);
+    let released = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("release"), 4);
+    assert_eq!(
+        event_field::<Symbol>(&env, &released, "reason"),
+        symbol_short!("approved")
+    );
+}
+
+#[test]
+fn test_versioned_event_schema_escrow_lifecycle() {
+    let (env, client, contract_id) = create_test_env();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &20_000);
+    token_admin_client.mint(&funder, &1_000);
+    for id in 1..=6_u64 {
+        client.lock_funds(&depositor, &id, &1_000, &100);
+    }
+    client.set_arbiter(&arbiter);
+    let signers = soroban_sdk::vec![&env, admin.clone()];
+    client.update_multisig_config(&10_000, &signers, &1);
+
+    let mut seen = contract_event_count(&env, &contract_id);
+
+    // Funding, deadline and assignment changes
+    client.top_up(&depositor, &1, &500);
+    let topped = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("top_up"), 1);
+    assert_payload_fields(
+        &env,
+        &topped,
+        &[
+            "version",
+            "bounty_id",
+            "depositor",
+            "old_amount",
+            "new_amount",
+            "token",
+            "timestamp",
+        ],
+    );
+    assert_eq!(event_field::<i128>(&env, &topped, "new_amount"), 1_500);
+    client.contribute(&1, &funder, &250);
+    let contributed = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("top_up"), 1);
+    assert_eq!(
+        event_field::<Address>(&env, &contributed, "depositor"),
+        funder
+    );
+    assert_eq!(event_field::<Address>(&env, &contributed, "token"), token);
+
+    client.extend_deadline(&depositor, &1, &200);
+    let extended = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("extend"), 1);
+    assert_eq!(event_field::<u64>(&env, &extended, "new_deadline"), 200);
+
+    client.assign_contributor(&admin, &1, &contributor);
+    let assigned = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("assign"), 1);
+    assert_eq!(
+        event_field::<Address>(&env, &assigned, "contributor"),
+        contributor
+    );
+    client.unassign_contributor(&admin, &1);
+    let unassigned = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("unassign"), 1);
+    assert_eq!(
+        event_field::<Address>(&env, &unassigned, "unassigned_by"),
+        admin
+    );
+
+    client.approve_large_release(&1, &contributor, &admin);
+    let approval = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("approval"), 1);
+    assert_eq!(event_field::<Address>(&env, &approval, "approver"), admin);
+
+    // Timelocked releases: queued, then cancelled
+    client.set_release_timelock(&0, &50);
+    seen = contract_event_count(&env, &contract_id);
+    client.release_funds(&2, &contributor);
+    let queued = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("rel_queue"), 2);
+    assert_payload_fields(
+        &env,
+        &queued,
+        &[
+            "version",
+            "bounty_id",
+            "recipient",
+            "amount",
+            "executable_at",
+            "timestamp",
+        ],
+    );
+    client.cancel_pending_release(&2);
+    let cancelled = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("rel_cncl"), 2);
+    assert_eq!(event_field::<i128>(&env, &cancelled, "amount"), 1_000);
+    client.set_release_timelock(&0, &0);
+    seen = contract_event_count(&env, &contract_id);
+
+    // A split release is one event for every recipient
+    let payouts = soroban_sdk::vec![
+        &env,
+        (contributor.clone(), 600_i128),
+        (funder.clone(), 400_i128)
+    ];
+    client.release_funds_split(&3, &payouts);
+    let split = assert_one_event(&env, &contract_id, &mut seen, symbol_short!("split"), 3);
+    assert_eq!(event_field::<i128>(&env, &split, "amount"), 1_000);
+    assert_eq!(event_field::<Address>(&env, &split, "token"), token);
+
+    // Admin and emergency refunds publish only the refund itself
+    client.admin_refund(&4);
+    assert_one_event(&env, &contract_id, &mut seen, symbol_short!("refund"), 4);
+    client.emergency_refund(&5);
+    assert_one_event(&env, &contract_id, &mut seen, symbol_short!("refund"), 5);
+
+    // Dispute resolution is published next to the payout it makes
+    client.assign_contributor(&admin, &6, &contributor);
+    client.open_dispute(&6);
+    client.resolve_dispute(&6, &crate::DisputeRuling::Release);
+    let resolved = versioned_event_payload(&env, &contract_id, symbol_short!("resolve"), 6);
+    assert_eq!(event_field::<u32>(&env, &resolved, "version"), 3);
+    assert_eq!(event_field::<Address>(&env, &resolved, "arbiter"), arbiter);
+    versioned_event_payload(&env, &contract_id, symbol_short!("release"), 6);
+}
+
+#[test]
+fn test_emit_events_toggle_mutes_transfer_events_only() {
+    let (env, client, contract_id) = create_test_env();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let (token, token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    client.init(&admin, &token);
+    token_admin_client.mint(&depositor, &10_000);
+    client.set_whitelist(&depositor, &true);
+    assert!(client.get_emit_events());
+
+    client.set_emit_events(&false);
+    assert!(!client.get_emit_events());
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
+        symbol_short!("evt_emit")
+    );
+    let toggled: Map<Symbol, Val> = Map::try_from_val(&env, &data).unwrap();
+    assert!(!event_field::<bool>(&env, &toggled, "enabled"));
+
+    let count_contract_events = |env: &Env| {
+        env.events()
+            .all()
+            .iter()
+            .filter(|(contract, _, _)| *contract == contract_id)
+            .count()
+    };
+    let published = count_contract_events(&env);
+
+    client.lock_funds(&depositor, &1, &1_000, &100);
+    assert_eq!(count_contract_events(&env), published);
+    client.release_funds(&1, &contributor);
+    assert_eq!(count_contract_events(&env), published);
+    client.lock_funds(&depositor, &2, &1_000, &100);
+    env.ledger().set_timestamp(101);
+    client.refund(&2);
+    assert_eq!(count_contract_events(&env), published);
+
+    // State and transfers are unaffected
+    assert_eq!(token_client.balance(&contributor), 1_000);
+    assert_eq!(token_client.balance(&depositor), 9_000);
+    let stats = client.get_aggregate_stats();
+    assert_eq!(stats.total_released, 1_000);
+    assert_eq!(stats.total_refunded, 1_000);
+
+    // Other events are still published
+    client.lock_funds(&depositor, &3, &1_000, &200);
+    client.cancel_escrow(&3);
+    versioned_event_payload(&env, &contract_id, symbol_short!("cancel"), 3);
+
+    client.set_emit_events(&true);
+    assert!(client.get_emit_events());
+    client.lock_funds(&depositor, &4, &1_000, &200);
+    versioned_event_payload(&env, &contract_id, symbol_short!("lock"), 4);
+}
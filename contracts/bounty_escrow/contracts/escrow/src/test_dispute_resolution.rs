@@ -1,56 +1,267 @@
 #![cfg(test)]
 
-// Dispute resolution test stubs
-// These tests will be implemented once Issue 61 (dispute resolution) is complete
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+
+struct Setup<'a> {
+    env: Env,
+    depositor: Address,
+    contributor: Address,
+    arbiter: Address,
+    token: token::Client<'a>,
+    escrow: BountyEscrowContractClient<'a>,
+}
+
+impl<'a> Setup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        let arbiter = Address::generate(&env);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token = token::Client::new(&env, &token_id);
+        token::StellarAssetClient::new(&env, &token_id).mint(&depositor, &10_000);
+
+        let escrow_id = env.register_contract(None, BountyEscrowContract);
+        let escrow = BountyEscrowContractClient::new(&env, &escrow_id);
+        escrow.init(&admin, &token_id);
+        escrow.set_arbiter(&arbiter);
+
+        Setup {
+            env,
+            depositor,
+            contributor,
+            arbiter,
+            token,
+            escrow,
+        }
+    }
+
+    /// Lock `amount`, assign the contributor and open a dispute.
+    fn disputed(&self, bounty_id: u64, amount: i128) {
+        let deadline = self.env.ledger().timestamp() + 1000;
+        self.escrow
+            .lock_funds(&self.depositor, &bounty_id, &amount, &deadline);
+        self.escrow
+            .assign_contributor(&self.depositor, &bounty_id, &self.contributor);
+        self.escrow.open_dispute(&bounty_id);
+    }
+}
 
 #[test]
 fn test_open_dispute_blocks_release() {
-    // TODO: Once dispute resolution is implemented (Issue 61), add:
-    // 1. Lock funds for a bounty
-    // 2. Open a dispute
-    // 3. Attempt to release funds
-    // 4. Assert that release is blocked while dispute is open
+    let s = Setup::new();
+    s.disputed(1, 1_000);
+
+    assert_eq!(s.escrow.get_escrow_info(&1).status, EscrowStatus::Disputed);
+    let res = s.escrow.try_release_funds(&1, &s.contributor);
+    assert_eq!(res, Err(Ok(Error::EscrowDisputed)));
+    let res = s.escrow.try_partial_release(&1, &s.contributor, &100);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
 }
 
 #[test]
 fn test_open_dispute_blocks_refund() {
-    // TODO: Once dispute resolution is implemented (Issue 61), add:
-    // 1. Lock funds for a bounty
-    // 2. Wait for deadline to pass
-    // 3. Open a dispute
-    // 4. Attempt to refund
-    // 5. Assert that refund is blocked while dispute is open
+    let s = Setup::new();
+    s.disputed(1, 1_000);
+
+    s.env.ledger().set_timestamp(2_000);
+    let res = s.escrow.try_refund(&1);
+    assert_eq!(res, Err(Ok(Error::EscrowDisputed)));
+    assert_eq!(s.token.balance(&s.escrow.address), 1_000);
+}
+
+#[test]
+fn test_only_assignee_can_open_dispute() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    s.escrow.lock_funds(&s.depositor, &1, &1_000, &deadline);
+
+    let res = s.escrow.try_open_dispute(&1);
+    assert_eq!(res, Err(Ok(Error::NotAssignedContributor)));
+
+    s.escrow
+        .assign_contributor(&s.depositor, &1, &s.contributor);
+    s.escrow.open_dispute(&1);
+    assert_eq!(s.env.auths()[0].0, s.contributor);
+
+    let res = s.escrow.try_open_dispute(&1);
+    assert_eq!(res, Err(Ok(Error::EscrowDisputed)));
 }
 
 #[test]
 fn test_resolve_dispute_in_favor_of_release() {
-    // TODO: Once dispute resolution is implemented (Issue 61), add:
-    // 1. Lock funds for a bounty
-    // 2. Open a dispute
-    // 3. Resolve dispute in favor of release
-    // 4. Verify funds are released to contributor
-    // 5. Verify escrow status is Released
-    // 6. Verify final balances are correct
+    let s = Setup::new();
+    s.disputed(1, 1_000);
+
+    s.escrow.resolve_dispute(&1, &DisputeRuling::Release);
+    assert_eq!(s.env.auths()[0].0, s.arbiter);
+
+    assert_eq!(s.escrow.get_escrow_info(&1).status, EscrowStatus::Released);
+    assert_eq!(s.token.balance(&s.contributor), 1_000);
+    assert_eq!(s.token.balance(&s.escrow.address), 0);
+}
+
+#[test]
+fn test_resolve_dispute_release_after_partial_release_pays_remaining() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    s.escrow.lock_funds(&s.depositor, &1, &1_000, &deadline);
+    s.escrow.lock_funds(&s.depositor, &2, &500, &deadline);
+    s.escrow
+        .assign_contributor(&s.depositor, &1, &s.contributor);
+    s.escrow.partial_release(&1, &s.contributor, &300);
+    s.escrow.open_dispute(&1);
+
+    s.escrow.resolve_dispute(&1, &DisputeRuling::Release);
+
+    assert_eq!(s.token.balance(&s.contributor), 1_000);
+    assert_eq!(s.escrow.get_escrow_info(&1).remaining_amount, 0);
+    // Bounty 2 is still fully backed
+    assert_eq!(s.token.balance(&s.escrow.address), 500);
 }
 
 #[test]
 fn test_resolve_dispute_in_favor_of_refund() {
-    // TODO: Once dispute resolution is implemented (Issue 61), add:
-    // 1. Lock funds for a bounty
-    // 2. Open a dispute
-    // 3. Resolve dispute in favor of refund
-    // 4. Verify funds are refunded to depositor
-    // 5. Verify escrow status is Refunded
-    // 6. Verify final balances are correct
+    let s = Setup::new();
+    s.disputed(1, 1_000);
+
+    s.escrow.resolve_dispute(&1, &DisputeRuling::Refund);
+
+    let escrow = s.escrow.get_escrow_info(&1);
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(escrow.refund_history.len(), 1);
+    assert_eq!(s.token.balance(&s.depositor), 10_000);
+    assert_eq!(s.token.balance(&s.contributor), 0);
+}
+
+#[test]
+fn test_resolve_dispute_split() {
+    let s = Setup::new();
+    s.disputed(1, 1_000);
+
+    let res = s
+        .escrow
+        .try_resolve_dispute(&1, &DisputeRuling::Split(10_001));
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+    let res = s.escrow.try_resolve_dispute(&1, &DisputeRuling::Split(0));
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    s.escrow.resolve_dispute(&1, &DisputeRuling::Split(7_000));
+
+    let escrow = s.escrow.get_escrow_info(&1);
+    assert_eq!(escrow.status, EscrowStatus::Settled);
+    assert_eq!(escrow.remaining_amount, 300);
+    assert_eq!(
+        escrow.refund_history.get(0).unwrap().reason,
+        symbol_short!("dispute")
+    );
+    assert_eq!(s.token.balance(&s.contributor), 700);
+    assert_eq!(s.token.balance(&s.depositor), 9_300);
+}
+
+#[test]
+fn test_resolve_dispute_split_large_amount_does_not_overflow() {
+    let s = Setup::new();
+    let amount = i128::MAX / 4;
+    token::StellarAssetClient::new(&s.env, &s.token.address).mint(&s.depositor, &amount);
+    s.disputed(1, amount);
+
+    s.escrow.resolve_dispute(&1, &DisputeRuling::Split(7_000));
+
+    let released = amount / 10 * 7 + amount % 10 * 7 / 10;
+    assert_eq!(s.token.balance(&s.contributor), released);
+    assert_eq!(s.escrow.get_escrow_info(&1).status, EscrowStatus::Settled);
+}
+
+#[test]
+fn test_resolve_dispute_requires_arbiter_and_dispute() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token_id).mint(&depositor, &1_000);
+    let escrow_id = env.register_contract(None, BountyEscrowContract);
+    let escrow = BountyEscrowContractClient::new(&env, &escrow_id);
+    escrow.init(&Address::generate(&env), &token_id);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    escrow.lock_funds(&depositor, &1, &1_000, &deadline);
+    escrow.assign_contributor(&depositor, &1, &contributor);
+    escrow.open_dispute(&1);
+
+    let res = escrow.try_resolve_dispute(&1, &DisputeRuling::Release);
+    assert_eq!(res, Err(Ok(Error::ArbiterNotSet)));
+
+    let arbiter = Address::generate(&env);
+    escrow.set_arbiter(&arbiter);
+    assert_eq!(escrow.get_arbiter(), Some(arbiter));
+    escrow.resolve_dispute(&1, &DisputeRuling::Release);
+
+    let res = escrow.try_resolve_dispute(&1, &DisputeRuling::Release);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+#[test]
+fn test_depositor_chosen_arbiter_cannot_resolve_dispute() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    s.escrow
+        .lock_funds_with_arbiter(&s.depositor, &1, &1_000, &deadline, &s.depositor);
+    s.escrow
+        .assign_contributor(&s.depositor, &1, &s.contributor);
+    s.escrow.open_dispute(&1);
+
+    s.escrow.resolve_dispute(&1, &DisputeRuling::Release);
+    assert_eq!(s.env.auths()[0].0, s.arbiter);
+    assert_eq!(s.token.balance(&s.contributor), 1_000);
 }
 
 #[test]
 fn test_dispute_status_tracking() {
-    // TODO: Once dispute resolution is implemented (Issue 61), add:
-    // 1. Lock funds for a bounty
-    // 2. Verify dispute status is not disputed
-    // 3. Open a dispute
-    // 4. Verify dispute status shows disputed with correct opener
-    // 5. Resolve dispute
-    // 6. Verify dispute status is no longer disputed
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    s.escrow.lock_funds(&s.depositor, &1, &1_000, &deadline);
+    s.escrow.lock_funds(&s.depositor, &2, &400, &deadline);
+    assert_eq!(s.escrow.get_dispute(&1), None);
+
+    s.env.ledger().set_timestamp(50);
+    s.escrow
+        .assign_contributor(&s.depositor, &1, &s.contributor);
+    s.escrow.open_dispute(&1);
+
+    let dispute = s.escrow.get_dispute(&1).unwrap();
+    assert_eq!(dispute.contributor, s.contributor);
+    assert_eq!(dispute.opened_at, 50);
+
+    let stats = s.escrow.get_aggregate_stats();
+    assert_eq!(stats.count_disputed, 1);
+    assert_eq!(stats.total_disputed, 1_000);
+    assert_eq!(stats.count_locked, 1);
+    assert_eq!(stats.total_locked, 400);
+
+    let disputed = s
+        .escrow
+        .query_escrows_by_status(&EscrowStatus::Disputed, &0, &10);
+    assert_eq!(disputed.len(), 1);
+    assert_eq!(disputed.get(0).unwrap().bounty_id, 1);
+
+    s.escrow.resolve_dispute(&1, &DisputeRuling::Release);
+    assert_eq!(s.escrow.get_dispute(&1), None);
+    let stats = s.escrow.get_aggregate_stats();
+    assert_eq!(stats.count_disputed, 0);
+    assert_eq!(stats.total_disputed, 0);
+    assert_eq!(stats.total_released, 1_000);
 }
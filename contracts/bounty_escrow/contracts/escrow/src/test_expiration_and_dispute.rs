@@ -1,9 +1,13 @@
 #![cfg(test)]
 
-use crate::{BountyEscrowContract, BountyEscrowContractClient, EscrowStatus};
+use crate::events::DeadlineExtended;
+use crate::{
+    BountyEscrowContract, BountyEscrowContractClient, Error, EscrowStatus, RefundEligibility,
+};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    token, Address, Env,
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, Address, Env, Symbol, TryFromVal,
 };
 
 fn create_token_contract<'a>(
@@ -158,9 +162,9 @@ fn test_missed_claim_window_requires_admin_cancel_then_refund() {
     // Advance to after claim window but before deadline
     setup.env.ledger().set_timestamp(claim_expires_at + 1);
 
-    // Escrow is still Locked with pending claim
+    // Escrow is still held for the expired claim
     let escrow = setup.escrow.get_escrow_info(&bounty_id);
-    assert_eq!(escrow.status, EscrowStatus::Locked);
+    assert_eq!(escrow.status, EscrowStatus::PendingClaim);
     assert_eq!(setup.token.balance(&setup.escrow.address), amount);
 
     // Admin cancels the expired pending claim
@@ -406,3 +410,120 @@ fn test_claim_cancellation_restores_refund_eligibility() {
 
     assert_eq!(setup.token.balance(&setup.depositor), 10_000_000);
 }
+
+// Deadline extension
+
+#[test]
+fn test_extend_deadline_delays_refund() {
+    let setup = TestSetup::new();
+    let bounty_id = 50;
+    let now = setup.env.ledger().timestamp();
+    let deadline = now + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1000, &deadline);
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    let new_deadline = deadline + 500;
+    setup
+        .escrow
+        .extend_deadline(&setup.depositor, &bounty_id, &new_deadline);
+
+    let escrow = setup.escrow.get_escrow_info(&bounty_id);
+    assert_eq!(escrow.deadline, new_deadline);
+
+    // Eligibility and refund follow the new deadline straight away
+    let (eligibility, deadline_passed, _, _) = setup.escrow.get_refund_eligibility(&bounty_id);
+    assert_eq!(eligibility, RefundEligibility::DeadlineNotPassed);
+    assert!(!deadline_passed);
+    let res = setup.escrow.try_refund(&bounty_id);
+    assert_eq!(res, Err(Ok(Error::DeadlineNotPassed)));
+
+    let found = setup
+        .escrow
+        .query_escrows_by_deadline(&new_deadline, &new_deadline, &0, &10);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found.get(0).unwrap().bounty_id, bounty_id);
+}
+
+#[test]
+fn test_extend_deadline_by_admin() {
+    let setup = TestSetup::new();
+    let bounty_id = 51;
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1000, &deadline);
+
+    setup
+        .escrow
+        .extend_deadline(&setup.admin, &bounty_id, &(deadline + 100));
+
+    let (_, topics, data) = setup.env.events().all().last().unwrap();
+    let topic = Symbol::try_from_val(&setup.env, &topics.get(2).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("extend"));
+    let event = DeadlineExtended::try_from_val(&setup.env, &data).unwrap();
+    assert_eq!(event.old_deadline, deadline);
+    assert_eq!(event.new_deadline, deadline + 100);
+    assert_eq!(event.extended_by, setup.admin);
+    assert_eq!(
+        setup.escrow.get_escrow_info(&bounty_id).deadline,
+        deadline + 100
+    );
+}
+
+#[test]
+fn test_extend_deadline_rejects_invalid_requests() {
+    let setup = TestSetup::new();
+    let bounty_id = 52;
+    let now = setup.env.ledger().timestamp();
+    let deadline = now + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1000, &deadline);
+
+    // Not later than the current deadline
+    let res = setup
+        .escrow
+        .try_extend_deadline(&setup.depositor, &bounty_id, &deadline);
+    assert_eq!(res, Err(Ok(Error::InvalidDeadline)));
+
+    // Later than the deadline but already in the past
+    setup.env.ledger().set_timestamp(deadline + 50);
+    let res = setup
+        .escrow
+        .try_extend_deadline(&setup.depositor, &bounty_id, &(deadline + 10));
+    assert_eq!(res, Err(Ok(Error::InvalidDeadline)));
+
+    // Neither depositor nor admin
+    let res = setup
+        .escrow
+        .try_extend_deadline(&setup.contributor, &bounty_id, &(deadline + 500));
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    // Released escrows cannot be extended
+    setup.escrow.release_funds(&bounty_id, &setup.contributor);
+    let res = setup
+        .escrow
+        .try_extend_deadline(&setup.depositor, &bounty_id, &(deadline + 500));
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+#[test]
+fn test_extend_deadline_rejected_after_refund() {
+    let setup = TestSetup::new();
+    let bounty_id = 53;
+    let deadline = setup.env.ledger().timestamp() + 100;
+    setup
+        .escrow
+        .lock_funds(&setup.depositor, &bounty_id, &1000, &deadline);
+
+    setup.env.ledger().set_timestamp(deadline + 1);
+    setup.escrow.refund(&bounty_id);
+
+    let res = setup
+        .escrow
+        .try_extend_deadline(&setup.depositor, &bounty_id, &(deadline + 500));
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+    assert_eq!(setup.escrow.get_escrow_info(&bounty_id).deadline, deadline);
+}
@@ -0,0 +1,393 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+
+fn create_token_contract<'a>(
+    e: &Env,
+    admin: &Address,
+) -> (token::Client<'a>, token::StellarAssetClient<'a>) {
+    let contract_address = e
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    (
+        token::Client::new(e, &contract_address),
+        token::StellarAssetClient::new(e, &contract_address),
+    )
+}
+
+fn create_escrow_contract<'a>(e: &Env) -> BountyEscrowContractClient<'a> {
+    let contract_id = e.register_contract(None, BountyEscrowContract);
+    BountyEscrowContractClient::new(e, &contract_id)
+}
+
+struct Setup<'a> {
+    env: Env,
+    depositor: Address,
+    contributor: Address,
+    token: token::Client<'a>,
+    escrow: BountyEscrowContractClient<'a>,
+}
+
+impl<'a> Setup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        let (token, token_admin) = create_token_contract(&env, &admin);
+        let escrow = create_escrow_contract(&env);
+        escrow.init(&admin, &token.address);
+        token_admin.mint(&depositor, &1_000_000);
+        Setup {
+            env,
+            depositor,
+            contributor,
+            token,
+            escrow,
+        }
+    }
+}
+
+#[test]
+fn test_lock_with_milestones_stores_slices() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    let amounts = vec![
+        &s.env,
+        (300_i128, deadline),
+        (500_i128, deadline),
+        (200_i128, deadline),
+    ];
+    s.escrow
+        .lock_funds_with_milestones(&s.depositor, &1, &amounts);
+
+    let info = s.escrow.get_escrow_info(&1);
+    assert_eq!(info.amount, 1_000);
+    assert_eq!(info.remaining_amount, 1_000);
+    assert_eq!(info.status, EscrowStatus::Locked);
+    assert_eq!(info.milestones.len(), 3);
+    assert_eq!(info.milestones.get(1).unwrap().amount, 500);
+    assert!(info
+        .milestones
+        .iter()
+        .all(|m| m.status == MilestoneStatus::Pending));
+    assert_eq!(s.token.balance(&s.escrow.address), 1_000);
+}
+
+#[test]
+fn test_release_milestones_progresses_to_released() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    let amounts = vec![&s.env, (300_i128, deadline), (700_i128, deadline)];
+    s.escrow
+        .lock_funds_with_milestones(&s.depositor, &2, &amounts);
+
+    s.escrow.release_milestone(&2, &0, &s.contributor);
+    let info = s.escrow.get_escrow_info(&2);
+    assert_eq!(info.status, EscrowStatus::PartiallyReleased);
+    assert_eq!(info.remaining_amount, 700);
+    assert_eq!(
+        info.milestones.get(0).unwrap().status,
+        MilestoneStatus::Released
+    );
+    assert_eq!(
+        info.milestones.get(1).unwrap().status,
+        MilestoneStatus::Pending
+    );
+    assert_eq!(s.token.balance(&s.contributor), 300);
+
+    let stats = s.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 700);
+    assert_eq!(stats.total_released, 300);
+    assert_eq!(stats.count_locked, 1);
+
+    s.escrow.release_milestone(&2, &1, &s.contributor);
+    let info = s.escrow.get_escrow_info(&2);
+    assert_eq!(info.status, EscrowStatus::Released);
+    assert_eq!(info.remaining_amount, 0);
+    assert_eq!(s.token.balance(&s.contributor), 1_000);
+}
+
+#[test]
+fn test_release_milestones_out_of_order() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    let amounts = vec![
+        &s.env,
+        (100_i128, deadline),
+        (200_i128, deadline),
+        (300_i128, deadline),
+    ];
+    s.escrow
+        .lock_funds_with_milestones(&s.depositor, &3, &amounts);
+
+    s.escrow.release_milestone(&3, &2, &s.contributor);
+    s.escrow.release_milestone(&3, &0, &s.contributor);
+    assert_eq!(
+        s.escrow.get_escrow_info(&3).status,
+        EscrowStatus::PartiallyReleased
+    );
+    s.escrow.release_milestone(&3, &1, &s.contributor);
+    assert_eq!(s.escrow.get_escrow_info(&3).status, EscrowStatus::Released);
+}
+
+#[test]
+fn test_release_milestone_twice_fails() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    let amounts = vec![&s.env, (100_i128, deadline), (200_i128, deadline)];
+    s.escrow
+        .lock_funds_with_milestones(&s.depositor, &4, &amounts);
+
+    s.escrow.release_milestone(&4, &0, &s.contributor);
+    let res = s.escrow.try_release_milestone(&4, &0, &s.contributor);
+    assert_eq!(res, Err(Ok(Error::MilestoneAlreadyReleased)));
+}
+
+#[test]
+fn test_release_milestone_index_out_of_range() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    let amounts = vec![&s.env, (100_i128, deadline)];
+    s.escrow
+        .lock_funds_with_milestones(&s.depositor, &5, &amounts);
+
+    let res = s.escrow.try_release_milestone(&5, &1, &s.contributor);
+    assert_eq!(res, Err(Ok(Error::MilestoneNotFound)));
+}
+
+#[test]
+fn test_lock_with_milestones_rejects_empty_and_non_positive() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+
+    let empty: Vec<(i128, u64)> = vec![&s.env];
+    let res = s
+        .escrow
+        .try_lock_funds_with_milestones(&s.depositor, &6, &empty);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    let with_zero = vec![&s.env, (100_i128, deadline), (0_i128, deadline)];
+    let res = s
+        .escrow
+        .try_lock_funds_with_milestones(&s.depositor, &6, &with_zero);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+    assert_eq!(s.escrow.get_escrow_count(), 0);
+}
+
+#[test]
+fn test_refund_after_partial_milestones_returns_unpaid_remainder() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    let amounts = vec![&s.env, (400_i128, deadline), (600_i128, deadline)];
+    s.escrow
+        .lock_funds_with_milestones(&s.depositor, &7, &amounts);
+    s.escrow.release_milestone(&7, &0, &s.contributor);
+
+    let before = s.token.balance(&s.depositor);
+    s.env.ledger().set_timestamp(deadline + 1);
+    s.escrow.refund(&7);
+
+    assert_eq!(s.token.balance(&s.depositor), before + 600);
+    assert_eq!(s.escrow.get_escrow_info(&7).status, EscrowStatus::Refunded);
+    assert_eq!(s.token.balance(&s.escrow.address), 0);
+}
+
+#[test]
+fn test_lock_with_milestones_uses_latest_deadline() {
+    let s = Setup::new();
+    let now = s.env.ledger().timestamp();
+    let amounts = vec![&s.env, (100_i128, now + 500), (200_i128, now + 100)];
+    s.escrow
+        .lock_funds_with_milestones(&s.depositor, &8, &amounts);
+
+    let info = s.escrow.get_escrow_info(&8);
+    assert_eq!(info.deadline, now + 500);
+    assert_eq!(info.milestones.get(1).unwrap().deadline, now + 100);
+}
+
+#[test]
+fn test_refund_only_returns_expired_milestones() {
+    let s = Setup::new();
+    let now = s.env.ledger().timestamp();
+    let amounts = vec![
+        &s.env,
+        (100_i128, now + 100),
+        (200_i128, now + 200),
+        (300_i128, now + 300),
+    ];
+    s.escrow
+        .lock_funds_with_milestones(&s.depositor, &9, &amounts);
+
+    // Nothing has expired yet
+    let res = s.escrow.try_refund(&9);
+    assert_eq!(res, Err(Ok(Error::DeadlineNotPassed)));
+
+    s.escrow.release_milestone(&9, &0, &s.contributor);
+    let before = s.token.balance(&s.depositor);
+
+    // Milestone 1 has expired, milestone 2 has not
+    s.env.ledger().set_timestamp(now + 250);
+    s.escrow.refund(&9);
+    assert_eq!(s.token.balance(&s.depositor), before + 200);
+
+    let info = s.escrow.get_escrow_info(&9);
+    assert_eq!(info.status, EscrowStatus::PartiallyRefunded);
+    assert_eq!(info.remaining_amount, 300);
+    assert_eq!(
+        info.milestones.get(1).unwrap().status,
+        MilestoneStatus::Refunded
+    );
+    assert_eq!(
+        info.milestones.get(2).unwrap().status,
+        MilestoneStatus::Pending
+    );
+
+    let stats = s.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_released, 100);
+    assert_eq!(stats.total_refunded, 200);
+    assert_eq!(stats.total_locked, 300);
+
+    // The refunded milestone can no longer be released, the pending one can
+    let res = s.escrow.try_release_milestone(&9, &1, &s.contributor);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+    s.escrow.release_milestone(&9, &2, &s.contributor);
+
+    let info = s.escrow.get_escrow_info(&9);
+    assert_eq!(info.status, EscrowStatus::Released);
+    assert_eq!(info.remaining_amount, 0);
+    assert_eq!(s.token.balance(&s.escrow.address), 0);
+
+    let stats = s.escrow.get_aggregate_stats();
+    assert_eq!(stats.total_released, 400);
+    assert_eq!(stats.total_refunded, 200);
+    assert_eq!(stats.total_locked, 0);
+}
+
+/// Eligibility follows the per-milestone deadlines that `refund` uses, including
+/// after some milestones have been paid out.
+#[test]
+fn test_refund_eligibility_follows_milestone_deadlines() {
+    let s = Setup::new();
+    let now = s.env.ledger().timestamp();
+    let amounts = vec![&s.env, (100_i128, now + 100), (200_i128, now + 300)];
+    s.escrow
+        .lock_funds_with_milestones(&s.depositor, &12, &amounts);
+    s.escrow.release_milestone(&12, &0, &s.contributor);
+
+    let (eligibility, ..) = s.escrow.get_refund_eligibility(&12);
+    assert_eq!(eligibility, RefundEligibility::DeadlineNotPassed);
+
+    // Past milestone 0's deadline, but that milestone is already paid
+    s.env.ledger().set_timestamp(now + 200);
+    let (eligibility, ..) = s.escrow.get_refund_eligibility(&12);
+    assert_eq!(eligibility, RefundEligibility::DeadlineNotPassed);
+    assert_eq!(s.escrow.try_refund(&12), Err(Ok(Error::DeadlineNotPassed)));
+
+    s.env.ledger().set_timestamp(now + 300);
+    assert_eq!(
+        s.escrow.get_escrow_info(&12).status,
+        EscrowStatus::PartiallyReleased
+    );
+    let (eligibility, ..) = s.escrow.get_refund_eligibility(&12);
+    assert_eq!(eligibility, RefundEligibility::Eligible);
+    assert_eq!(s.escrow.get_all_refund_eligible_ids(&10), vec![&s.env, 12]);
+    s.escrow.refund(&12);
+    assert_eq!(s.escrow.get_escrow_info(&12).status, EscrowStatus::Refunded);
+}
+
+#[test]
+fn test_partial_release_rejected_on_milestone_escrow() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    s.escrow.lock_funds(&s.depositor, &99, &500, &deadline);
+    let amounts = vec![&s.env, (100_i128, deadline), (100_i128, deadline)];
+    s.escrow
+        .lock_funds_with_milestones(&s.depositor, &10, &amounts);
+
+    // Funds held for milestones can only leave through release_milestone or refund
+    let res = s.escrow.try_partial_release(&10, &s.contributor, &150);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    s.escrow.release_milestone(&10, &0, &s.contributor);
+    s.env.ledger().set_timestamp(deadline + 1);
+    let before = s.token.balance(&s.depositor);
+    s.escrow.refund(&10);
+
+    assert_eq!(s.token.balance(&s.depositor), before + 100);
+    assert_eq!(s.token.balance(&s.contributor), 100);
+    assert_eq!(s.escrow.get_escrow_info(&10).remaining_amount, 0);
+    // The other escrow's funds are untouched
+    assert_eq!(s.token.balance(&s.escrow.address), 500);
+}
+
+#[test]
+fn test_milestone_payouts_never_exceed_remaining() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    s.escrow.lock_funds(&s.depositor, &99, &500, &deadline);
+    let amounts = vec![&s.env, (100_i128, deadline), (100_i128, deadline)];
+    s.escrow
+        .lock_funds_with_milestones(&s.depositor, &11, &amounts);
+
+    // An escrow whose balance no longer covers its milestones
+    s.env.as_contract(&s.escrow.address, || {
+        let mut escrow: Escrow = s
+            .env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(11))
+            .unwrap();
+        escrow.remaining_amount = 50;
+        s.env
+            .storage()
+            .persistent()
+            .set(&DataKey::Escrow(11), &escrow);
+    });
+
+    let res = s.escrow.try_release_milestone(&11, &0, &s.contributor);
+    assert_eq!(res, Err(Ok(Error::InsufficientEscrowBalance)));
+    s.env.ledger().set_timestamp(deadline + 1);
+    let res = s.escrow.try_refund(&11);
+    assert_eq!(res, Err(Ok(Error::InsufficientEscrowBalance)));
+
+    assert_eq!(s.escrow.get_escrow_info(&11).remaining_amount, 50);
+    assert_eq!(s.token.balance(&s.escrow.address), 700);
+}
+
+#[test]
+fn test_milestone_refund_is_split_across_funders() {
+    let s = Setup::new();
+    let funder = Address::generate(&s.env);
+    let deadline = s.env.ledger().timestamp() + 1000;
+    let amounts = vec![&s.env, (100_i128, deadline), (100_i128, deadline)];
+    s.escrow
+        .lock_funds_with_milestones(&s.depositor, &12, &amounts);
+    s.env.as_contract(&s.escrow.address, || {
+        s.env.storage().persistent().set(
+            &DataKey::Contributions(12),
+            &vec![
+                &s.env,
+                (s.depositor.clone(), 150_i128),
+                (funder.clone(), 50),
+            ],
+        );
+    });
+
+    s.escrow.release_milestone(&12, &0, &s.contributor);
+    s.env.ledger().set_timestamp(deadline + 1);
+    let before = s.token.balance(&s.depositor);
+    s.escrow.refund(&12);
+
+    assert_eq!(s.token.balance(&s.depositor), before + 75);
+    assert_eq!(s.token.balance(&funder), 25);
+    let info = s.escrow.get_escrow_info(&12);
+    assert_eq!(info.status, EscrowStatus::Refunded);
+    assert_eq!(info.refund_history.len(), 2);
+    assert_eq!(s.token.balance(&s.escrow.address), 0);
+}
@@ -2,8 +2,8 @@
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    token, Address, Env,
+    testutils::{Address as _, Events, Ledger},
+    token, Address, Env, Symbol, TryFromVal,
 };
 
 fn create_token_contract<'a>(
@@ -56,7 +56,7 @@ fn test_granular_pause_lock() {
 
     let bounty_id_2: u64 = 2;
     let res = escrow_client.try_lock_funds(&depositor, &bounty_id_2, &100, &deadline);
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(Error::LockPaused)));
 
     escrow_client.set_paused(&Some(false), &None, &None);
     let flags = escrow_client.get_pause_flags();
@@ -90,7 +90,7 @@ fn test_granular_pause_release() {
     assert_eq!(flags.release_paused, true);
 
     let res = escrow_client.try_release_funds(&bounty_id, &contributor);
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(Error::ReleasePaused)));
 
     escrow_client.set_paused(&None, &Some(false), &None);
     let flags = escrow_client.get_pause_flags();
@@ -126,7 +126,7 @@ fn test_granular_pause_refund() {
     assert_eq!(flags.refund_paused, true);
 
     let res = escrow_client.try_refund(&bounty_id);
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(Error::RefundPaused)));
 
     escrow_client.set_paused(&None, &None, &Some(false));
     let flags = escrow_client.get_pause_flags();
@@ -158,3 +158,166 @@ fn test_mixed_pause_states() {
     assert_eq!(flags.release_paused, false);
     assert_eq!(flags.refund_paused, false);
 }
+
+#[test]
+fn test_set_pause_flags_sets_every_flag() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &admin);
+    let (escrow_client, _) = create_escrow_contract(&env);
+    escrow_client.init(&admin, &token_client.address);
+
+    escrow_client.set_pause_flags(&true, &false, &true);
+    let flags = escrow_client.get_pause_flags();
+    assert!(flags.lock_paused);
+    assert!(!flags.release_paused);
+    assert!(flags.refund_paused);
+
+    escrow_client.set_pause_flags(&false, &true, &false);
+    let flags = escrow_client.get_pause_flags();
+    assert!(!flags.lock_paused);
+    assert!(flags.release_paused);
+    assert!(!flags.refund_paused);
+}
+
+#[test]
+fn test_contract_pause_blocks_lock_release_and_top_up() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &admin);
+    let (escrow_client, _) = create_escrow_contract(&env);
+
+    escrow_client.init(&admin, &token_client.address);
+    token_admin_client.mint(&depositor, &1000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    escrow_client.lock_funds(&depositor, &1, &100, &deadline);
+
+    assert!(!escrow_client.is_paused());
+    escrow_client.pause();
+    assert!(escrow_client.is_paused());
+
+    let res = escrow_client.try_lock_funds(&depositor, &2, &100, &deadline);
+    assert_eq!(res, Err(Ok(Error::ContractPaused)));
+    let res = escrow_client.try_release_funds(&1, &contributor);
+    assert_eq!(res, Err(Ok(Error::ContractPaused)));
+    let res = escrow_client.try_top_up(&depositor, &1, &50);
+    assert_eq!(res, Err(Ok(Error::ContractPaused)));
+
+    escrow_client.unpause();
+    assert!(!escrow_client.is_paused());
+    escrow_client.release_funds(&1, &contributor);
+    assert_eq!(token_client.balance(&contributor), 100);
+}
+
+#[test]
+fn test_contract_pause_blocks_cancel_and_arbiter_refund_paths() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &admin);
+    let (escrow_client, _) = create_escrow_contract(&env);
+
+    escrow_client.init(&admin, &token_client.address);
+    token_admin_client.mint(&depositor, &1000);
+
+    let now = env.ledger().timestamp();
+    escrow_client.lock_funds(&depositor, &1, &100, &(now + 1000));
+    escrow_client.lock_funds_with_arbiter(&depositor, &2, &100, &(now + 1000), &arbiter);
+    escrow_client.lock_funds_vesting(&depositor, &3, &100, &now, &(now + 1000));
+
+    escrow_client.pause();
+    let res = escrow_client.try_cancel_escrow(&1);
+    assert_eq!(res, Err(Ok(Error::ContractPaused)));
+    let res = escrow_client.try_arbiter_refund(&2);
+    assert_eq!(res, Err(Ok(Error::ContractPaused)));
+    let res = escrow_client.try_cancel_vesting(&3);
+    assert_eq!(res, Err(Ok(Error::ContractPaused)));
+    assert_eq!(token_client.balance(&depositor), 700);
+
+    escrow_client.unpause();
+    escrow_client.cancel_escrow(&1);
+    escrow_client.arbiter_refund(&2);
+    escrow_client.cancel_vesting(&3);
+    assert_eq!(token_client.balance(&depositor), 1000);
+}
+
+#[test]
+fn test_contract_pause_blocks_refund_until_unpaused() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &admin);
+    let (escrow_client, _) = create_escrow_contract(&env);
+
+    escrow_client.init(&admin, &token_client.address);
+    token_admin_client.mint(&depositor, &1000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+    escrow_client.lock_funds(&depositor, &1, &100, &deadline);
+
+    escrow_client.pause();
+    env.ledger().set_timestamp(deadline + 1);
+    let res = escrow_client.try_refund(&1);
+    assert_eq!(res, Err(Ok(Error::ContractPaused)));
+
+    // Views keep working so monitoring isn't blinded during a pause
+    let stats = escrow_client.get_aggregate_stats();
+    assert_eq!(stats.count_locked, 1);
+    assert_eq!(stats.count_refunded, 0);
+    let locked = escrow_client.query_escrows_by_status(&EscrowStatus::Locked, &0, &10);
+    assert_eq!(locked.len(), 1);
+    assert_eq!(
+        escrow_client
+            .query_escrows_by_depositor(&depositor, &0, &10)
+            .len(),
+        1
+    );
+
+    escrow_client.unpause();
+    escrow_client.refund(&1);
+
+    assert_eq!(token_client.balance(&depositor), 1000);
+    assert_eq!(
+        escrow_client.get_escrow_info(&1).status,
+        EscrowStatus::Refunded
+    );
+    let stats = escrow_client.get_aggregate_stats();
+    assert_eq!(stats.count_locked, 0);
+    assert_eq!(stats.count_refunded, 1);
+    assert_eq!(stats.total_refunded, 100);
+}
+
+#[test]
+fn test_contract_pause_emits_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &admin);
+    let (escrow_client, escrow_address) = create_escrow_contract(&env);
+    escrow_client.init(&admin, &token_client.address);
+
+    escrow_client.pause();
+    let events = env.events().all();
+    let (contract, topics, _) = events.last().unwrap();
+    assert_eq!(contract, escrow_address);
+    let topic = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("paused"));
+
+    escrow_client.unpause();
+    let (_, topics, _) = env.events().all().last().unwrap();
+    let topic = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("unpaused"));
+}
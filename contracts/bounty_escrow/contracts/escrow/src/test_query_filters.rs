@@ -177,6 +177,462 @@ fn test_query_by_status_pagination_offset_and_limit() {
     );
 }
 
+// paged status query tests
+
+#[test]
+fn test_query_by_status_paged_walks_all_pages_in_lock_order() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+
+    for id in 1..=7u64 {
+        s.escrow.lock_funds(&s.depositor, &id, &100, &dl);
+    }
+    s.escrow.release_funds(&3, &s.contributor);
+
+    let first = s
+        .escrow
+        .query_escrows_by_status_paged(&EscrowStatus::Locked, &0, &2);
+    assert_eq!(first.items.len(), 2);
+    assert_eq!(first.items.get(0).unwrap().bounty_id, 1);
+    assert_eq!(first.items.get(1).unwrap().bounty_id, 2);
+    assert_eq!(first.next_index, Some(2));
+
+    // Bounty 3 is released, so the second page skips over it.
+    let second = s.escrow.query_escrows_by_status_paged(
+        &EscrowStatus::Locked,
+        &first.next_index.unwrap(),
+        &2,
+    );
+    assert_eq!(second.items.get(0).unwrap().bounty_id, 4);
+    assert_eq!(second.items.get(1).unwrap().bounty_id, 5);
+    assert_eq!(second.next_index, Some(5));
+
+    let last = s.escrow.query_escrows_by_status_paged(
+        &EscrowStatus::Locked,
+        &second.next_index.unwrap(),
+        &2,
+    );
+    assert_eq!(last.items.len(), 2);
+    assert_eq!(last.items.get(0).unwrap().bounty_id, 6);
+    assert_eq!(last.items.get(1).unwrap().bounty_id, 7);
+    assert_eq!(last.next_index, None);
+}
+
+#[test]
+fn test_query_by_status_paged_start_past_end_is_empty() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    s.escrow.lock_funds(&s.depositor, &1, &100, &dl);
+
+    let page = s
+        .escrow
+        .query_escrows_by_status_paged(&EscrowStatus::Locked, &10, &5);
+    assert_eq!(page.items.len(), 0);
+    assert_eq!(page.next_index, None);
+}
+
+#[test]
+fn test_query_by_status_paged_caps_limit() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    // Stay clear of the hourly lock limit.
+    s.escrow.set_whitelist(&s.depositor, &true);
+    // The test budget is shared by every lock below.
+    s.env.budget().reset_unlimited();
+    for id in 1..=(MAX_PAGE_SIZE as u64 + 5) {
+        s.escrow.lock_funds(&s.depositor, &id, &10, &dl);
+    }
+
+    let page = s
+        .escrow
+        .query_escrows_by_status_paged(&EscrowStatus::Locked, &0, &u32::MAX);
+    assert_eq!(page.items.len(), MAX_PAGE_SIZE);
+    assert_eq!(page.next_index, Some(MAX_PAGE_SIZE));
+}
+
+#[test]
+fn test_status_pages_of_ten_cover_twenty_five_escrows() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    for id in 1..=25u64 {
+        s.escrow.lock_funds(&s.depositor, &id, &100, &dl);
+    }
+    assert_eq!(
+        s.escrow.get_escrow_count_by_status(&EscrowStatus::Locked),
+        25
+    );
+    assert_eq!(
+        s.escrow.get_escrow_count_by_status(&EscrowStatus::Released),
+        0
+    );
+
+    // Offset pages
+    let mut seen = soroban_sdk::Vec::<u64>::new(&s.env);
+    for offset in [0u32, 10, 20] {
+        let page = s
+            .escrow
+            .query_escrows_by_status(&EscrowStatus::Locked, &offset, &10);
+        let ids = s
+            .escrow
+            .get_escrow_ids_by_status(&EscrowStatus::Locked, &offset, &10);
+        assert_eq!(page.len(), ids.len());
+        for (item, id) in page.iter().zip(ids.iter()) {
+            assert_eq!(item.bounty_id, id);
+            seen.push_back(id);
+        }
+    }
+    assert_eq!(seen.len(), 25);
+    for (i, id) in seen.iter().enumerate() {
+        assert_eq!(id, i as u64 + 1);
+    }
+    assert_eq!(
+        s.escrow
+            .query_escrows_by_status(&EscrowStatus::Locked, &30, &10)
+            .len(),
+        0
+    );
+
+    // Cursor pages return the same ids in the same order
+    let mut cursor = Some(0u32);
+    let mut sizes = soroban_sdk::Vec::<u32>::new(&s.env);
+    let mut next_id = 1u64;
+    while let Some(start) = cursor {
+        let page = s
+            .escrow
+            .get_escrow_ids_by_status_paged(&EscrowStatus::Locked, &start, &10);
+        sizes.push_back(page.ids.len());
+        for id in page.ids.iter() {
+            assert_eq!(id, next_id);
+            next_id += 1;
+        }
+        cursor = page.next_index;
+    }
+    assert_eq!(sizes, soroban_sdk::vec![&s.env, 10u32, 10, 5]);
+}
+
+// depositor paging tests
+#[test]
+fn test_query_by_depositor_paged_walks_pages_in_lock_order() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    let other = Address::generate(&s.env);
+    s.token_admin.mint(&other, &1_000);
+    for id in 1..=12u64 {
+        s.escrow.lock_funds(&s.depositor, &id, &100, &dl);
+    }
+    s.escrow.lock_funds(&other, &99, &100, &dl);
+    assert_eq!(s.escrow.get_depositor_escrow_count(&s.depositor), 12);
+    assert_eq!(s.escrow.get_depositor_escrow_count(&other), 1);
+    assert_eq!(
+        s.escrow
+            .get_depositor_escrow_count(&Address::generate(&s.env)),
+        0
+    );
+
+    let first = s
+        .escrow
+        .query_escrows_by_depositor_paged(&s.depositor, &0, &5);
+    assert_eq!(first.items.len(), 5);
+    assert_eq!(first.items.get(0).unwrap().bounty_id, 1);
+    assert_eq!(first.next_index, Some(5));
+    let second = s
+        .escrow
+        .query_escrows_by_depositor_paged(&s.depositor, &5, &5);
+    assert_eq!(second.items.get(0).unwrap().bounty_id, 6);
+    assert_eq!(second.next_index, Some(10));
+    let last = s
+        .escrow
+        .query_escrows_by_depositor_paged(&s.depositor, &10, &5);
+    assert_eq!(last.items.len(), 2);
+    assert_eq!(last.items.get(1).unwrap().bounty_id, 12);
+    assert_eq!(last.next_index, None);
+}
+
+#[test]
+fn test_query_by_depositor_offset_past_end_is_empty() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    s.escrow.lock_funds(&s.depositor, &1, &100, &dl);
+
+    let page = s
+        .escrow
+        .query_escrows_by_depositor_paged(&s.depositor, &20, &5);
+    assert_eq!(page.items.len(), 0);
+    assert_eq!(page.next_index, None);
+    let page = s
+        .escrow
+        .query_escrows_by_depositor_paged(&s.depositor, &u32::MAX, &u32::MAX);
+    assert_eq!(page.items.len(), 0);
+    assert_eq!(
+        s.escrow
+            .query_escrows_by_depositor(&s.depositor, &u32::MAX, &u32::MAX)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_query_by_depositor_and_status_intersects_filters() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    let other = Address::generate(&s.env);
+    s.token_admin.mint(&other, &1_000);
+    for id in 1..=4u64 {
+        s.escrow.lock_funds(&s.depositor, &id, &100, &dl);
+    }
+    s.escrow.lock_funds(&other, &5, &100, &dl);
+    s.escrow.release_funds(&2, &s.contributor);
+    s.escrow.release_funds(&5, &s.contributor);
+
+    let locked = s
+        .escrow
+        .query_by_depositor_and_status(&s.depositor, &EscrowStatus::Locked);
+    assert_eq!(locked.len(), 3);
+    assert_eq!(locked.get(0).unwrap().bounty_id, 1);
+    assert_eq!(locked.get(1).unwrap().bounty_id, 3);
+    assert_eq!(locked.get(2).unwrap().bounty_id, 4);
+
+    let released = s
+        .escrow
+        .query_by_depositor_and_status(&s.depositor, &EscrowStatus::Released);
+    assert_eq!(released.len(), 1);
+    assert_eq!(released.get(0).unwrap().bounty_id, 2);
+    assert_eq!(released.get(0).unwrap().escrow.amount, 100);
+
+    // Known depositor, nothing in that status
+    assert_eq!(
+        s.escrow
+            .query_by_depositor_and_status(&other, &EscrowStatus::Locked)
+            .len(),
+        0
+    );
+}
+
+// existence tests
+#[test]
+fn test_escrow_exists_and_get_status_handle_missing_ids() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    assert!(!s.escrow.escrow_exists(&1));
+    assert_eq!(s.escrow.get_status(&1), None);
+
+    s.escrow.lock_funds(&s.depositor, &1, &100, &dl);
+    assert!(s.escrow.escrow_exists(&1));
+    assert_eq!(s.escrow.get_status(&1), Some(EscrowStatus::Locked));
+
+    s.escrow.release_funds(&1, &s.contributor);
+    assert_eq!(s.escrow.get_status(&1), Some(EscrowStatus::Released));
+    assert!(!s.escrow.escrow_exists(&2));
+}
+
+// combined filter tests
+fn empty_filter(env: &Env) -> EscrowFilter {
+    EscrowFilter {
+        statuses: Vec::new(env),
+        min_amount: None,
+        max_amount: None,
+        min_deadline: None,
+        max_deadline: None,
+        depositor: None,
+    }
+}
+
+#[test]
+fn test_query_escrows_combines_filters() {
+    let s = Setup::new();
+    let other = Address::generate(&s.env);
+    s.token_admin.mint(&other, &10_000);
+    let dl = s.env.ledger().timestamp() + 1000;
+
+    s.escrow.lock_funds(&s.depositor, &1, &100, &dl);
+    s.escrow.lock_funds(&s.depositor, &2, &500, &(dl + 1000));
+    s.escrow.lock_funds(&s.depositor, &3, &900, &dl);
+    s.escrow.lock_funds(&other, &4, &500, &dl);
+    s.escrow.release_funds(&3, &s.contributor);
+
+    let mut filter = empty_filter(&s.env);
+    filter.statuses = vec![&s.env, EscrowStatus::Locked];
+    filter.min_amount = Some(200);
+    filter.max_deadline = Some(dl);
+    let results = s
+        .escrow
+        .query_escrows(&filter, &SortBy::CreatedAsc, &0, &10);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results.get(0).unwrap().bounty_id, 4);
+
+    filter.depositor = Some(s.depositor.clone());
+    filter.max_deadline = None;
+    let results = s
+        .escrow
+        .query_escrows(&filter, &SortBy::CreatedAsc, &0, &10);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results.get(0).unwrap().bounty_id, 2);
+}
+
+#[test]
+fn test_query_escrows_single_field_and_paging() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    for id in 1..=4u64 {
+        s.escrow.lock_funds(&s.depositor, &id, &100, &dl);
+    }
+    s.escrow.release_funds(&2, &s.contributor);
+
+    let mut filter = empty_filter(&s.env);
+    filter.statuses = vec![&s.env, EscrowStatus::Locked];
+    let page = s.escrow.query_escrows(&filter, &SortBy::CreatedAsc, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().bounty_id, 3);
+
+    // No criteria at all matches everything
+    assert_eq!(
+        s.escrow
+            .query_escrows(&empty_filter(&s.env), &SortBy::CreatedAsc, &0, &10)
+            .len(),
+        4
+    );
+}
+
+#[test]
+fn test_query_escrows_no_match_returns_empty() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    s.escrow.lock_funds(&s.depositor, &1, &100, &dl);
+
+    let mut filter = empty_filter(&s.env);
+    filter.statuses = vec![&s.env, EscrowStatus::Refunded];
+    filter.max_amount = Some(1_000);
+    assert_eq!(
+        s.escrow
+            .query_escrows(&filter, &SortBy::CreatedAsc, &0, &10)
+            .len(),
+        0
+    );
+
+    let mut filter = empty_filter(&s.env);
+    filter.depositor = Some(Address::generate(&s.env));
+    assert_eq!(
+        s.escrow
+            .query_escrows(&filter, &SortBy::CreatedAsc, &0, &10)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_query_escrows_sorts_before_paging() {
+    let s = Setup::new();
+    let base = s.env.ledger().timestamp();
+    s.escrow.lock_funds(&s.depositor, &1, &300, &(base + 3000));
+    s.escrow.lock_funds(&s.depositor, &2, &500, &(base + 1000));
+    s.escrow.lock_funds(&s.depositor, &3, &100, &(base + 2000));
+    s.escrow.lock_funds(&s.depositor, &4, &500, &(base + 4000));
+
+    let ids = |sort: SortBy, offset: u32| -> Vec<u64> {
+        let mut ids = Vec::new(&s.env);
+        for e in s
+            .escrow
+            .query_escrows(&empty_filter(&s.env), &sort, &offset, &2)
+            .iter()
+        {
+            ids.push_back(e.bounty_id);
+        }
+        ids
+    };
+    // Page 2 continues where page 1 stopped; equal amounts keep lock order
+    assert_eq!(ids(SortBy::AmountDesc, 0), vec![&s.env, 2u64, 4]);
+    assert_eq!(ids(SortBy::AmountDesc, 2), vec![&s.env, 1u64, 3]);
+    assert_eq!(ids(SortBy::DeadlineAsc, 0), vec![&s.env, 2u64, 3]);
+    assert_eq!(ids(SortBy::DeadlineAsc, 2), vec![&s.env, 1u64, 4]);
+    assert_eq!(ids(SortBy::CreatedDesc, 0), vec![&s.env, 4u64, 3]);
+    assert_eq!(ids(SortBy::CreatedAsc, 2), vec![&s.env, 3u64, 4]);
+}
+
+#[test]
+fn test_query_escrows_sort_rejects_oversized_match_set() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    s.escrow.set_whitelist(&s.depositor, &true);
+    s.env.budget().reset_unlimited();
+    for id in 1..=(MAX_SORTED_RESULTS as u64 + 1) {
+        s.escrow.lock_funds(&s.depositor, &id, &10, &dl);
+    }
+
+    let res = s
+        .escrow
+        .try_query_escrows(&empty_filter(&s.env), &SortBy::AmountDesc, &0, &10);
+    assert_eq!(res, Err(Ok(Error::LimitExceeded)));
+
+    // Lock order needs no sorting, and a narrower filter fits under the cap
+    let all = s
+        .escrow
+        .query_escrows(&empty_filter(&s.env), &SortBy::CreatedDesc, &0, &10);
+    assert_eq!(all.get(0).unwrap().bounty_id, MAX_SORTED_RESULTS as u64 + 1);
+    s.escrow.release_funds(&7, &s.contributor);
+    let mut filter = empty_filter(&s.env);
+    filter.statuses = vec![&s.env, EscrowStatus::Released];
+    let sorted = s
+        .escrow
+        .query_escrows(&filter, &SortBy::DeadlineAsc, &0, &10);
+    assert_eq!(sorted.len(), 1);
+    assert_eq!(sorted.get(0).unwrap().bounty_id, 7);
+}
+
+#[test]
+fn test_escrows_expiring_within_window() {
+    let s = Setup::new();
+    s.escrow.set_whitelist(&s.depositor, &true);
+    s.env.ledger().set_timestamp(10_000);
+    s.escrow.lock_funds(&s.depositor, &1, &100, &10_500);
+    s.escrow.lock_funds(&s.depositor, &2, &100, &10_100);
+    s.escrow.lock_funds(&s.depositor, &3, &100, &20_000);
+    s.escrow.lock_funds(&s.depositor, &4, &100, &10_300);
+    s.escrow.lock_funds(&s.depositor, &5, &100, &10_050);
+    s.escrow.release_funds(&4, &s.contributor);
+
+    let soon = s.escrow.get_escrows_expiring_within(&500, &10);
+    assert_eq!(soon.len(), 3);
+    assert_eq!(soon.get(0).unwrap().bounty_id, 5);
+    assert_eq!(soon.get(1).unwrap().bounty_id, 2);
+    assert_eq!(soon.get(2).unwrap().bounty_id, 1);
+    assert_eq!(s.escrow.count_escrows_expiring_within(&500), 3);
+    assert_eq!(s.escrow.get_escrows_expiring_within(&500, &1).len(), 1);
+
+    // The window moves with the ledger; a deadline exactly at now has passed
+    s.env.ledger().set_timestamp(10_100);
+    let soon = s.escrow.get_escrows_expiring_within(&500, &10);
+    assert_eq!(soon.len(), 1);
+    assert_eq!(soon.get(0).unwrap().bounty_id, 1);
+    assert_eq!(s.escrow.count_escrows_expiring_within(&0), 0);
+    assert_eq!(s.escrow.count_escrows_expiring_within(&u64::MAX), 2);
+}
+
+#[test]
+fn test_escrows_expiring_soon_orders_by_deadline_then_id() {
+    let s = Setup::new();
+    s.escrow.set_whitelist(&s.depositor, &true);
+    s.env.ledger().set_timestamp(10_000);
+    s.escrow.lock_funds(&s.depositor, &9, &100, &10_200);
+    s.escrow.lock_funds(&s.depositor, &5, &100, &12_000);
+    s.escrow.lock_funds(&s.depositor, &3, &100, &10_200);
+    s.escrow.lock_funds(&s.depositor, &7, &100, &10_100);
+    s.escrow.lock_funds(&s.depositor, &8, &100, &10_050);
+    s.escrow.release_funds(&8, &s.contributor);
+    s.env.ledger().set_timestamp(10_150);
+
+    let ids = |limit: u32| -> Vec<u64> {
+        let mut ids = Vec::new(&s.env);
+        for e in s.escrow.get_escrows_expiring_soon(&limit).iter() {
+            ids.push_back(e.bounty_id);
+        }
+        ids
+    };
+    // Already expired escrows lead; released ones are left out
+    assert_eq!(ids(10), vec![&s.env, 7u64, 3, 9, 5]);
+    assert_eq!(ids(3), vec![&s.env, 7u64, 3, 9]);
+    assert_eq!(ids(0).len(), 0);
+}
+
 // amount filter tests
 #[test]
 fn test_query_by_amount_range_returns_matching_escrows() {
@@ -290,6 +746,104 @@ fn test_query_by_depositor_returns_empty_for_unknown_address() {
     assert_eq!(results.len(), 0);
 }
 
+// contributor filter tests
+
+#[test]
+fn test_query_by_contributor_tracks_every_release_path() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    let other = Address::generate(&s.env);
+
+    s.escrow.lock_funds(&s.depositor, &1, &100, &dl);
+    s.escrow.lock_funds(&s.depositor, &2, &200, &dl);
+    s.escrow.lock_funds(&s.depositor, &3, &300, &dl);
+    s.escrow.lock_funds(&s.depositor, &4, &400, &dl);
+
+    s.escrow.release_funds(&3, &s.contributor);
+    s.escrow.release_split(
+        &1,
+        &soroban_sdk::vec![
+            &s.env,
+            (s.contributor.clone(), 5_000u32),
+            (other.clone(), 5_000u32)
+        ],
+    );
+
+    let paid = s
+        .escrow
+        .query_escrows_by_contributor(&s.contributor, &0, &10);
+    assert_eq!(paid.len(), 2);
+    assert_eq!(paid.get(0).unwrap().bounty_id, 3);
+    assert_eq!(paid.get(1).unwrap().bounty_id, 1);
+
+    let paid = s.escrow.query_escrows_by_contributor(&other, &0, &10);
+    assert_eq!(paid.len(), 1);
+    assert_eq!(paid.get(0).unwrap().bounty_id, 1);
+
+    // Paging works the same way as the depositor index
+    let page = s
+        .escrow
+        .query_escrows_by_contributor(&s.contributor, &1, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().bounty_id, 1);
+
+    // The id-only view lists the same bounties
+    let ids = s.escrow.get_contributor_bounty_ids(&s.contributor, &0, &10);
+    assert_eq!(ids, soroban_sdk::vec![&s.env, 3u64, 1]);
+    let ids = s.escrow.get_contributor_bounty_ids(&other, &0, &10);
+    assert_eq!(ids, soroban_sdk::vec![&s.env, 1u64]);
+    let ids = s.escrow.get_contributor_bounty_ids(&s.contributor, &5, &10);
+    assert_eq!(ids.len(), 0);
+}
+
+#[test]
+fn test_query_by_contributor_caps_limit() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    s.escrow.set_whitelist(&s.depositor, &true);
+    s.env.budget().reset_unlimited();
+    for id in 1..=(MAX_PAGE_SIZE as u64 + 5) {
+        s.escrow.lock_funds(&s.depositor, &id, &10, &dl);
+        s.escrow
+            .assign_contributor(&s.depositor, &id, &s.contributor);
+    }
+
+    let page = s
+        .escrow
+        .query_escrows_by_contributor(&s.contributor, &0, &u32::MAX);
+    assert_eq!(page.len(), MAX_PAGE_SIZE);
+
+    let ids = s
+        .escrow
+        .get_contributor_bounty_ids(&s.contributor, &0, &u32::MAX);
+    assert_eq!(ids.len(), MAX_PAGE_SIZE);
+    assert_eq!(ids.get(0), Some(1));
+}
+
+#[test]
+fn test_query_by_contributor_lists_bounty_once_across_partial_releases() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    s.escrow.lock_funds(&s.depositor, &1, &300, &dl);
+
+    s.escrow.partial_release(&1, &s.contributor, &100);
+    s.escrow.partial_release(&1, &s.contributor, &200);
+
+    let paid = s
+        .escrow
+        .query_escrows_by_contributor(&s.contributor, &0, &10);
+    assert_eq!(paid.len(), 1);
+    assert_eq!(paid.get(0).unwrap().escrow.status, EscrowStatus::Released);
+
+    let unpaid = Address::generate(&s.env);
+    assert_eq!(
+        s.escrow
+            .query_escrows_by_contributor(&unpaid, &0, &10)
+            .len(),
+        0
+    );
+}
+
 // get_escrow_ids_by_status tests
 
 #[test]
@@ -387,3 +941,138 @@ fn test_aggregate_stats_reflects_correct_counts_after_lifecycle() {
     assert_eq!(stats.total_refunded, 300); // bounty 3
     assert_eq!(stats.total_locked, 400); // bounty 4
 }
+
+// batch info tests
+
+#[test]
+fn test_get_escrow_info_batch_preserves_order_and_marks_missing() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    s.escrow.lock_funds(&s.depositor, &1, &100, &dl);
+    s.escrow.lock_funds(&s.depositor, &2, &200, &dl);
+    s.escrow.release_funds(&2, &s.contributor);
+
+    let infos = s
+        .escrow
+        .get_escrow_info_batch(&soroban_sdk::vec![&s.env, 2u64, 99u64, 1u64]);
+    assert_eq!(infos.len(), 3);
+
+    let second = infos.get(0).unwrap().unwrap();
+    assert_eq!(second.amount, 200);
+    assert_eq!(second.status, EscrowStatus::Released);
+    assert_eq!(infos.get(1).unwrap(), None);
+    assert_eq!(infos.get(2).unwrap().unwrap().amount, 100);
+}
+
+#[test]
+fn test_get_escrow_info_batch_rejects_oversized_request() {
+    let s = Setup::new();
+    let mut ids = soroban_sdk::Vec::new(&s.env);
+    for id in 0..=(MAX_INFO_BATCH_SIZE as u64) {
+        ids.push_back(id);
+    }
+    let res = s.escrow.try_get_escrow_info_batch(&ids);
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+
+    ids.pop_back();
+    assert_eq!(
+        s.escrow.get_escrow_info_batch(&ids).len(),
+        MAX_INFO_BATCH_SIZE
+    );
+}
+
+// metadata tests
+fn metadata(env: &Env, repo: &str, issue_number: u64) -> EscrowMetadata {
+    EscrowMetadata {
+        repo: Bytes::from_slice(env, repo.as_bytes()),
+        issue_number,
+        title_hash: BytesN::from_array(env, &[7u8; 32]),
+    }
+}
+
+#[test]
+fn test_query_by_repo_groups_escrows_with_metadata() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    let core = metadata(&s.env, "grainlify/core", 12);
+    s.escrow
+        .lock_funds_with_metadata(&s.depositor, &1, &100, &dl, &core);
+    s.escrow.lock_funds_with_metadata(
+        &s.depositor,
+        &2,
+        &200,
+        &dl,
+        &metadata(&s.env, "grainlify/web", 3),
+    );
+    s.escrow.lock_funds_with_metadata(
+        &s.depositor,
+        &3,
+        &300,
+        &dl,
+        &metadata(&s.env, "grainlify/core", 40),
+    );
+    s.escrow.lock_funds(&s.depositor, &4, &400, &dl);
+
+    assert_eq!(s.escrow.get_escrow_metadata(&1), Some(core.clone()));
+    assert_eq!(s.escrow.get_escrow_metadata(&4), None);
+
+    let core_hash: BytesN<32> = s.env.crypto().sha256(&core.repo).into();
+    let results = s.escrow.query_escrows_by_repo(&core_hash, &0, &10);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results.get(0).unwrap().bounty_id, 1);
+    assert_eq!(results.get(1).unwrap().bounty_id, 3);
+}
+
+#[test]
+fn test_query_by_repo_caps_limit() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    let core = metadata(&s.env, "grainlify/core", 12);
+    s.escrow.set_whitelist(&s.depositor, &true);
+    s.env.budget().reset_unlimited();
+    for id in 1..=(MAX_PAGE_SIZE as u64 + 5) {
+        s.escrow
+            .lock_funds_with_metadata(&s.depositor, &id, &10, &dl, &core);
+    }
+
+    let core_hash: BytesN<32> = s.env.crypto().sha256(&core.repo).into();
+    let page = s.escrow.query_escrows_by_repo(&core_hash, &0, &u32::MAX);
+    assert_eq!(page.len(), MAX_PAGE_SIZE);
+}
+
+#[test]
+fn test_query_by_project_caps_limit() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    s.escrow.set_whitelist(&s.depositor, &true);
+    s.env.budget().reset_unlimited();
+    for id in 1..=(MAX_PAGE_SIZE as u64 + 5) {
+        s.escrow.lock_funds(&s.depositor, &id, &10, &dl);
+    }
+
+    // Project 0 holds every escrow locked without a project
+    let page = s.escrow.query_escrows_by_project(&0, &0, &u32::MAX);
+    assert_eq!(page.len(), MAX_PAGE_SIZE);
+}
+
+#[test]
+fn test_update_metadata_moves_escrow_between_repos() {
+    let s = Setup::new();
+    let dl = s.env.ledger().timestamp() + 1000;
+    let core = metadata(&s.env, "grainlify/core", 12);
+    let web = metadata(&s.env, "grainlify/web", 5);
+    s.escrow
+        .lock_funds_with_metadata(&s.depositor, &1, &100, &dl, &core);
+
+    s.escrow.update_metadata(&1, &web);
+    assert_eq!(s.escrow.get_escrow_metadata(&1), Some(web.clone()));
+    let core_hash: BytesN<32> = s.env.crypto().sha256(&core.repo).into();
+    let web_hash: BytesN<32> = s.env.crypto().sha256(&web.repo).into();
+    assert_eq!(s.escrow.query_escrows_by_repo(&core_hash, &0, &10).len(), 0);
+    assert_eq!(s.escrow.query_escrows_by_repo(&web_hash, &0, &10).len(), 1);
+
+    // Frozen once the escrow is no longer Locked
+    s.escrow.release_funds(&1, &s.contributor);
+    let res = s.escrow.try_update_metadata(&1, &core);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
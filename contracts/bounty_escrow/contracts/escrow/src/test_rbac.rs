@@ -2,8 +2,8 @@
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    token, vec, Address, Env, String,
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    token, vec, Address, Env, IntoVal, String,
 };
 
 struct RbacSetup<'a> {
@@ -202,3 +202,100 @@ fn test_random_cannot_lock_funds_for_depositor() {
     // This should panic: no auth mocked, depositor.require_auth() will fail
     client2.lock_funds(&depositor2, &1u64, &1000i128, &3600);
 }
+
+// ─────────────────────────────────────────────────────────
+// Admin Transfer Tests
+// ─────────────────────────────────────────────────────────
+
+fn stored_admin(setup: &RbacSetup) -> Address {
+    setup.env.as_contract(&setup.client.address, || {
+        setup
+            .env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::Admin)
+            .unwrap()
+    })
+}
+
+#[test]
+fn test_admin_transfer_requires_acceptance() {
+    let setup = RbacSetup::new();
+    let new_admin = Address::generate(&setup.env);
+    let original = stored_admin(&setup);
+
+    setup.client.propose_admin(&new_admin);
+    // The old admin keeps control until the candidate accepts
+    assert_eq!(setup.client.get_admin(), original);
+    assert_eq!(setup.client.get_pending_admin(), Some(new_admin.clone()));
+
+    setup.client.accept_admin(&new_admin);
+    let auths = setup.env.auths();
+    assert_eq!(auths[0].0, new_admin);
+    assert_eq!(setup.client.get_admin(), new_admin);
+    assert_eq!(setup.client.get_pending_admin(), None);
+
+    // The handover is one-shot
+    let res = setup.client.try_accept_admin(&new_admin);
+    assert_eq!(res, Err(Ok(Error::NotPendingAdmin)));
+}
+
+#[test]
+fn test_accept_admin_without_proposal_fails() {
+    let setup = RbacSetup::new();
+    let original = stored_admin(&setup);
+    let res = setup.client.try_accept_admin(&setup.random);
+    assert_eq!(res, Err(Ok(Error::NotPendingAdmin)));
+    assert_eq!(stored_admin(&setup), original);
+}
+
+#[test]
+fn test_accept_admin_by_wrong_address_fails() {
+    let setup = RbacSetup::new();
+    let candidate = Address::generate(&setup.env);
+    let original = stored_admin(&setup);
+    setup.client.propose_admin(&candidate);
+
+    let res = setup.client.try_accept_admin(&setup.random);
+    assert_eq!(res, Err(Ok(Error::NotPendingAdmin)));
+    assert_eq!(stored_admin(&setup), original);
+    assert_eq!(setup.client.get_pending_admin(), Some(candidate));
+}
+
+#[test]
+#[should_panic]
+fn test_accept_admin_rejects_other_signers() {
+    let setup = RbacSetup::new();
+    let candidate = Address::generate(&setup.env);
+    setup.client.propose_admin(&candidate);
+
+    // Only the random address signs, so the candidate's auth is missing
+    setup.env.mock_auths(&[MockAuth {
+        address: &setup.random,
+        invoke: &MockAuthInvoke {
+            contract: &setup.client.address,
+            fn_name: "accept_admin",
+            args: (candidate.clone(),).into_val(&setup.env),
+            sub_invokes: &[],
+        },
+    }]);
+    setup.client.accept_admin(&candidate);
+}
+
+#[test]
+fn test_cancel_admin_transfer() {
+    let setup = RbacSetup::new();
+    let candidate = Address::generate(&setup.env);
+    let original = stored_admin(&setup);
+    setup.client.propose_admin(&candidate);
+
+    setup.client.cancel_admin_transfer();
+    assert_eq!(setup.client.get_pending_admin(), None);
+
+    let res = setup.client.try_accept_admin(&candidate);
+    assert_eq!(res, Err(Ok(Error::NotPendingAdmin)));
+    assert_eq!(stored_admin(&setup), original);
+
+    let res = setup.client.try_cancel_admin_transfer();
+    assert_eq!(res, Err(Ok(Error::NotPendingAdmin)));
+}
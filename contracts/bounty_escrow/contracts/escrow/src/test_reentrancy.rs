@@ -0,0 +1,205 @@
+#![cfg(test)]
+
+//! Reentrancy guard tests.
+//!
+//! `MaliciousToken` stands in for the escrow token and tries to call back
+//! into the escrow from inside `transfer`, before the outer call returns.
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+#[contract]
+pub struct MaliciousToken;
+
+#[contractimpl]
+impl MaliciousToken {
+    /// Arm the token: the next `transfer` tries to release `bounty_id` to `attacker`.
+    pub fn arm(env: Env, escrow: Address, bounty_id: u64, attacker: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("target"), &(escrow, bounty_id, attacker));
+    }
+
+    pub fn transfer(env: Env, _from: Address, _to: Address, _amount: i128) {
+        let target: Option<(Address, u64, Address)> =
+            env.storage().instance().get(&symbol_short!("target"));
+        if let Some((escrow, bounty_id, attacker)) = target {
+            env.storage().instance().remove(&symbol_short!("target"));
+            let client = BountyEscrowContractClient::new(&env, &escrow);
+            let reentered = client.try_release_funds(&bounty_id, &attacker).is_ok();
+            env.storage()
+                .instance()
+                .set(&symbol_short!("reentered"), &reentered);
+        }
+    }
+
+    pub fn reentered(env: Env) -> Option<bool> {
+        env.storage().instance().get(&symbol_short!("reentered"))
+    }
+}
+
+struct Setup {
+    env: Env,
+    depositor: Address,
+    attacker: Address,
+    token: MaliciousTokenClient<'static>,
+    escrow: BountyEscrowContractClient<'static>,
+}
+
+impl Setup {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let attacker = Address::generate(&env);
+        let token_id = env.register_contract(None, MaliciousToken);
+        let token = MaliciousTokenClient::new(&env, &token_id);
+        let escrow_id = env.register_contract(None, BountyEscrowContract);
+        let escrow = BountyEscrowContractClient::new(&env, &escrow_id);
+        escrow.init(&admin, &token_id);
+        Setup {
+            env,
+            depositor,
+            attacker,
+            token,
+            escrow,
+        }
+    }
+
+    fn set_guard(&self) {
+        self.env.as_contract(&self.escrow.address, || {
+            self.env
+                .storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &true);
+        });
+    }
+}
+
+#[test]
+fn test_malicious_token_cannot_reenter_lock_funds() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    s.escrow.lock_funds(&s.depositor, &1, &1_000, &deadline);
+
+    s.token.arm(&s.escrow.address, &1, &s.attacker);
+    s.escrow.lock_funds(&s.depositor, &2, &500, &deadline);
+
+    assert_eq!(s.token.reentered(), Some(false));
+    assert_eq!(s.escrow.get_escrow_info(&1).status, EscrowStatus::Locked);
+    assert_eq!(s.escrow.get_escrow_info(&2).status, EscrowStatus::Locked);
+}
+
+#[test]
+fn test_malicious_token_cannot_reenter_release_funds() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    let contributor = Address::generate(&s.env);
+    s.escrow.lock_funds(&s.depositor, &1, &1_000, &deadline);
+    s.escrow.lock_funds(&s.depositor, &2, &1_000, &deadline);
+
+    s.token.arm(&s.escrow.address, &2, &s.attacker);
+    s.escrow.release_funds(&1, &contributor);
+
+    assert_eq!(s.token.reentered(), Some(false));
+    assert_eq!(s.escrow.get_escrow_info(&1).status, EscrowStatus::Released);
+    assert_eq!(s.escrow.get_escrow_info(&2).status, EscrowStatus::Locked);
+}
+
+#[test]
+fn test_malicious_token_cannot_reenter_refund() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 100;
+    s.escrow.lock_funds(&s.depositor, &1, &1_000, &deadline);
+    s.escrow.lock_funds(&s.depositor, &2, &1_000, &deadline);
+
+    s.env.ledger().set_timestamp(deadline + 1);
+    s.token.arm(&s.escrow.address, &2, &s.attacker);
+    s.escrow.refund(&1);
+
+    assert_eq!(s.token.reentered(), Some(false));
+    assert_eq!(s.escrow.get_escrow_info(&1).status, EscrowStatus::Refunded);
+    assert_eq!(s.escrow.get_escrow_info(&2).status, EscrowStatus::Locked);
+}
+
+#[test]
+fn test_malicious_token_cannot_reenter_partial_release() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    let contributor = Address::generate(&s.env);
+    s.escrow.lock_funds(&s.depositor, &1, &1_000, &deadline);
+    s.escrow.lock_funds(&s.depositor, &2, &1_000, &deadline);
+
+    s.token.arm(&s.escrow.address, &2, &s.attacker);
+    s.escrow.partial_release(&1, &contributor, &400);
+
+    assert_eq!(s.token.reentered(), Some(false));
+    assert_eq!(s.escrow.get_escrow_info(&1).remaining_amount, 600);
+    assert_eq!(s.escrow.get_escrow_info(&2).status, EscrowStatus::Locked);
+}
+
+#[test]
+fn test_malicious_token_cannot_reenter_claim() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 1000;
+    let contributor = Address::generate(&s.env);
+    s.escrow.lock_funds(&s.depositor, &1, &1_000, &deadline);
+    s.escrow.lock_funds(&s.depositor, &2, &1_000, &deadline);
+    s.escrow.authorize_claim(&1, &contributor);
+
+    s.token.arm(&s.escrow.address, &2, &s.attacker);
+    s.escrow.claim(&1);
+
+    assert_eq!(s.token.reentered(), Some(false));
+    assert_eq!(s.escrow.get_escrow_info(&1).status, EscrowStatus::Released);
+    assert_eq!(s.escrow.get_escrow_info(&2).status, EscrowStatus::Locked);
+}
+
+#[test]
+fn test_guarded_entrypoints_reject_nested_calls() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 100;
+    s.escrow.lock_funds(&s.depositor, &1, &1_000, &deadline);
+    s.env.ledger().set_timestamp(deadline + 1);
+
+    s.set_guard();
+    let res = s
+        .escrow
+        .try_lock_funds(&s.depositor, &2, &1_000, &(deadline + 100));
+    assert_eq!(res, Err(Ok(Error::ReentrancyDetected)));
+    let res = s.escrow.try_release_funds(&1, &s.attacker);
+    assert_eq!(res, Err(Ok(Error::ReentrancyDetected)));
+    let res = s.escrow.try_refund(&1);
+    assert_eq!(res, Err(Ok(Error::ReentrancyDetected)));
+    let res = s.escrow.try_partial_release(&1, &s.attacker, &100);
+    assert_eq!(res, Err(Ok(Error::ReentrancyDetected)));
+    let res = s.escrow.try_claim(&1);
+    assert_eq!(res, Err(Ok(Error::ReentrancyDetected)));
+    let res = s.escrow.try_top_up(&s.depositor, &1, &100);
+    assert_eq!(res, Err(Ok(Error::ReentrancyDetected)));
+    let res = s.escrow.try_cancel_escrow(&1);
+    assert_eq!(res, Err(Ok(Error::ReentrancyDetected)));
+}
+
+#[test]
+fn test_guard_is_cleared_after_each_call() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 100;
+    let contributor = Address::generate(&s.env);
+    s.escrow.lock_funds(&s.depositor, &1, &1_000, &deadline);
+    s.escrow.lock_funds(&s.depositor, &2, &1_000, &deadline);
+    s.escrow.release_funds(&1, &contributor);
+
+    // A failed call must not leave the guard behind either
+    let res = s.escrow.try_refund(&2);
+    assert_eq!(res, Err(Ok(Error::DeadlineNotPassed)));
+
+    s.env.ledger().set_timestamp(deadline + 1);
+    s.escrow.refund(&2);
+    assert_eq!(s.escrow.get_escrow_info(&2).status, EscrowStatus::Refunded);
+}
@@ -1,5 +1,6 @@
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map, Symbol,
+    contract, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env, Map, Symbol,
+    Vec,
 };
 
 // --- Enums y Structs permanecen igual ---
@@ -12,6 +13,7 @@ pub enum ProposalStatus {
     Rejected,
     Executed,
     Expired,
+    Cancelled,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -26,9 +28,23 @@ pub enum VoteType {
 #[contracttype]
 pub enum VotingScheme {
     OnePersonOneVote,
+    /// Power is the governance tokens a voter has staked with `stake`, as it
+    /// stood when the proposal was created. Tokens bought or staked after
+    /// `create_proposal` ran do not count towards that proposal, and
+    /// unstaking afterwards does not reduce it.
     TokenWeighted,
 }
 
+/// What `execute_proposal` would decide on the current tallies; see
+/// `preview_outcome`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ProposalOutcome {
+    Pass,
+    FailQuorum,
+    FailThreshold,
+}
+
 #[derive(Clone, Debug)]
 #[contracttype]
 pub struct Proposal {
@@ -45,6 +61,8 @@ pub struct Proposal {
     pub votes_against: i128,
     pub votes_abstain: i128,
     pub total_votes: u32,
+    /// Stake epoch at creation; `TokenWeighted` votes use stake as of it.
+    pub snapshot: u32,
 }
 
 #[derive(Clone, Debug)]
@@ -56,6 +74,16 @@ pub struct GovernanceConfig {
     pub approval_threshold: u32,
     pub min_proposal_stake: i128,
     pub voting_scheme: VotingScheme,
+    /// When set, a `Rejected` proposal forfeits its stake to `treasury`.
+    pub slash_rejected_stake: bool,
+    pub treasury: Option<Address>,
+    /// Distinct voters a proposal needs on top of `quorum_percentage`; 0 for
+    /// no floor. Delegators voted for by their delegate do not count.
+    pub min_voters: u32,
+    /// Cap on the power of a single vote under `TokenWeighted`, in basis
+    /// points of the voting supply; 0 or 10000 for no cap. A delegate's vote
+    /// is capped after adding up the power of its delegators.
+    pub max_vote_weight_bps: u32,
 }
 
 #[derive(Clone, Debug)]
@@ -68,11 +96,106 @@ pub struct Vote {
     pub timestamp: u64,
 }
 
+/// One page of `list_proposals` / `list_proposals_by_status`.
+/// `next_cursor` is the id to pass as the next start, or None at the end.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ProposalPage {
+    pub proposals: Vec<Proposal>,
+    pub next_cursor: Option<u32>,
+}
+
+// --- Eventos ---
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ProposalCreated {
+    pub proposal_id: u32,
+    pub proposer: Address,
+    pub new_wasm_hash: BytesN<32>,
+    pub stake: i128,
+    pub voting_start: u64,
+    pub voting_end: u64,
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ProposalExecuted {
+    pub proposal_id: u32,
+    pub new_wasm_hash: BytesN<32>,
+    pub votes_for: i128,
+    pub votes_against: i128,
+    pub votes_abstain: i128,
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ProposalRejected {
+    pub proposal_id: u32,
+    pub quorum_met: bool,
+    pub votes_for: i128,
+    pub votes_against: i128,
+    pub votes_abstain: i128,
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct VoteChanged {
+    pub proposal_id: u32,
+    pub voter: Address,
+    pub old_vote: VoteType,
+    pub new_vote: VoteType,
+    pub voting_power: i128,
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct DelegationChanged {
+    pub delegator: Address,
+    pub delegate: Option<Address>,
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct StakeSlashed {
+    pub proposal_id: u32,
+    pub proposer: Address,
+    pub amount: i128,
+    pub treasury: Address,
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ProposalCancelled {
+    pub proposal_id: u32,
+    pub proposer: Address,
+    pub stake_returned: i128,
+}
+
 // Storage keys
 pub const PROPOSALS: Symbol = symbol_short!("PROPOSALS");
 pub const PROPOSAL_COUNT: Symbol = symbol_short!("PROP_CNT");
 pub const VOTES: Symbol = symbol_short!("VOTES");
 pub const GOVERNANCE_CONFIG: Symbol = symbol_short!("GOV_CFG");
+pub const GOVERNANCE_ADMIN: Symbol = symbol_short!("GOV_ADM");
+pub const GOVERNANCE_TOKEN: Symbol = symbol_short!("GOV_TKN");
+// Stake libre por dirección (Map<Address, i128>)
+pub const STAKES: Symbol = symbol_short!("STAKES");
+// Stake bloqueado por cada propuesta (Map<u32, i128>)
+pub const PROPOSAL_STAKES: Symbol = symbol_short!("PROP_STK");
+// Historial de stake de cada dirección en almacenamiento persistente, bajo la
+// clave (STAKE_HISTORY, dirección): (época, stake total) (Vec<(u32, i128)>)
+pub const STAKE_HISTORY: Symbol = symbol_short!("STK_HIST");
+// Época de stake, sube con cada cambio de stake (u32)
+pub const STAKE_EPOCH: Symbol = symbol_short!("STK_EPOCH");
+// Poder de voto total contra el que se mide el quórum
+pub const VOTING_SUPPLY: Symbol = symbol_short!("VOTE_SUP");
+// Registro de votantes: delegador -> delegado (Map<Address, Address>)
+pub const DELEGATES: Symbol = symbol_short!("DELEGATES");
+// Delegado -> delegadores (Map<Address, Vec<Address>>)
+pub const DELEGATORS: Symbol = symbol_short!("DLGTORS");
+
+// Máximo de propuestas devueltas por página
+pub const MAX_PROPOSAL_PAGE: u32 = 50;
 
 #[soroban_sdk::contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -92,6 +215,15 @@ pub enum Error {
     ProposalNotApproved = 12,
     ExecutionDelayNotMet = 13,
     ProposalExpired = 14,
+    TokenNotSet = 15,
+    InvalidAmount = 16,
+    NoVotingPower = 17,
+    VotingSupplyNotSet = 18,
+    NotVoted = 19,
+    DelegationCycle = 20,
+    NotDelegated = 21,
+    VoteDelegated = 22,
+    TreasuryNotSet = 23,
 }
 
 // --- CLAVE: Añadir macro #[contract] ---
@@ -106,17 +238,117 @@ impl GovernanceContract {
         config: GovernanceConfig,
     ) -> Result<(), Error> {
         admin.require_auth();
-        if config.quorum_percentage > 10000 || config.approval_threshold > 10000 {
-            return Err(Error::InvalidThreshold);
-        }
-        if config.approval_threshold < 5000 {
-            return Err(Error::ThresholdTooLow);
-        }
+        validate_config(&config)?;
         env.storage().instance().set(&GOVERNANCE_CONFIG, &config);
+        env.storage().instance().set(&GOVERNANCE_ADMIN, &admin);
         env.storage().instance().set(&PROPOSAL_COUNT, &0u32);
         Ok(())
     }
 
+    /// Replaces the governance config. Admin only; validated like
+    /// `init_governance`. Proposals already created keep their voting window
+    /// and execution delay.
+    pub fn set_governance_config(env: Env, config: GovernanceConfig) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&GOVERNANCE_ADMIN)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        validate_config(&config)?;
+        env.storage().instance().set(&GOVERNANCE_CONFIG, &config);
+        Ok(())
+    }
+
+    pub fn get_governance_config(env: Env) -> Result<GovernanceConfig, Error> {
+        env.storage()
+            .instance()
+            .get(&GOVERNANCE_CONFIG)
+            .ok_or(Error::NotInitialized)
+    }
+
+    /// Sets the token used for proposal stakes. Admin only.
+    pub fn set_governance_token(env: Env, token: Address) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&GOVERNANCE_ADMIN)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage().instance().set(&GOVERNANCE_TOKEN, &token);
+        Ok(())
+    }
+
+    /// Moves `amount` governance tokens from `staker` into the contract.
+    pub fn stake(env: Env, staker: Address, amount: i128) -> Result<i128, Error> {
+        staker.require_auth();
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&GOVERNANCE_TOKEN)
+            .ok_or(Error::TokenNotSet)?;
+        token::Client::new(&env, &token_addr).transfer(
+            &staker,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let mut stakes: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&STAKES)
+            .unwrap_or(Map::new(&env));
+        let staked = stakes.get(staker.clone()).unwrap_or(0) + amount;
+        stakes.set(staker.clone(), staked);
+        env.storage().instance().set(&STAKES, &stakes);
+        checkpoint_stake(&env, &staker, amount);
+        Ok(staked)
+    }
+
+    /// Returns free (not locked in a proposal) stake back to `staker`.
+    pub fn unstake(env: Env, staker: Address, amount: i128) -> Result<i128, Error> {
+        staker.require_auth();
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let mut stakes: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&STAKES)
+            .unwrap_or(Map::new(&env));
+        let staked = stakes.get(staker.clone()).unwrap_or(0);
+        if staked < amount {
+            return Err(Error::InsufficientStake);
+        }
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&GOVERNANCE_TOKEN)
+            .ok_or(Error::TokenNotSet)?;
+
+        stakes.set(staker.clone(), staked - amount);
+        env.storage().instance().set(&STAKES, &stakes);
+        checkpoint_stake(&env, &staker, -amount);
+        token::Client::new(&env, &token_addr).transfer(
+            &env.current_contract_address(),
+            &staker,
+            &amount,
+        );
+        Ok(staked - amount)
+    }
+
+    pub fn get_stake(env: Env, staker: Address) -> i128 {
+        let stakes: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&STAKES)
+            .unwrap_or(Map::new(&env));
+        stakes.get(staker).unwrap_or(0)
+    }
+
     pub fn create_proposal(
         env: Env,
         proposer: Address,
@@ -130,6 +362,19 @@ impl GovernanceContract {
             .get(&GOVERNANCE_CONFIG)
             .ok_or(Error::NotInitialized)?;
 
+        // El proposer debe tener al menos `min_proposal_stake` libre; se bloquea
+        // mientras la propuesta exista.
+        let mut stakes: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&STAKES)
+            .unwrap_or(Map::new(&env));
+        let staked = stakes.get(proposer.clone()).unwrap_or(0);
+        if staked < config.min_proposal_stake {
+            return Err(Error::InsufficientStake);
+        }
+        let stake = config.min_proposal_stake;
+
         let proposal_id: u32 = env.storage().instance().get(&PROPOSAL_COUNT).unwrap_or(0);
         let current_time = env.ledger().timestamp();
 
@@ -142,11 +387,12 @@ impl GovernanceContract {
             voting_start: current_time,
             voting_end: current_time + config.voting_period,
             execution_delay: config.execution_delay,
-            status: ProposalStatus::Active,
+            status: ProposalStatus::Pending,
             votes_for: 0,
             votes_against: 0,
             votes_abstain: 0,
             total_votes: 0,
+            snapshot: env.storage().instance().get(&STAKE_EPOCH).unwrap_or(0),
         };
 
         let mut proposals: Map<u32, Proposal> = env
@@ -154,12 +400,36 @@ impl GovernanceContract {
             .instance()
             .get(&PROPOSALS)
             .unwrap_or(Map::new(&env));
-        proposals.set(proposal_id, proposal);
+        proposals.set(proposal_id, proposal.clone());
         env.storage().instance().set(&PROPOSALS, &proposals);
         env.storage()
             .instance()
             .set(&PROPOSAL_COUNT, &(proposal_id + 1));
 
+        if stake > 0 {
+            stakes.set(proposer.clone(), staked - stake);
+            env.storage().instance().set(&STAKES, &stakes);
+            let mut locked: Map<u32, i128> = env
+                .storage()
+                .instance()
+                .get(&PROPOSAL_STAKES)
+                .unwrap_or(Map::new(&env));
+            locked.set(proposal_id, stake);
+            env.storage().instance().set(&PROPOSAL_STAKES, &locked);
+        }
+
+        env.events().publish(
+            (symbol_short!("prop_new"), proposal_id),
+            ProposalCreated {
+                proposal_id,
+                proposer,
+                new_wasm_hash: proposal.new_wasm_hash,
+                stake,
+                voting_start: proposal.voting_start,
+                voting_end: proposal.voting_end,
+            },
+        );
+
         Ok(proposal_id)
     }
 
@@ -177,14 +447,19 @@ impl GovernanceContract {
             .ok_or(Error::ProposalsNotFound)?;
         let mut proposal = proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
 
-        if proposal.status != ProposalStatus::Active {
+        if proposal.status != ProposalStatus::Pending && proposal.status != ProposalStatus::Active {
             return Err(Error::ProposalNotActive);
         }
 
         let current_time = env.ledger().timestamp();
+        if current_time < proposal.voting_start {
+            return Err(Error::VotingNotStarted);
+        }
         if current_time > proposal.voting_end {
             return Err(Error::VotingEnded);
         }
+        // Una propuesta pasa a Active con el primer voto dentro de la ventana
+        proposal.status = ProposalStatus::Active;
 
         let mut votes: Map<(u32, Address), Vote> = env
             .storage()
@@ -194,21 +469,49 @@ impl GovernanceContract {
         if votes.contains_key((proposal_id, voter.clone())) {
             return Err(Error::AlreadyVoted);
         }
+        let delegates: Map<Address, Address> = env
+            .storage()
+            .instance()
+            .get(&DELEGATES)
+            .unwrap_or(Map::new(&env));
+        if delegates.contains_key(voter.clone()) {
+            return Err(Error::VoteDelegated);
+        }
 
         let config: GovernanceConfig = env
             .storage()
             .instance()
             .get(&GOVERNANCE_CONFIG)
             .ok_or(Error::NotInitialized)?;
-        let voting_power = match config.voting_scheme {
-            VotingScheme::OnePersonOneVote => 1i128,
-            VotingScheme::TokenWeighted => 100i128, // Simplificado para el test
-        };
+        let mut power = voting_power(&env, &config, &voter, proposal.snapshot)?;
+
+        // El delegado vota también por sus delegadores que aún no votaron.
+        // Se les registra un voto con poder 0 para que no puedan votar otra vez.
+        for delegator in delegators_of(&env, &voter).iter() {
+            if votes.contains_key((proposal_id, delegator.clone())) {
+                continue;
+            }
+            power += voting_power(&env, &config, &delegator, proposal.snapshot)?;
+            votes.set(
+                (proposal_id, delegator.clone()),
+                Vote {
+                    voter: delegator,
+                    proposal_id,
+                    vote_type: vote_type.clone(),
+                    voting_power: 0,
+                    timestamp: current_time,
+                },
+            );
+        }
+        if power <= 0 {
+            return Err(Error::NoVotingPower);
+        }
+        let power = cap_vote_weight(&env, &config, power)?;
 
         match vote_type {
-            VoteType::For => proposal.votes_for += voting_power,
-            VoteType::Against => proposal.votes_against += voting_power,
-            VoteType::Abstain => proposal.votes_abstain += voting_power,
+            VoteType::For => proposal.votes_for += power,
+            VoteType::Against => proposal.votes_against += power,
+            VoteType::Abstain => proposal.votes_abstain += power,
         }
         proposal.total_votes += 1;
 
@@ -218,7 +521,7 @@ impl GovernanceContract {
                 voter: voter.clone(),
                 proposal_id,
                 vote_type,
-                voting_power,
+                voting_power: power,
                 timestamp: current_time,
             },
         );
@@ -229,107 +532,766 @@ impl GovernanceContract {
         Ok(())
     }
 
-    pub fn finalize_proposal(env: Env, proposal_id: u32) -> Result<ProposalStatus, Error> {
+    /// Moves an existing vote to `new_vote_type` while the voting window is
+    /// open. The voting power recorded with the original vote moves with it,
+    /// so tallies stay consistent; `total_votes` is unchanged.
+    ///
+    /// A delegate's change also moves the zero-power records of the
+    /// delegators it voted for. Delegators cannot change those records
+    /// themselves (`VoteDelegated`).
+    pub fn change_vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u32,
+        new_vote_type: VoteType,
+    ) -> Result<(), Error> {
+        voter.require_auth();
         let mut proposals: Map<u32, Proposal> = env
             .storage()
             .instance()
             .get(&PROPOSALS)
             .ok_or(Error::ProposalsNotFound)?;
         let mut proposal = proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
-        let config: GovernanceConfig = env
+
+        if proposal.status != ProposalStatus::Active {
+            return Err(Error::ProposalNotActive);
+        }
+        let current_time = env.ledger().timestamp();
+        if current_time > proposal.voting_end {
+            return Err(Error::VotingEnded);
+        }
+
+        let mut votes: Map<(u32, Address), Vote> = env
             .storage()
             .instance()
-            .get(&GOVERNANCE_CONFIG)
-            .ok_or(Error::NotInitialized)?;
+            .get(&VOTES)
+            .unwrap_or(Map::new(&env));
+        let mut vote = votes
+            .get((proposal_id, voter.clone()))
+            .ok_or(Error::NotVoted)?;
+        // Un registro con poder 0 es el voto que emitió el delegado
+        if vote.voting_power == 0 {
+            return Err(Error::VoteDelegated);
+        }
+        if vote.vote_type == new_vote_type {
+            return Ok(());
+        }
 
-        if env.ledger().timestamp() <= proposal.voting_end {
-            return Err(Error::VotingStillActive);
+        let power = vote.voting_power;
+        match vote.vote_type {
+            VoteType::For => proposal.votes_for -= power,
+            VoteType::Against => proposal.votes_against -= power,
+            VoteType::Abstain => proposal.votes_abstain -= power,
+        }
+        match new_vote_type {
+            VoteType::For => proposal.votes_for += power,
+            VoteType::Against => proposal.votes_against += power,
+            VoteType::Abstain => proposal.votes_abstain += power,
         }
 
-        // Lógica de umbral (Threshold)
-        let total_cast = proposal.votes_for + proposal.votes_against;
-        if total_cast == 0 {
-            proposal.status = ProposalStatus::Rejected;
-        } else {
-            let approval_bps = (proposal.votes_for * 10000) / total_cast;
-            if approval_bps >= config.approval_threshold as i128 {
-                proposal.status = ProposalStatus::Approved;
-            } else {
-                proposal.status = ProposalStatus::Rejected;
+        for delegator in delegators_of(&env, &voter).iter() {
+            if let Some(mut delegated) = votes.get((proposal_id, delegator.clone())) {
+                if delegated.voting_power == 0 {
+                    delegated.vote_type = new_vote_type.clone();
+                    delegated.timestamp = current_time;
+                    votes.set((proposal_id, delegator), delegated);
+                }
             }
         }
 
-        proposals.set(proposal_id, proposal.clone());
+        let old_vote = vote.vote_type;
+        vote.vote_type = new_vote_type.clone();
+        vote.timestamp = current_time;
+        votes.set((proposal_id, voter.clone()), vote);
+        proposals.set(proposal_id, proposal);
         env.storage().instance().set(&PROPOSALS, &proposals);
-        Ok(proposal.status)
+        env.storage().instance().set(&VOTES, &votes);
+
+        env.events().publish(
+            (symbol_short!("vote_chg"), proposal_id),
+            VoteChanged {
+                proposal_id,
+                voter,
+                old_vote,
+                new_vote: new_vote_type,
+                voting_power: power,
+            },
+        );
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::{Address as _, Ledger};
+    /// Delegates `delegator`'s voting power to `delegate_address`, replacing
+    /// any previous delegation. While delegated, `delegator` cannot vote
+    /// directly; the delegate's votes carry their power instead. Delegation
+    /// is not transitive, and chains that loop back (A -> B -> A) are
+    /// rejected with `DelegationCycle`.
+    pub fn delegate(env: Env, delegator: Address, delegate_address: Address) -> Result<(), Error> {
+        delegator.require_auth();
+        let mut delegates: Map<Address, Address> = env
+            .storage()
+            .instance()
+            .get(&DELEGATES)
+            .unwrap_or(Map::new(&env));
 
-    fn setup_test(env: &Env) -> (GovernanceContractClient, Address, Address) {
-        let contract_id = env.register_contract(None, GovernanceContract);
-        let client = GovernanceContractClient::new(env, &contract_id);
-        let admin = Address::generate(env);
-        let user = Address::generate(env);
+        let mut next = Some(delegate_address.clone());
+        let mut steps = 0;
+        while let Some(current) = next {
+            if current == delegator || steps > delegates.len() {
+                return Err(Error::DelegationCycle);
+            }
+            next = delegates.get(current);
+            steps += 1;
+        }
 
-        let config = GovernanceConfig {
-            voting_period: 100,
-            execution_delay: 0,
-            quorum_percentage: 1000,
-            approval_threshold: 5000,
-            min_proposal_stake: 0,
-            voting_scheme: VotingScheme::OnePersonOneVote,
-        };
+        if let Some(previous) = delegates.get(delegator.clone()) {
+            remove_delegator(&env, &previous, &delegator);
+        }
+        delegates.set(delegator.clone(), delegate_address.clone());
+        env.storage().instance().set(&DELEGATES, &delegates);
 
-        env.mock_all_auths();
-        client.init_governance(&admin, &config);
-        (client, admin, user)
-    }
+        let mut delegators: Map<Address, Vec<Address>> = env
+            .storage()
+            .instance()
+            .get(&DELEGATORS)
+            .unwrap_or(Map::new(&env));
+        let mut list = delegators
+            .get(delegate_address.clone())
+            .unwrap_or(Vec::new(&env));
+        list.push_back(delegator.clone());
+        delegators.set(delegate_address.clone(), list);
+        env.storage().instance().set(&DELEGATORS, &delegators);
 
-    #[test]
-    fn test_edge_case_double_voting() {
-        let env = Env::default();
-        let (client, _, user) = setup_test(&env);
-        let prop_id = client.create_proposal(
-            &user,
-            &BytesN::from_array(&env, &[0u8; 32]),
-            &symbol_short!("test"),
+        env.events().publish(
+            (symbol_short!("delegate"),),
+            DelegationChanged {
+                delegator,
+                delegate: Some(delegate_address),
+            },
         );
-
-        client.cast_vote(&user, &prop_id, &VoteType::For);
-
-        let result = client.try_cast_vote(&user, &prop_id, &VoteType::For);
-        assert_eq!(result, Err(Ok(Error::AlreadyVoted)));
+        Ok(())
     }
 
-    #[test]
-    fn test_edge_case_voting_after_expiration() {
-        let env = Env::default();
-        let (client, _, user) = setup_test(&env);
-        let prop_id = client.create_proposal(
-            &user,
-            &BytesN::from_array(&env, &[0u8; 32]),
-            &symbol_short!("test"),
-        );
+    /// Removes `delegator`'s delegation so they can vote directly again.
+    pub fn undelegate(env: Env, delegator: Address) -> Result<(), Error> {
+        delegator.require_auth();
+        let mut delegates: Map<Address, Address> = env
+            .storage()
+            .instance()
+            .get(&DELEGATES)
+            .unwrap_or(Map::new(&env));
+        let previous = delegates
+            .get(delegator.clone())
+            .ok_or(Error::NotDelegated)?;
+        delegates.remove(delegator.clone());
+        env.storage().instance().set(&DELEGATES, &delegates);
+        remove_delegator(&env, &previous, &delegator);
 
-        env.ledger().with_mut(|li| li.timestamp = 200); // Saltamos al futuro (periodo era 100)
+        env.events().publish(
+            (symbol_short!("undeleg"),),
+            DelegationChanged {
+                delegator,
+                delegate: None,
+            },
+        );
+        Ok(())
+    }
 
-        let result = client.try_cast_vote(&user, &prop_id, &VoteType::For);
-        assert_eq!(result, Err(Ok(Error::VotingEnded)));
+    pub fn get_delegate(env: Env, delegator: Address) -> Option<Address> {
+        let delegates: Map<Address, Address> = env
+            .storage()
+            .instance()
+            .get(&DELEGATES)
+            .unwrap_or(Map::new(&env));
+        delegates.get(delegator)
     }
 
-    #[test]
-    fn test_edge_case_exact_threshold() {
-        let env = Env::default();
-        let (client, _, user1) = setup_test(&env);
-        let user2 = Address::generate(&env);
+    /// Voting power delegated to `address` under the current voting scheme,
+    /// not counting its own. Uses current stake; votes on a proposal use the
+    /// stake snapshotted when it was created.
+    pub fn get_delegated_power(env: Env, address: Address) -> Result<i128, Error> {
+        let config: GovernanceConfig = env
+            .storage()
+            .instance()
+            .get(&GOVERNANCE_CONFIG)
+            .ok_or(Error::NotInitialized)?;
+        let now: u32 = env.storage().instance().get(&STAKE_EPOCH).unwrap_or(0);
+        let mut power = 0i128;
+        for delegator in delegators_of(&env, &address).iter() {
+            power += voting_power(&env, &config, &delegator, now)?;
+        }
+        Ok(power)
+    }
 
-        let prop_id = client.create_proposal(
-            &user1,
+    /// Withdraws a `Pending` or `Active` proposal. Only the proposer can
+    /// cancel; their locked stake is returned to their free stake and any
+    /// votes already cast are discarded.
+    pub fn cancel_proposal(env: Env, proposal_id: u32) -> Result<(), Error> {
+        let mut proposals: Map<u32, Proposal> = env
+            .storage()
+            .instance()
+            .get(&PROPOSALS)
+            .ok_or(Error::ProposalsNotFound)?;
+        let mut proposal = proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+        proposal.proposer.require_auth();
+
+        if proposal.status != ProposalStatus::Pending && proposal.status != ProposalStatus::Active {
+            return Err(Error::ProposalNotActive);
+        }
+
+        let mut votes: Map<(u32, Address), Vote> = env
+            .storage()
+            .instance()
+            .get(&VOTES)
+            .unwrap_or(Map::new(&env));
+        for (key, _) in votes.clone().iter() {
+            if key.0 == proposal_id {
+                votes.remove(key);
+            }
+        }
+        env.storage().instance().set(&VOTES, &votes);
+
+        proposal.status = ProposalStatus::Cancelled;
+        proposal.votes_for = 0;
+        proposal.votes_against = 0;
+        proposal.votes_abstain = 0;
+        proposal.total_votes = 0;
+        proposals.set(proposal_id, proposal.clone());
+        env.storage().instance().set(&PROPOSALS, &proposals);
+        let stake_returned = release_proposal_stake(&env, &proposal);
+
+        env.events().publish(
+            (symbol_short!("prop_cncl"), proposal_id),
+            ProposalCancelled {
+                proposal_id,
+                proposer: proposal.proposer,
+                stake_returned,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Option<Proposal> {
+        let proposals: Map<u32, Proposal> = env
+            .storage()
+            .instance()
+            .get(&PROPOSALS)
+            .unwrap_or(Map::new(&env));
+        proposals.get(proposal_id)
+    }
+
+    /// Lists up to `limit` proposals by id, starting at `start_id`.
+    pub fn list_proposals(env: Env, start_id: u32, limit: u32) -> ProposalPage {
+        list_page(&env, start_id, limit, None)
+    }
+
+    /// Like `list_proposals`, but only returns proposals in `status`.
+    /// `start` is a proposal id; pass `next_cursor` to continue.
+    pub fn list_proposals_by_status(
+        env: Env,
+        status: ProposalStatus,
+        start: u32,
+        limit: u32,
+    ) -> ProposalPage {
+        list_page(&env, start, limit, Some(status))
+    }
+
+    pub fn get_vote(env: Env, proposal_id: u32, voter: Address) -> Option<Vote> {
+        let votes: Map<(u32, Address), Vote> = env
+            .storage()
+            .instance()
+            .get(&VOTES)
+            .unwrap_or(Map::new(&env));
+        votes.get((proposal_id, voter))
+    }
+
+    /// Sets the total voting power quorum is measured against: the number of
+    /// eligible voters under `OnePersonOneVote`, or the stakeable token supply
+    /// under `TokenWeighted`. Admin only.
+    pub fn set_voting_supply(env: Env, supply: i128) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&GOVERNANCE_ADMIN)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        if supply <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        env.storage().instance().set(&VOTING_SUPPLY, &supply);
+        Ok(())
+    }
+
+    /// Executes a proposal once `voting_end + execution_delay` has passed.
+    ///
+    /// Participation (for + against + abstain) must reach `quorum_percentage`
+    /// of the voting supply from at least `min_voters` distinct voters, and
+    /// `votes_for` must reach `approval_threshold`
+    /// of for + against, both in basis points. A passing proposal upgrades
+    /// this contract to `new_wasm_hash` and ends `Executed`; otherwise it ends
+    /// `Rejected`. Either way the proposer's stake is unlocked, unless the
+    /// proposal was rejected and `slash_rejected_stake` sends it to the treasury.
+    pub fn execute_proposal(env: Env, proposal_id: u32) -> Result<ProposalStatus, Error> {
+        let mut proposals: Map<u32, Proposal> = env
+            .storage()
+            .instance()
+            .get(&PROPOSALS)
+            .ok_or(Error::ProposalsNotFound)?;
+        let mut proposal = proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+        let config: GovernanceConfig = env
+            .storage()
+            .instance()
+            .get(&GOVERNANCE_CONFIG)
+            .ok_or(Error::NotInitialized)?;
+
+        match proposal.status {
+            ProposalStatus::Pending | ProposalStatus::Active | ProposalStatus::Approved => {}
+            _ => return Err(Error::ProposalNotActive),
+        }
+
+        let now = env.ledger().timestamp();
+        if now <= proposal.voting_end {
+            return Err(Error::VotingStillActive);
+        }
+        if now < proposal.voting_end + proposal.execution_delay {
+            return Err(Error::ExecutionDelayNotMet);
+        }
+
+        let supply: i128 = env
+            .storage()
+            .instance()
+            .get(&VOTING_SUPPLY)
+            .ok_or(Error::VotingSupplyNotSet)?;
+
+        let (quorum_met, threshold_met) = tally(&config, supply, &proposal);
+
+        proposal.status = if quorum_met && threshold_met {
+            ProposalStatus::Executed
+        } else {
+            ProposalStatus::Rejected
+        };
+        proposals.set(proposal_id, proposal.clone());
+        env.storage().instance().set(&PROPOSALS, &proposals);
+        settle_proposal_stake(&env, &config, &proposal)?;
+
+        if proposal.status == ProposalStatus::Rejected {
+            env.events().publish(
+                (symbol_short!("prop_rej"), proposal_id),
+                ProposalRejected {
+                    proposal_id,
+                    quorum_met,
+                    votes_for: proposal.votes_for,
+                    votes_against: proposal.votes_against,
+                    votes_abstain: proposal.votes_abstain,
+                },
+            );
+            return Ok(ProposalStatus::Rejected);
+        }
+
+        env.events().publish(
+            (symbol_short!("prop_exec"), proposal_id),
+            ProposalExecuted {
+                proposal_id,
+                new_wasm_hash: proposal.new_wasm_hash.clone(),
+                votes_for: proposal.votes_for,
+                votes_against: proposal.votes_against,
+                votes_abstain: proposal.votes_abstain,
+            },
+        );
+        env.deployer()
+            .update_current_contract_wasm(proposal.new_wasm_hash);
+        Ok(ProposalStatus::Executed)
+    }
+
+    /// Read-only projection of `execute_proposal` on the votes cast so far.
+    /// Quorum is checked first, so a proposal short on both reports
+    /// `FailQuorum`. Works during and after the voting window, until
+    /// the proposal is executed, rejected or cancelled.
+    pub fn preview_outcome(env: Env, proposal_id: u32) -> Result<ProposalOutcome, Error> {
+        let proposals: Map<u32, Proposal> = env
+            .storage()
+            .instance()
+            .get(&PROPOSALS)
+            .ok_or(Error::ProposalsNotFound)?;
+        let proposal = proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+        let config: GovernanceConfig = env
+            .storage()
+            .instance()
+            .get(&GOVERNANCE_CONFIG)
+            .ok_or(Error::NotInitialized)?;
+
+        match proposal.status {
+            ProposalStatus::Pending | ProposalStatus::Active | ProposalStatus::Approved => {}
+            _ => return Err(Error::ProposalNotActive),
+        }
+        let supply: i128 = env
+            .storage()
+            .instance()
+            .get(&VOTING_SUPPLY)
+            .ok_or(Error::VotingSupplyNotSet)?;
+
+        Ok(match tally(&config, supply, &proposal) {
+            (false, _) => ProposalOutcome::FailQuorum,
+            (true, false) => ProposalOutcome::FailThreshold,
+            (true, true) => ProposalOutcome::Pass,
+        })
+    }
+
+    pub fn finalize_proposal(env: Env, proposal_id: u32) -> Result<ProposalStatus, Error> {
+        let mut proposals: Map<u32, Proposal> = env
+            .storage()
+            .instance()
+            .get(&PROPOSALS)
+            .ok_or(Error::ProposalsNotFound)?;
+        let mut proposal = proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+        let config: GovernanceConfig = env
+            .storage()
+            .instance()
+            .get(&GOVERNANCE_CONFIG)
+            .ok_or(Error::NotInitialized)?;
+
+        if proposal.status != ProposalStatus::Pending && proposal.status != ProposalStatus::Active {
+            return Err(Error::ProposalNotActive);
+        }
+        if env.ledger().timestamp() <= proposal.voting_end {
+            return Err(Error::VotingStillActive);
+        }
+
+        // Lógica de umbral (Threshold)
+        let total_cast = proposal.votes_for + proposal.votes_against;
+        if total_cast == 0 {
+            proposal.status = ProposalStatus::Rejected;
+        } else {
+            let approval_bps = (proposal.votes_for * 10000) / total_cast;
+            if approval_bps >= config.approval_threshold as i128 {
+                proposal.status = ProposalStatus::Approved;
+            } else {
+                proposal.status = ProposalStatus::Rejected;
+            }
+        }
+
+        proposals.set(proposal_id, proposal.clone());
+        env.storage().instance().set(&PROPOSALS, &proposals);
+        // Una propuesta aprobada conserva el stake hasta su ejecución
+        if proposal.status == ProposalStatus::Rejected {
+            settle_proposal_stake(&env, &config, &proposal)?;
+        }
+        Ok(proposal.status)
+    }
+}
+
+fn validate_config(config: &GovernanceConfig) -> Result<(), Error> {
+    if config.quorum_percentage > 10000
+        || config.approval_threshold > 10000
+        || config.max_vote_weight_bps > 10000
+    {
+        return Err(Error::InvalidThreshold);
+    }
+    if config.approval_threshold < 5000 {
+        return Err(Error::ThresholdTooLow);
+    }
+    if config.slash_rejected_stake && config.treasury.is_none() {
+        return Err(Error::TreasuryNotSet);
+    }
+    Ok(())
+}
+
+// (quórum alcanzado, umbral de aprobación alcanzado), ambos en puntos básicos.
+// El quórum también exige `min_voters` votantes distintos.
+fn tally(config: &GovernanceConfig, supply: i128, proposal: &Proposal) -> (bool, bool) {
+    let participation = proposal.votes_for + proposal.votes_against + proposal.votes_abstain;
+    let quorum_met = participation * 10000 >= supply * config.quorum_percentage as i128
+        && proposal.total_votes >= config.min_voters;
+    let total_cast = proposal.votes_for + proposal.votes_against;
+    let threshold_met = total_cast > 0
+        && proposal.votes_for * 10000 >= total_cast * config.approval_threshold as i128;
+    (quorum_met, threshold_met)
+}
+
+// Al cerrar una propuesta: el stake vuelve al proposer, o al tesoro si fue
+// rechazada y la configuración pide penalizarla
+fn settle_proposal_stake(
+    env: &Env,
+    config: &GovernanceConfig,
+    proposal: &Proposal,
+) -> Result<(), Error> {
+    if proposal.status != ProposalStatus::Rejected || !config.slash_rejected_stake {
+        release_proposal_stake(env, proposal);
+        return Ok(());
+    }
+
+    let treasury = config.treasury.clone().ok_or(Error::TreasuryNotSet)?;
+    let mut locked: Map<u32, i128> = env
+        .storage()
+        .instance()
+        .get(&PROPOSAL_STAKES)
+        .unwrap_or(Map::new(env));
+    let Some(stake) = locked.get(proposal.id) else {
+        return Ok(());
+    };
+    locked.remove(proposal.id);
+    env.storage().instance().set(&PROPOSAL_STAKES, &locked);
+
+    let token_addr: Address = env
+        .storage()
+        .instance()
+        .get(&GOVERNANCE_TOKEN)
+        .ok_or(Error::TokenNotSet)?;
+    token::Client::new(env, &token_addr).transfer(
+        &env.current_contract_address(),
+        &treasury,
+        &stake,
+    );
+    checkpoint_stake(env, &proposal.proposer, -stake);
+    env.events().publish(
+        (symbol_short!("stk_slash"), proposal.id),
+        StakeSlashed {
+            proposal_id: proposal.id,
+            proposer: proposal.proposer.clone(),
+            amount: stake,
+            treasury,
+        },
+    );
+    Ok(())
+}
+
+// Devuelve el stake bloqueado por la propuesta al saldo libre del proposer
+fn release_proposal_stake(env: &Env, proposal: &Proposal) -> i128 {
+    let mut locked: Map<u32, i128> = env
+        .storage()
+        .instance()
+        .get(&PROPOSAL_STAKES)
+        .unwrap_or(Map::new(env));
+    let Some(stake) = locked.get(proposal.id) else {
+        return 0;
+    };
+    locked.remove(proposal.id);
+    env.storage().instance().set(&PROPOSAL_STAKES, &locked);
+
+    let mut stakes: Map<Address, i128> = env
+        .storage()
+        .instance()
+        .get(&STAKES)
+        .unwrap_or(Map::new(env));
+    let staked = stakes.get(proposal.proposer.clone()).unwrap_or(0);
+    stakes.set(proposal.proposer.clone(), staked + stake);
+    env.storage().instance().set(&STAKES, &stakes);
+    stake
+}
+
+// Recorre los ids desde `start` hasta llenar la página
+fn list_page(env: &Env, start: u32, limit: u32, status: Option<ProposalStatus>) -> ProposalPage {
+    let proposals: Map<u32, Proposal> = env
+        .storage()
+        .instance()
+        .get(&PROPOSALS)
+        .unwrap_or(Map::new(env));
+    let count: u32 = env.storage().instance().get(&PROPOSAL_COUNT).unwrap_or(0);
+    let limit = limit.min(MAX_PROPOSAL_PAGE);
+
+    let mut page = Vec::new(env);
+    let mut id = start;
+    while id < count && page.len() < limit {
+        if let Some(proposal) = proposals.get(id) {
+            if status.is_none() || status.as_ref() == Some(&proposal.status) {
+                page.push_back(proposal);
+            }
+        }
+        id += 1;
+    }
+
+    ProposalPage {
+        proposals: page,
+        next_cursor: if id < count { Some(id) } else { None },
+    }
+}
+
+fn delegators_of(env: &Env, delegate: &Address) -> Vec<Address> {
+    let delegators: Map<Address, Vec<Address>> = env
+        .storage()
+        .instance()
+        .get(&DELEGATORS)
+        .unwrap_or(Map::new(env));
+    delegators.get(delegate.clone()).unwrap_or(Vec::new(env))
+}
+
+fn remove_delegator(env: &Env, delegate: &Address, delegator: &Address) {
+    let mut delegators: Map<Address, Vec<Address>> = env
+        .storage()
+        .instance()
+        .get(&DELEGATORS)
+        .unwrap_or(Map::new(env));
+    let mut list = delegators.get(delegate.clone()).unwrap_or(Vec::new(env));
+    if let Some(i) = list.first_index_of(delegator) {
+        list.remove(i);
+    }
+    if list.is_empty() {
+        delegators.remove(delegate.clone());
+    } else {
+        delegators.set(delegate.clone(), list);
+    }
+    env.storage().instance().set(&DELEGATORS, &delegators);
+}
+
+// OnePersonOneVote: 1 por dirección. TokenWeighted: stake del votante en la
+// época `snapshot`.
+fn voting_power(
+    env: &Env,
+    config: &GovernanceConfig,
+    voter: &Address,
+    snapshot: u32,
+) -> Result<i128, Error> {
+    match config.voting_scheme {
+        VotingScheme::OnePersonOneVote => Ok(1),
+        VotingScheme::TokenWeighted => {
+            if !env.storage().instance().has(&GOVERNANCE_TOKEN) {
+                return Err(Error::TokenNotSet);
+            }
+            Ok(stake_at(env, voter, snapshot))
+        }
+    }
+}
+
+// Con el tope activo ningún voto (con sus delegaciones) pasa de
+// `max_vote_weight_bps` del suministro de voto
+fn cap_vote_weight(env: &Env, config: &GovernanceConfig, power: i128) -> Result<i128, Error> {
+    if config.voting_scheme != VotingScheme::TokenWeighted
+        || config.max_vote_weight_bps == 0
+        || config.max_vote_weight_bps >= 10000
+    {
+        return Ok(power);
+    }
+    let supply: i128 = env
+        .storage()
+        .instance()
+        .get(&VOTING_SUPPLY)
+        .ok_or(Error::VotingSupplyNotSet)?;
+    Ok(power.min(supply * config.max_vote_weight_bps as i128 / 10000))
+}
+
+// Registra el stake total de `staker` (libre + bloqueado) tras sumarle `delta`,
+// en una época nueva. Descarta los checkpoints que ya no puede leer ninguna
+// propuesta abierta.
+fn checkpoint_stake(env: &Env, staker: &Address, delta: i128) {
+    let epoch: u32 = env.storage().instance().get(&STAKE_EPOCH).unwrap_or(0) + 1;
+    env.storage().instance().set(&STAKE_EPOCH, &epoch);
+
+    let key = (STAKE_HISTORY, staker.clone());
+    let mut checkpoints: Vec<(u32, i128)> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    let total = checkpoints.last().map(|(_, total)| total).unwrap_or(0) + delta;
+    checkpoints.push_back((epoch, total));
+
+    // Basta con el último checkpoint anterior o igual a la instantánea más antigua
+    let floor = oldest_open_snapshot(env).unwrap_or(epoch);
+    while checkpoints.len() > 1 && checkpoints.get(1).unwrap().0 <= floor {
+        checkpoints.pop_front();
+    }
+    env.storage().persistent().set(&key, &checkpoints);
+}
+
+// Instantánea más antigua entre las propuestas que aún se pueden votar
+fn oldest_open_snapshot(env: &Env) -> Option<u32> {
+    let proposals: Map<u32, Proposal> = env
+        .storage()
+        .instance()
+        .get(&PROPOSALS)
+        .unwrap_or(Map::new(env));
+    proposals
+        .values()
+        .iter()
+        .filter(|p| p.status == ProposalStatus::Pending || p.status == ProposalStatus::Active)
+        .map(|p| p.snapshot)
+        .min()
+}
+
+// Stake total de `staker` al cerrar la época `snapshot`
+fn stake_at(env: &Env, staker: &Address, snapshot: u32) -> i128 {
+    let checkpoints: Vec<(u32, i128)> = env
+        .storage()
+        .persistent()
+        .get(&(STAKE_HISTORY, staker.clone()))
+        .unwrap_or(Vec::new(env));
+    for (epoch, total) in checkpoints.iter().rev() {
+        if epoch <= snapshot {
+            return total;
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Events, Ledger};
+    use soroban_sdk::IntoVal;
+
+    fn setup_test(env: &Env) -> (GovernanceContractClient, Address, Address) {
+        let contract_id = env.register_contract(None, GovernanceContract);
+        let client = GovernanceContractClient::new(env, &contract_id);
+        let admin = Address::generate(env);
+        let user = Address::generate(env);
+
+        let config = GovernanceConfig {
+            voting_period: 100,
+            execution_delay: 0,
+            quorum_percentage: 1000,
+            approval_threshold: 5000,
+            min_proposal_stake: 0,
+            voting_scheme: VotingScheme::OnePersonOneVote,
+            slash_rejected_stake: false,
+            treasury: None,
+            min_voters: 0,
+            max_vote_weight_bps: 0,
+        };
+
+        env.mock_all_auths();
+        client.init_governance(&admin, &config);
+        (client, admin, user)
+    }
+
+    #[test]
+    fn test_edge_case_double_voting() {
+        let env = Env::default();
+        let (client, _, user) = setup_test(&env);
+        let prop_id = client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+
+        client.cast_vote(&user, &prop_id, &VoteType::For);
+
+        let result = client.try_cast_vote(&user, &prop_id, &VoteType::For);
+        assert_eq!(result, Err(Ok(Error::AlreadyVoted)));
+    }
+
+    #[test]
+    fn test_edge_case_voting_after_expiration() {
+        let env = Env::default();
+        let (client, _, user) = setup_test(&env);
+        let prop_id = client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+
+        env.ledger().with_mut(|li| li.timestamp = 200); // Saltamos al futuro (periodo era 100)
+
+        let result = client.try_cast_vote(&user, &prop_id, &VoteType::For);
+        assert_eq!(result, Err(Ok(Error::VotingEnded)));
+    }
+
+    #[test]
+    fn test_edge_case_exact_threshold() {
+        let env = Env::default();
+        let (client, _, user1) = setup_test(&env);
+        let user2 = Address::generate(&env);
+
+        let prop_id = client.create_proposal(
+            &user1,
             &BytesN::from_array(&env, &[0u8; 32]),
             &symbol_short!("test"),
         );
@@ -367,4 +1329,1095 @@ mod test {
 
         assert_eq!(status, ProposalStatus::Rejected);
     }
+
+    fn setup_staking(
+        env: &Env,
+        min_proposal_stake: i128,
+    ) -> (GovernanceContractClient, token::StellarAssetClient) {
+        setup_with_scheme(env, min_proposal_stake, VotingScheme::OnePersonOneVote)
+    }
+
+    fn setup_with_scheme(
+        env: &Env,
+        min_proposal_stake: i128,
+        voting_scheme: VotingScheme,
+    ) -> (GovernanceContractClient, token::StellarAssetClient) {
+        let contract_id = env.register_contract(None, GovernanceContract);
+        let client = GovernanceContractClient::new(env, &contract_id);
+        let config = GovernanceConfig {
+            voting_period: 100,
+            execution_delay: 0,
+            quorum_percentage: 1000,
+            approval_threshold: 5000,
+            min_proposal_stake,
+            voting_scheme,
+            slash_rejected_stake: false,
+            treasury: None,
+            min_voters: 0,
+            max_vote_weight_bps: 0,
+        };
+        env.mock_all_auths();
+        client.init_governance(&Address::generate(env), &config);
+        let token_admin = Address::generate(env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        client.set_governance_token(&token_id);
+        (client, token::StellarAssetClient::new(env, &token_id))
+    }
+
+    #[test]
+    fn test_create_proposal_is_pending_with_voting_window() {
+        let env = Env::default();
+        let (client, _, user) = setup_test(&env);
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+        let first = client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &symbol_short!("first"),
+        );
+        let second = client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &symbol_short!("second"),
+        );
+        assert_eq!(first, 0);
+        assert_eq!(second, 1);
+
+        let proposals: Map<u32, Proposal> = env.as_contract(&client.address, || {
+            env.storage().instance().get(&PROPOSALS).unwrap()
+        });
+        let proposal = proposals.get(second).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Pending);
+        assert_eq!(proposal.proposer, user);
+        assert_eq!(proposal.voting_start, 1_000);
+        assert_eq!(proposal.voting_end, 1_100);
+
+        // El primer voto activa la propuesta
+        client.cast_vote(&user, &second, &VoteType::For);
+        let proposals: Map<u32, Proposal> = env.as_contract(&client.address, || {
+            env.storage().instance().get(&PROPOSALS).unwrap()
+        });
+        assert_eq!(
+            proposals.get(second).unwrap().status,
+            ProposalStatus::Active
+        );
+    }
+
+    #[test]
+    fn test_create_proposal_emits_event() {
+        let env = Env::default();
+        let (client, _, user) = setup_test(&env);
+        let prop_id = client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+
+        let (contract, topics, _) = env.events().all().last().unwrap();
+        assert_eq!(contract, client.address);
+        let name: Symbol = topics.get(0).unwrap().into_val(&env);
+        let id: u32 = topics.get(1).unwrap().into_val(&env);
+        assert_eq!(name, symbol_short!("prop_new"));
+        assert_eq!(id, prop_id);
+    }
+
+    #[test]
+    fn test_create_proposal_requires_min_stake() {
+        let env = Env::default();
+        let (client, token) = setup_staking(&env, 500);
+        let proposer = Address::generate(&env);
+        token.mint(&proposer, &1_000);
+
+        let result = client.try_create_proposal(
+            &proposer,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        assert_eq!(result, Err(Ok(Error::InsufficientStake)));
+
+        client.stake(&proposer, &499);
+        let result = client.try_create_proposal(
+            &proposer,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        assert_eq!(result, Err(Ok(Error::InsufficientStake)));
+
+        client.stake(&proposer, &1);
+        client.create_proposal(
+            &proposer,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+    }
+
+    #[test]
+    fn test_create_proposal_locks_stake() {
+        let env = Env::default();
+        let (client, token) = setup_staking(&env, 500);
+        let proposer = Address::generate(&env);
+        token.mint(&proposer, &800);
+        client.stake(&proposer, &800);
+
+        client.create_proposal(
+            &proposer,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        assert_eq!(client.get_stake(&proposer), 300);
+
+        // Solo el stake libre se puede retirar o usar en otra propuesta
+        let result = client.try_create_proposal(
+            &proposer,
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &symbol_short!("test"),
+        );
+        assert_eq!(result, Err(Ok(Error::InsufficientStake)));
+        assert_eq!(
+            client.try_unstake(&proposer, &301),
+            Err(Ok(Error::InsufficientStake))
+        );
+        client.unstake(&proposer, &300);
+        assert_eq!(client.get_stake(&proposer), 0);
+        assert_eq!(
+            token::Client::new(&env, &token.address).balance(&proposer),
+            300
+        );
+    }
+
+    #[test]
+    fn test_cast_vote_one_person_one_vote() {
+        let env = Env::default();
+        let (client, token) = setup_with_scheme(&env, 0, VotingScheme::OnePersonOneVote);
+        let whale = Address::generate(&env);
+        let user = Address::generate(&env);
+        token.mint(&whale, &1_000_000);
+
+        let prop_id = client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        client.cast_vote(&whale, &prop_id, &VoteType::For);
+        client.cast_vote(&user, &prop_id, &VoteType::Abstain);
+
+        let proposal = client.get_proposal(&prop_id).unwrap();
+        assert_eq!(proposal.votes_for, 1);
+        assert_eq!(proposal.votes_against, 0);
+        assert_eq!(proposal.votes_abstain, 1);
+        assert_eq!(proposal.total_votes, 2);
+
+        let vote = client.get_vote(&prop_id, &whale).unwrap();
+        assert_eq!(vote.vote_type, VoteType::For);
+        assert_eq!(vote.voting_power, 1);
+    }
+
+    #[test]
+    fn test_cast_vote_token_weighted_uses_stake() {
+        let env = Env::default();
+        let (client, token) = setup_with_scheme(&env, 0, VotingScheme::TokenWeighted);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        token.mint(&alice, &700);
+        token.mint(&bob, &300);
+        token.mint(&carol, &50);
+        client.stake(&alice, &700);
+        client.stake(&bob, &300);
+        client.stake(&carol, &50);
+
+        let prop_id = client.create_proposal(
+            &alice,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        client.cast_vote(&alice, &prop_id, &VoteType::For);
+        client.cast_vote(&bob, &prop_id, &VoteType::Against);
+        client.cast_vote(&carol, &prop_id, &VoteType::Abstain);
+
+        let proposal = client.get_proposal(&prop_id).unwrap();
+        assert_eq!(proposal.votes_for, 700);
+        assert_eq!(proposal.votes_against, 300);
+        assert_eq!(proposal.votes_abstain, 50);
+        assert_eq!(proposal.total_votes, 3);
+        assert_eq!(client.get_vote(&prop_id, &bob).unwrap().voting_power, 300);
+    }
+
+    #[test]
+    fn test_vote_cap_applies_to_delegated_total() {
+        let env = Env::default();
+        let (client, token) = setup_with_scheme(&env, 0, VotingScheme::TokenWeighted);
+        let mut config = client.get_governance_config();
+        config.max_vote_weight_bps = 2500;
+        client.set_governance_config(&config);
+        client.set_voting_supply(&1_000);
+        let delegate = Address::generate(&env);
+        token.mint(&delegate, &200);
+        client.stake(&delegate, &200);
+        for _ in 0..2 {
+            let delegator = Address::generate(&env);
+            token.mint(&delegator, &200);
+            client.stake(&delegator, &200);
+            client.delegate(&delegator, &delegate);
+        }
+
+        // Cada dirección está bajo el tope, pero la suma (600) no
+        let prop_id = client.create_proposal(
+            &delegate,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        client.cast_vote(&delegate, &prop_id, &VoteType::For);
+        assert_eq!(
+            client.get_vote(&prop_id, &delegate).unwrap().voting_power,
+            250
+        );
+        assert_eq!(client.get_proposal(&prop_id).unwrap().votes_for, 250);
+    }
+
+    #[test]
+    fn test_token_weighted_vote_capped_at_max_weight() {
+        let env = Env::default();
+        let (client, token) = setup_with_scheme(&env, 0, VotingScheme::TokenWeighted);
+        let mut config = client.get_governance_config();
+        config.max_vote_weight_bps = 2500;
+        client.set_governance_config(&config);
+        let whale = Address::generate(&env);
+        let minnow = Address::generate(&env);
+        token.mint(&whale, &800);
+        client.stake(&whale, &800);
+        token.mint(&minnow, &200);
+        client.stake(&minnow, &200);
+
+        let prop_id = client.create_proposal(
+            &whale,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        // Sin suministro de voto no se puede calcular el tope
+        let result = client.try_cast_vote(&whale, &prop_id, &VoteType::For);
+        assert_eq!(result, Err(Ok(Error::VotingSupplyNotSet)));
+
+        // El 80% del suministro queda limitado al 25%
+        client.set_voting_supply(&1_000);
+        client.cast_vote(&whale, &prop_id, &VoteType::For);
+        client.cast_vote(&minnow, &prop_id, &VoteType::Against);
+        assert_eq!(client.get_vote(&prop_id, &whale).unwrap().voting_power, 250);
+        let proposal = client.get_proposal(&prop_id).unwrap();
+        assert_eq!(proposal.votes_for, 250);
+        assert_eq!(proposal.votes_against, 200);
+
+        // 10000 desactiva el tope
+        config.max_vote_weight_bps = 10000;
+        client.set_governance_config(&config);
+        let next_id = client.create_proposal(
+            &whale,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("next"),
+        );
+        client.cast_vote(&whale, &next_id, &VoteType::For);
+        assert_eq!(client.get_vote(&next_id, &whale).unwrap().voting_power, 800);
+
+        config.max_vote_weight_bps = 10001;
+        let result = client.try_set_governance_config(&config);
+        assert_eq!(result, Err(Ok(Error::InvalidThreshold)));
+    }
+
+    #[test]
+    fn test_token_weighted_power_is_snapshotted_at_creation() {
+        let env = Env::default();
+        let (client, token) = setup_with_scheme(&env, 0, VotingScheme::TokenWeighted);
+        let alice = Address::generate(&env);
+        let whale = Address::generate(&env);
+        token.mint(&alice, &100);
+        client.stake(&alice, &100);
+        token.mint(&whale, &10);
+        client.stake(&whale, &10);
+
+        let prop_id = client.create_proposal(
+            &alice,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        assert_eq!(client.get_proposal(&prop_id).unwrap().snapshot, 2);
+
+        // Comprar y stakear después de crear la propuesta no cambia el poder
+        token.mint(&whale, &5_000);
+        client.stake(&whale, &5_000);
+        client.cast_vote(&whale, &prop_id, &VoteType::For);
+        assert_eq!(client.get_vote(&prop_id, &whale).unwrap().voting_power, 10);
+
+        // Retirar stake después tampoco lo reduce
+        client.unstake(&alice, &100);
+        client.cast_vote(&alice, &prop_id, &VoteType::Against);
+        let proposal = client.get_proposal(&prop_id).unwrap();
+        assert_eq!(proposal.votes_for, 10);
+        assert_eq!(proposal.votes_against, 100);
+
+        // Una propuesta nueva ve el stake actual
+        let next_id = client.create_proposal(
+            &whale,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("next"),
+        );
+        client.cast_vote(&whale, &next_id, &VoteType::For);
+        assert_eq!(
+            client.get_vote(&next_id, &whale).unwrap().voting_power,
+            5_010
+        );
+        let result = client.try_cast_vote(&alice, &next_id, &VoteType::For);
+        assert_eq!(result, Err(Ok(Error::NoVotingPower)));
+    }
+
+    #[test]
+    fn test_stake_history_is_pruned_past_oldest_open_snapshot() {
+        let env = Env::default();
+        let (client, token) = setup_with_scheme(&env, 0, VotingScheme::TokenWeighted);
+        let alice = Address::generate(&env);
+        let history_len = |who: &Address| {
+            env.as_contract(&client.address, || {
+                env.storage()
+                    .persistent()
+                    .get::<_, Vec<(u32, i128)>>(&(STAKE_HISTORY, who.clone()))
+                    .unwrap()
+                    .len()
+            })
+        };
+        token.mint(&alice, &1_000);
+
+        // Sin propuestas abiertas solo se guarda el último checkpoint
+        for _ in 0..3 {
+            client.stake(&alice, &10);
+        }
+        assert_eq!(history_len(&alice), 1);
+
+        let prop_id = client.create_proposal(
+            &alice,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        client.stake(&alice, &10);
+        client.stake(&alice, &10);
+        assert_eq!(history_len(&alice), 3);
+        client.cast_vote(&alice, &prop_id, &VoteType::For);
+        assert_eq!(client.get_vote(&prop_id, &alice).unwrap().voting_power, 30);
+
+        // Al cerrarse la propuesta el historial vuelve a un solo checkpoint
+        client.cancel_proposal(&prop_id);
+        client.stake(&alice, &10);
+        assert_eq!(history_len(&alice), 1);
+        assert_eq!(client.get_stake(&alice), 60);
+    }
+
+    #[test]
+    fn test_cast_vote_token_weighted_without_balance() {
+        let env = Env::default();
+        let (client, _) = setup_with_scheme(&env, 0, VotingScheme::TokenWeighted);
+        let user = Address::generate(&env);
+
+        let prop_id = client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        let result = client.try_cast_vote(&user, &prop_id, &VoteType::For);
+        assert_eq!(result, Err(Ok(Error::NoVotingPower)));
+        assert!(client.get_vote(&prop_id, &user).is_none());
+    }
+
+    #[test]
+    fn test_cast_vote_rejected_before_voting_start() {
+        let env = Env::default();
+        let (client, _, user) = setup_test(&env);
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        let prop_id = client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+
+        env.ledger().with_mut(|li| li.timestamp = 999);
+        let result = client.try_cast_vote(&user, &prop_id, &VoteType::For);
+        assert_eq!(result, Err(Ok(Error::VotingNotStarted)));
+
+        // Los extremos de la ventana son inclusivos
+        env.ledger().with_mut(|li| li.timestamp = 1_100);
+        client.cast_vote(&user, &prop_id, &VoteType::For);
+    }
+
+    #[test]
+    fn test_cast_vote_unknown_proposal() {
+        let env = Env::default();
+        let (client, _, user) = setup_test(&env);
+        client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        let result = client.try_cast_vote(&user, &7, &VoteType::For);
+        assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+    }
+
+    // Wasm mínimo válido para Soroban: cabecera + sección contractenvmetav0
+    fn upload_stub_wasm(env: &Env) -> BytesN<32> {
+        let wasm = soroban_sdk::Bytes::from_slice(
+            env,
+            b"\0asm\x01\0\0\0\0\x1e\x11contractenvmetav0\0\0\0\0\0\0\0\x15\0\0\0\0",
+        );
+        env.deployer().upload_contract_wasm(wasm)
+    }
+
+    fn setup_execution(env: &Env) -> (GovernanceContractClient, Address, Address, Address) {
+        let (client, _, user1) = setup_test(env);
+        let user2 = Address::generate(env);
+        let user3 = Address::generate(env);
+        client.set_voting_supply(&10);
+        (client, user1, user2, user3)
+    }
+
+    fn stored_proposal(env: &Env, client: &GovernanceContractClient, id: u32) -> Proposal {
+        let proposals: Map<u32, Proposal> = env.as_contract(&client.address, || {
+            env.storage().instance().get(&PROPOSALS).unwrap()
+        });
+        proposals.get(id).unwrap()
+    }
+
+    #[test]
+    fn test_execute_proposal_upgrades_contract() {
+        let env = Env::default();
+        let (client, user1, user2, _) = setup_execution(&env);
+        let wasm_hash = upload_stub_wasm(&env);
+        let prop_id = client.create_proposal(&user1, &wasm_hash, &symbol_short!("upgrade"));
+        client.cast_vote(&user1, &prop_id, &VoteType::For);
+        client.cast_vote(&user2, &prop_id, &VoteType::For);
+
+        env.ledger().with_mut(|li| li.timestamp = 101);
+        let status = client.execute_proposal(&prop_id);
+        assert_eq!(status, ProposalStatus::Executed);
+
+        let (_, topics, _) = env.events().all().last().unwrap();
+        let name: Symbol = topics.get(0).unwrap().into_val(&env);
+        assert_eq!(name, symbol_short!("prop_exec"));
+        // El contrato ya corre el wasm nuevo, así que se lee el storage directamente
+        assert_eq!(
+            stored_proposal(&env, &client, prop_id).status,
+            ProposalStatus::Executed
+        );
+    }
+
+    #[test]
+    fn test_execute_proposal_respects_execution_delay() {
+        let env = Env::default();
+        let (client, _, user) = setup_test(&env);
+        let config = GovernanceConfig {
+            voting_period: 100,
+            execution_delay: 50,
+            quorum_percentage: 1000,
+            approval_threshold: 5000,
+            min_proposal_stake: 0,
+            voting_scheme: VotingScheme::OnePersonOneVote,
+            slash_rejected_stake: false,
+            treasury: None,
+            min_voters: 0,
+            max_vote_weight_bps: 0,
+        };
+        client.init_governance(&Address::generate(&env), &config);
+        client.set_voting_supply(&1);
+        let prop_id = client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        client.cast_vote(&user, &prop_id, &VoteType::Against);
+
+        env.ledger().with_mut(|li| li.timestamp = 100);
+        let result = client.try_execute_proposal(&prop_id);
+        assert_eq!(result, Err(Ok(Error::VotingStillActive)));
+
+        env.ledger().with_mut(|li| li.timestamp = 149);
+        let result = client.try_execute_proposal(&prop_id);
+        assert_eq!(result, Err(Ok(Error::ExecutionDelayNotMet)));
+
+        env.ledger().with_mut(|li| li.timestamp = 150);
+        assert_eq!(client.execute_proposal(&prop_id), ProposalStatus::Rejected);
+    }
+
+    #[test]
+    fn test_execute_proposal_rejected_without_quorum() {
+        let env = Env::default();
+        let (client, user1, _, _) = setup_execution(&env);
+        let prop_id = client.create_proposal(
+            &user1,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        // 1 de 10 votos = 10% justo en el quórum; sin votos no llega
+        env.ledger().with_mut(|li| li.timestamp = 101);
+        assert_eq!(client.execute_proposal(&prop_id), ProposalStatus::Rejected);
+        let (_, topics, data) = env.events().all().last().unwrap();
+        let name: Symbol = topics.get(0).unwrap().into_val(&env);
+        assert_eq!(name, symbol_short!("prop_rej"));
+        let event: ProposalRejected = data.into_val(&env);
+        assert!(!event.quorum_met);
+
+        let result = client.try_execute_proposal(&prop_id);
+        assert_eq!(result, Err(Ok(Error::ProposalNotActive)));
+    }
+
+    #[test]
+    fn test_execute_proposal_rejected_below_threshold() {
+        let env = Env::default();
+        let (client, user1, user2, user3) = setup_execution(&env);
+        let prop_id = client.create_proposal(
+            &user1,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        client.cast_vote(&user1, &prop_id, &VoteType::For);
+        client.cast_vote(&user2, &prop_id, &VoteType::Against);
+        client.cast_vote(&user3, &prop_id, &VoteType::Against);
+
+        env.ledger().with_mut(|li| li.timestamp = 101);
+        assert_eq!(client.execute_proposal(&prop_id), ProposalStatus::Rejected);
+        let (_, _, data) = env.events().all().last().unwrap();
+        let event: ProposalRejected = data.into_val(&env);
+        assert!(event.quorum_met);
+        assert_eq!(
+            client.get_proposal(&prop_id).unwrap().status,
+            ProposalStatus::Rejected
+        );
+    }
+
+    #[test]
+    fn test_preview_outcome_follows_live_tallies() {
+        let env = Env::default();
+        let (client, user1, user2, user3) = setup_execution(&env);
+        let prop_id = client.create_proposal(
+            &user1,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        assert_eq!(
+            client.preview_outcome(&prop_id),
+            ProposalOutcome::FailQuorum
+        );
+
+        client.cast_vote(&user1, &prop_id, &VoteType::Against);
+        assert_eq!(
+            client.preview_outcome(&prop_id),
+            ProposalOutcome::FailThreshold
+        );
+
+        client.cast_vote(&user2, &prop_id, &VoteType::For);
+        client.cast_vote(&user3, &prop_id, &VoteType::For);
+        let before = client.get_proposal(&prop_id).unwrap();
+        assert_eq!(client.preview_outcome(&prop_id), ProposalOutcome::Pass);
+
+        // La vista previa no toca la propuesta
+        let after = client.get_proposal(&prop_id).unwrap();
+        assert_eq!(after.status, before.status);
+        assert_eq!(after.votes_for, 2);
+
+        client.cancel_proposal(&prop_id);
+        let result = client.try_preview_outcome(&prop_id);
+        assert_eq!(result, Err(Ok(Error::ProposalNotActive)));
+    }
+
+    #[test]
+    fn test_execute_proposal_rejected_below_min_voters() {
+        let env = Env::default();
+        let (client, token) = setup_with_scheme(&env, 0, VotingScheme::TokenWeighted);
+        let mut config = client.get_governance_config();
+        config.min_voters = 2;
+        client.set_governance_config(&config);
+        client.set_voting_supply(&1_000);
+        let whale = Address::generate(&env);
+        let minnow = Address::generate(&env);
+        token.mint(&whale, &900);
+        client.stake(&whale, &900);
+        token.mint(&minnow, &1);
+        client.stake(&minnow, &1);
+
+        let prop_id = client.create_proposal(
+            &whale,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        // 90% de participación, pero un solo votante
+        client.cast_vote(&whale, &prop_id, &VoteType::For);
+        assert_eq!(
+            client.preview_outcome(&prop_id),
+            ProposalOutcome::FailQuorum
+        );
+
+        let other_id = client.create_proposal(
+            &whale,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("other"),
+        );
+        client.cast_vote(&whale, &other_id, &VoteType::For);
+        client.cast_vote(&minnow, &other_id, &VoteType::Against);
+        assert_eq!(client.preview_outcome(&other_id), ProposalOutcome::Pass);
+
+        env.ledger().with_mut(|li| li.timestamp = 101);
+        assert_eq!(client.execute_proposal(&prop_id), ProposalStatus::Rejected);
+        let (_, _, data) = env.events().all().last().unwrap();
+        let event: ProposalRejected = data.into_val(&env);
+        assert!(!event.quorum_met);
+        assert_eq!(event.votes_for, 900);
+    }
+
+    #[test]
+    fn test_execute_proposal_requires_voting_supply() {
+        let env = Env::default();
+        let (client, _, user) = setup_test(&env);
+        let prop_id = client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        env.ledger().with_mut(|li| li.timestamp = 101);
+        let result = client.try_execute_proposal(&prop_id);
+        assert_eq!(result, Err(Ok(Error::VotingSupplyNotSet)));
+    }
+
+    #[test]
+    fn test_execute_proposal_unlocks_stake() {
+        let env = Env::default();
+        let (client, token) = setup_staking(&env, 500);
+        client.set_voting_supply(&10);
+        let proposer = Address::generate(&env);
+        token.mint(&proposer, &500);
+        client.stake(&proposer, &500);
+        let prop_id = client.create_proposal(
+            &proposer,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        assert_eq!(client.get_stake(&proposer), 0);
+
+        env.ledger().with_mut(|li| li.timestamp = 101);
+        client.execute_proposal(&prop_id);
+        assert_eq!(client.get_stake(&proposer), 500);
+    }
+
+    #[test]
+    fn test_change_vote_moves_power_between_buckets() {
+        let env = Env::default();
+        let (client, token) = setup_with_scheme(&env, 0, VotingScheme::TokenWeighted);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        token.mint(&alice, &400);
+        token.mint(&bob, &100);
+        client.stake(&alice, &400);
+        client.stake(&bob, &100);
+        let prop_id = client.create_proposal(
+            &alice,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        client.cast_vote(&alice, &prop_id, &VoteType::Abstain);
+        client.cast_vote(&bob, &prop_id, &VoteType::For);
+
+        // Abstain -> For
+        client.change_vote(&alice, &prop_id, &VoteType::For);
+        let proposal = client.get_proposal(&prop_id).unwrap();
+        assert_eq!(proposal.votes_for, 500);
+        assert_eq!(proposal.votes_abstain, 0);
+
+        // For -> Against
+        client.change_vote(&alice, &prop_id, &VoteType::Against);
+        let proposal = client.get_proposal(&prop_id).unwrap();
+        assert_eq!(proposal.votes_for, 100);
+        assert_eq!(proposal.votes_against, 400);
+        assert_eq!(proposal.votes_abstain, 0);
+        assert_eq!(proposal.total_votes, 2);
+        assert_eq!(
+            client.get_vote(&prop_id, &alice).unwrap().vote_type,
+            VoteType::Against
+        );
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        let name: Symbol = topics.get(0).unwrap().into_val(&env);
+        assert_eq!(name, symbol_short!("vote_chg"));
+        let event: VoteChanged = data.into_val(&env);
+        assert_eq!(event.old_vote, VoteType::For);
+        assert_eq!(event.new_vote, VoteType::Against);
+        assert_eq!(event.voting_power, 400);
+    }
+
+    #[test]
+    fn test_change_vote_requires_prior_vote_and_open_window() {
+        let env = Env::default();
+        let (client, _, user) = setup_test(&env);
+        let other = Address::generate(&env);
+        let prop_id = client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        client.cast_vote(&user, &prop_id, &VoteType::For);
+
+        let result = client.try_change_vote(&other, &prop_id, &VoteType::Against);
+        assert_eq!(result, Err(Ok(Error::NotVoted)));
+
+        env.ledger().with_mut(|li| li.timestamp = 101);
+        let result = client.try_change_vote(&user, &prop_id, &VoteType::Against);
+        assert_eq!(result, Err(Ok(Error::VotingEnded)));
+        assert_eq!(client.get_proposal(&prop_id).unwrap().votes_for, 1);
+    }
+
+    #[test]
+    fn test_delegate_votes_with_delegated_power() {
+        let env = Env::default();
+        let (client, token) = setup_with_scheme(&env, 0, VotingScheme::TokenWeighted);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        token.mint(&alice, &100);
+        token.mint(&bob, &250);
+        token.mint(&carol, &50);
+        client.stake(&alice, &100);
+        client.stake(&bob, &250);
+        client.stake(&carol, &50);
+
+        client.delegate(&bob, &alice);
+        client.delegate(&carol, &alice);
+        assert_eq!(client.get_delegate(&bob), Some(alice.clone()));
+        assert_eq!(client.get_delegated_power(&alice), 300);
+        assert_eq!(client.get_delegated_power(&bob), 0);
+
+        let prop_id = client.create_proposal(
+            &alice,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        let result = client.try_cast_vote(&bob, &prop_id, &VoteType::Against);
+        assert_eq!(result, Err(Ok(Error::VoteDelegated)));
+
+        client.cast_vote(&alice, &prop_id, &VoteType::For);
+        let proposal = client.get_proposal(&prop_id).unwrap();
+        assert_eq!(proposal.votes_for, 400);
+        assert_eq!(proposal.total_votes, 1);
+
+        // Retirar la delegación no permite votar dos veces la misma propuesta
+        client.undelegate(&bob);
+        assert_eq!(client.get_delegated_power(&alice), 50);
+        let result = client.try_cast_vote(&bob, &prop_id, &VoteType::Against);
+        assert_eq!(result, Err(Ok(Error::AlreadyVoted)));
+    }
+
+    #[test]
+    fn test_change_vote_moves_delegated_records() {
+        let env = Env::default();
+        let (client, token) = setup_with_scheme(&env, 0, VotingScheme::TokenWeighted);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        token.mint(&alice, &100);
+        token.mint(&bob, &250);
+        client.stake(&alice, &100);
+        client.stake(&bob, &250);
+        client.delegate(&bob, &alice);
+
+        let prop_id = client.create_proposal(
+            &alice,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        client.cast_vote(&alice, &prop_id, &VoteType::For);
+
+        let result = client.try_change_vote(&bob, &prop_id, &VoteType::Against);
+        assert_eq!(result, Err(Ok(Error::VoteDelegated)));
+        client.undelegate(&bob);
+        let result = client.try_change_vote(&bob, &prop_id, &VoteType::Against);
+        assert_eq!(result, Err(Ok(Error::VoteDelegated)));
+
+        client.delegate(&bob, &alice);
+        client.change_vote(&alice, &prop_id, &VoteType::Against);
+        let proposal = client.get_proposal(&prop_id).unwrap();
+        assert_eq!(proposal.votes_for, 0);
+        assert_eq!(proposal.votes_against, 350);
+        let delegated = client.get_vote(&prop_id, &bob).unwrap();
+        assert_eq!(delegated.vote_type, VoteType::Against);
+        assert_eq!(delegated.voting_power, 0);
+    }
+
+    #[test]
+    fn test_delegate_skips_delegators_who_already_voted() {
+        let env = Env::default();
+        let (client, _, alice) = setup_test(&env);
+        let bob = Address::generate(&env);
+        let prop_id = client.create_proposal(
+            &alice,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        client.cast_vote(&bob, &prop_id, &VoteType::Against);
+        client.delegate(&bob, &alice);
+        client.cast_vote(&alice, &prop_id, &VoteType::For);
+
+        let proposal = client.get_proposal(&prop_id).unwrap();
+        assert_eq!(proposal.votes_for, 1);
+        assert_eq!(proposal.votes_against, 1);
+        assert_eq!(
+            client.get_vote(&prop_id, &bob).unwrap().vote_type,
+            VoteType::Against
+        );
+    }
+
+    #[test]
+    fn test_delegation_cycle_rejected() {
+        let env = Env::default();
+        let (client, _, alice) = setup_test(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+
+        let result = client.try_delegate(&alice, &alice);
+        assert_eq!(result, Err(Ok(Error::DelegationCycle)));
+
+        client.delegate(&alice, &bob);
+        let result = client.try_delegate(&bob, &alice);
+        assert_eq!(result, Err(Ok(Error::DelegationCycle)));
+
+        client.delegate(&bob, &carol);
+        let result = client.try_delegate(&carol, &alice);
+        assert_eq!(result, Err(Ok(Error::DelegationCycle)));
+
+        // Cambiar de delegado mueve el poder
+        client.delegate(&alice, &carol);
+        assert_eq!(client.get_delegated_power(&bob), 0);
+        assert_eq!(client.get_delegated_power(&carol), 2);
+
+        let result = client.try_undelegate(&carol);
+        assert_eq!(result, Err(Ok(Error::NotDelegated)));
+    }
+
+    #[test]
+    fn test_cancel_proposal_returns_stake_and_discards_votes() {
+        let env = Env::default();
+        let (client, token) = setup_staking(&env, 500);
+        client.set_voting_supply(&10);
+        let proposer = Address::generate(&env);
+        let voter = Address::generate(&env);
+        token.mint(&proposer, &500);
+        client.stake(&proposer, &500);
+        let prop_id = client.create_proposal(
+            &proposer,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        client.cast_vote(&voter, &prop_id, &VoteType::For);
+
+        client.cancel_proposal(&prop_id);
+        assert_eq!(env.auths()[0].0, proposer);
+        assert_eq!(client.get_stake(&proposer), 500);
+
+        let proposal = client.get_proposal(&prop_id).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Cancelled);
+        assert_eq!(proposal.votes_for, 0);
+        assert_eq!(proposal.total_votes, 0);
+        assert!(client.get_vote(&prop_id, &voter).is_none());
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        let name: Symbol = topics.get(0).unwrap().into_val(&env);
+        assert_eq!(name, symbol_short!("prop_cncl"));
+        let event: ProposalCancelled = data.into_val(&env);
+        assert_eq!(event.stake_returned, 500);
+
+        // Ya no se puede votar, ejecutar ni finalizar
+        let result = client.try_cast_vote(&voter, &prop_id, &VoteType::For);
+        assert_eq!(result, Err(Ok(Error::ProposalNotActive)));
+        env.ledger().with_mut(|li| li.timestamp = 101);
+        let result = client.try_execute_proposal(&prop_id);
+        assert_eq!(result, Err(Ok(Error::ProposalNotActive)));
+        let result = client.try_finalize_proposal(&prop_id);
+        assert_eq!(result, Err(Ok(Error::ProposalNotActive)));
+    }
+
+    #[test]
+    fn test_cancel_proposal_only_while_open() {
+        let env = Env::default();
+        let (client, _, user) = setup_test(&env);
+        client.set_voting_supply(&1);
+        let prop_id = client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+        env.ledger().with_mut(|li| li.timestamp = 101);
+        client.execute_proposal(&prop_id);
+
+        let result = client.try_cancel_proposal(&prop_id);
+        assert_eq!(result, Err(Ok(Error::ProposalNotActive)));
+        let result = client.try_cancel_proposal(&9);
+        assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+    }
+
+    #[test]
+    fn test_cancel_proposal_requires_proposer_auth() {
+        let env = Env::default();
+        let (client, _, user) = setup_test(&env);
+        let prop_id = client.create_proposal(
+            &user,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+
+        env.mock_auths(&[]);
+        assert!(client.try_cancel_proposal(&prop_id).is_err());
+        assert_eq!(
+            client.get_proposal(&prop_id).unwrap().status,
+            ProposalStatus::Pending
+        );
+    }
+
+    #[test]
+    fn test_list_proposals_paginates() {
+        let env = Env::default();
+        let (client, _, user) = setup_test(&env);
+        for i in 0..5u8 {
+            client.create_proposal(
+                &user,
+                &BytesN::from_array(&env, &[i; 32]),
+                &symbol_short!("test"),
+            );
+        }
+
+        let page = client.list_proposals(&0, &2);
+        assert_eq!(page.proposals.len(), 2);
+        assert_eq!(page.proposals.get(1).unwrap().id, 1);
+        assert_eq!(page.next_cursor, Some(2));
+
+        let page = client.list_proposals(&4, &2);
+        assert_eq!(page.proposals.len(), 1);
+        assert_eq!(page.proposals.get(0).unwrap().id, 4);
+        assert_eq!(page.next_cursor, None);
+
+        let page = client.list_proposals(&9, &2);
+        assert_eq!(page.proposals.len(), 0);
+        assert_eq!(page.next_cursor, None);
+
+        // El límite se recorta a MAX_PROPOSAL_PAGE
+        let page = client.list_proposals(&0, &1_000);
+        assert_eq!(page.proposals.len(), 5);
+    }
+
+    #[test]
+    fn test_list_proposals_by_status() {
+        let env = Env::default();
+        let (client, _, user) = setup_test(&env);
+        for i in 0..4u8 {
+            client.create_proposal(
+                &user,
+                &BytesN::from_array(&env, &[i; 32]),
+                &symbol_short!("test"),
+            );
+        }
+        client.cancel_proposal(&1);
+        client.cancel_proposal(&3);
+
+        let page = client.list_proposals_by_status(&ProposalStatus::Cancelled, &0, &1);
+        assert_eq!(page.proposals.len(), 1);
+        assert_eq!(page.proposals.get(0).unwrap().id, 1);
+        assert_eq!(page.next_cursor, Some(2));
+
+        let page = client.list_proposals_by_status(
+            &ProposalStatus::Cancelled,
+            &page.next_cursor.unwrap(),
+            &1,
+        );
+        assert_eq!(page.proposals.get(0).unwrap().id, 3);
+        assert_eq!(page.next_cursor, None);
+
+        let page = client.list_proposals_by_status(&ProposalStatus::Pending, &0, &10);
+        assert_eq!(page.proposals.len(), 2);
+        assert_eq!(page.proposals.get(1).unwrap().id, 2);
+    }
+
+    #[test]
+    fn test_rejected_proposal_stake_slashed_to_treasury() {
+        let env = Env::default();
+        let (client, token) = setup_staking(&env, 500);
+        let treasury = Address::generate(&env);
+        let mut config = client.get_governance_config();
+        config.slash_rejected_stake = true;
+        client.set_voting_supply(&10);
+
+        let proposer = Address::generate(&env);
+        token.mint(&proposer, &500);
+        client.stake(&proposer, &500);
+        let prop_id = client.create_proposal(
+            &proposer,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+
+        // Sin tesoro no se puede activar la penalización
+        let result = client.try_set_governance_config(&config);
+        assert_eq!(result, Err(Ok(Error::TreasuryNotSet)));
+        config.treasury = Some(treasury.clone());
+        client.set_governance_config(&config);
+
+        env.ledger().with_mut(|li| li.timestamp = 101);
+        let status = client.execute_proposal(&prop_id);
+        assert_eq!(status, ProposalStatus::Rejected);
+        assert_eq!(client.get_stake(&proposer), 0);
+        assert_eq!(
+            token::Client::new(&env, &token.address).balance(&treasury),
+            500
+        );
+
+        // El evento de penalización precede al de rechazo
+        let events = env.events().all();
+        let (_, topics, data) = events.get(events.len() - 2).unwrap();
+        let name: Symbol = topics.get(0).unwrap().into_val(&env);
+        assert_eq!(name, symbol_short!("stk_slash"));
+        let event: StakeSlashed = data.into_val(&env);
+        assert_eq!(event.amount, 500);
+        assert_eq!(event.proposer, proposer);
+    }
+
+    #[test]
+    fn test_finalize_rejected_returns_stake_without_slashing() {
+        let env = Env::default();
+        let (client, token) = setup_staking(&env, 500);
+        let proposer = Address::generate(&env);
+        token.mint(&proposer, &500);
+        client.stake(&proposer, &500);
+        let prop_id = client.create_proposal(
+            &proposer,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &symbol_short!("test"),
+        );
+
+        env.ledger().with_mut(|li| li.timestamp = 101);
+        let status = client.finalize_proposal(&prop_id);
+        assert_eq!(status, ProposalStatus::Rejected);
+        assert_eq!(client.get_stake(&proposer), 500);
+    }
+
+    #[test]
+    fn test_set_governance_config_validates_thresholds() {
+        let env = Env::default();
+        let (client, _, _) = setup_test(&env);
+        let mut config = client.get_governance_config();
+
+        config.quorum_percentage = 10001;
+        let result = client.try_set_governance_config(&config);
+        assert_eq!(result, Err(Ok(Error::InvalidThreshold)));
+
+        config.quorum_percentage = 10000;
+        config.approval_threshold = 4999;
+        let result = client.try_set_governance_config(&config);
+        assert_eq!(result, Err(Ok(Error::ThresholdTooLow)));
+
+        config.approval_threshold = 10000;
+        client.set_governance_config(&config);
+        assert_eq!(client.get_governance_config().quorum_percentage, 10000);
+
+        env.mock_auths(&[]);
+        assert!(client.try_set_governance_config(&config).is_err());
+    }
 }